@@ -0,0 +1,193 @@
+use pulsar_admin_sdk::apis::configuration::Configuration;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::pulsar_admin;
+use crate::update::{Namespace, Tenant, Topic, TopicStats};
+
+struct Cached<V> {
+    fetched_at: Instant,
+    value: V,
+}
+
+impl<V: Clone> Cached<V> {
+    fn new(value: V) -> Cached<V> {
+        Cached {
+            fetched_at: Instant::now(),
+            value,
+        }
+    }
+
+    fn fresh(&self, ttl: Duration) -> Option<V> {
+        (self.fetched_at.elapsed() < ttl).then(|| self.value.clone())
+    }
+}
+
+#[derive(Default)]
+struct Entries {
+    tenants: Option<Cached<Vec<Tenant>>>,
+    // By tenant.
+    namespaces: HashMap<String, Cached<Vec<Namespace>>>,
+    // By `tenant/namespace`.
+    topics: HashMap<String, Cached<Vec<Topic>>>,
+    // By `tenant/namespace/topic`.
+    topic_stats: HashMap<String, Cached<TopicStats>>,
+}
+
+// Admin API responses of the lists and topic stats, reused for `ttl` so walking back and forth
+// does not ask a slow admin API again. Clones share the entries, so background stats fetches
+// fill it too. A zero ttl disables it.
+#[derive(Clone)]
+pub struct AdminCache {
+    ttl: Duration,
+    entries: Arc<Mutex<Entries>>,
+}
+
+impl AdminCache {
+    pub fn new(ttl: Duration) -> AdminCache {
+        AdminCache {
+            ttl,
+            entries: Arc::new(Mutex::new(Entries::default())),
+        }
+    }
+
+    fn entries(&self) -> MutexGuard<'_, Entries> {
+        self.entries
+            .lock()
+            .expect("cache lock must not be poisoned")
+    }
+
+    pub fn clear(&self) {
+        *self.entries() = Entries::default();
+    }
+
+    pub fn invalidate_tenants(&self) {
+        self.entries().tenants = None;
+    }
+
+    pub fn invalidate_tenant(&self, tenant: &str) {
+        let prefix = format!("{}/", tenant);
+        let mut entries = self.entries();
+        entries.namespaces.remove(tenant);
+        entries
+            .topics
+            .retain(|key, _| !key.starts_with(&prefix));
+        entries
+            .topic_stats
+            .retain(|key, _| !key.starts_with(&prefix));
+    }
+
+    pub fn invalidate_namespace(&self, tenant: &str, namespace: &str) {
+        let key = format!("{}/{}", tenant, namespace);
+        let prefix = format!("{}/", key);
+        let mut entries = self.entries();
+        entries.topics.remove(&key);
+        entries
+            .topic_stats
+            .retain(|key, _| !key.starts_with(&prefix));
+    }
+
+    pub async fn tenants(&self, cfg: &Configuration) -> anyhow::Result<Vec<Tenant>> {
+        let cached = self
+            .entries()
+            .tenants
+            .as_ref()
+            .and_then(|tenants| tenants.fresh(self.ttl));
+        if let Some(tenants) = cached {
+            return Ok(tenants);
+        }
+
+        let tenants = pulsar_admin::fetch_tenants(cfg).await?;
+        self.entries().tenants = Some(Cached::new(tenants.clone()));
+
+        Ok(tenants)
+    }
+
+    pub async fn namespaces(
+        &self,
+        tenant: &str,
+        cfg: &Configuration,
+    ) -> anyhow::Result<Vec<Namespace>> {
+        let cached = self
+            .entries()
+            .namespaces
+            .get(tenant)
+            .and_then(|namespaces| namespaces.fresh(self.ttl));
+        if let Some(namespaces) = cached {
+            return Ok(namespaces);
+        }
+
+        let namespaces = pulsar_admin::fetch_namespaces(tenant, cfg).await?;
+        self.entries()
+            .namespaces
+            .insert(tenant.to_string(), Cached::new(namespaces.clone()));
+
+        Ok(namespaces)
+    }
+
+    pub async fn topics(
+        &self,
+        tenant: &str,
+        namespace: &str,
+        cfg: &Configuration,
+    ) -> anyhow::Result<Vec<Topic>> {
+        let key = format!("{}/{}", tenant, namespace);
+        let cached = self
+            .entries()
+            .topics
+            .get(&key)
+            .and_then(|topics| topics.fresh(self.ttl));
+        if let Some(topics) = cached {
+            return Ok(topics);
+        }
+
+        let topics = pulsar_admin::fetch_topics(tenant, namespace, cfg).await?;
+        self.entries()
+            .topics
+            .insert(key, Cached::new(topics.clone()));
+
+        Ok(topics)
+    }
+
+    // Only the topics missing from the cache are fetched, `concurrency` at a time.
+    pub async fn topics_stats(
+        &self,
+        tenant: &str,
+        namespace: &str,
+        topics: Vec<String>,
+        cfg: &Configuration,
+        concurrency: usize,
+    ) -> BTreeMap<String, TopicStats> {
+        let key = |topic: &str| format!("{}/{}/{}", tenant, namespace, topic);
+        let mut stats = BTreeMap::new();
+        let mut missing = vec![];
+        {
+            let entries = self.entries();
+            for topic in topics {
+                match entries
+                    .topic_stats
+                    .get(&key(&topic))
+                    .and_then(|stats| stats.fresh(self.ttl))
+                {
+                    Some(cached) => {
+                        stats.insert(topic, cached);
+                    }
+                    None => missing.push(topic),
+                }
+            }
+        }
+
+        let fetched =
+            pulsar_admin::fetch_topics_stats(tenant, namespace, missing, cfg, concurrency).await;
+        let mut entries = self.entries();
+        for (topic, topic_stats) in fetched {
+            entries
+                .topic_stats
+                .insert(key(&topic), Cached::new(topic_stats.clone()));
+            stats.insert(topic, topic_stats);
+        }
+
+        stats
+    }
+}
@@ -1,17 +1,190 @@
-use anyhow::Result;
-use reqwest::{header::{HeaderMap, ACCEPT, CONTENT_TYPE}, Url};
+use anyhow::{anyhow, Result};
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, TimeDelta, Utc};
+use itertools::Itertools;
+use reqwest::{
+    header::{HeaderMap, ACCEPT, CONTENT_TYPE},
+    Url,
+};
 use serde::{Deserialize, Serialize};
-use std::{fs, path::PathBuf};
+use std::{collections::BTreeMap, fs, path::PathBuf, time::Duration};
+
+pub const DEFAULT_CONTEXT: &str = "default";
+const KEYRING_PREFIX: &str = "keyring:";
+const KEYRING_SERVICE: &str = "lgm";
 
 #[derive(Deserialize, Debug)]
 pub struct Config {
     pub pulsar_url: String,
     pub pulsar_admin_url: String,
     pub default_tenant: String,
+    pub default_namespace: Option<String>,
+    // `tenant/namespace/topic[/subscription]` paths kept on top and listed in the bookmarks.
+    #[serde(default)]
+    pub bookmarks: Vec<String>,
+    // Disables load generating commands such as the publish benchmark.
+    #[serde(default)]
+    pub production: bool,
+    // Destructive commands ask to type the resource name instead of a plain confirmation.
+    #[serde(default)]
+    pub typed_confirmation: bool,
+    #[serde(default = "default_freshness_threshold_secs")]
+    pub freshness_threshold_secs: u64,
+    // Fetched tenants, namespaces, topics and topic stats are reused for this long, `r` refetches
+    // the current view right away. 0 disables the cache.
+    #[serde(default = "default_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+    #[serde(default)]
+    pub auto_refresh: bool,
+    #[serde(
+        default = "default_auto_refresh_secs",
+        alias = "subscriptions_refresh_secs"
+    )]
+    pub auto_refresh_secs: u64,
+    #[serde(default = "default_max_captured_messages")]
+    pub max_captured_messages: usize,
+    // Interval of the crash recovery checkpoints taken while listening, 0 disables them.
+    #[serde(default = "default_journal_interval_secs")]
+    pub journal_interval_secs: u64,
+    #[serde(default = "default_timestamp_format")]
+    pub timestamp_format: String,
+    pub stats_export: Option<StatsExport>,
+    #[serde(default)]
+    pub columns: Columns,
+    // Descriptors of protobuf topics, Pulsar does not keep them for plain bytes topics.
+    #[serde(default)]
+    pub protobuf: Vec<ProtobufMapping>,
+    #[serde(default)]
+    pub dead_letter: Vec<DeadLetterPolicy>,
+    #[serde(default)]
+    pub contexts: BTreeMap<String, Context>,
     pub auth: Auth,
 }
 
-#[derive(Deserialize, Debug)]
+impl Config {
+    pub fn contexts(&self) -> Vec<(String, Context)> {
+        let default = Context {
+            pulsar_url: self.pulsar_url.clone(),
+            pulsar_admin_url: self.pulsar_admin_url.clone(),
+            default_tenant: self.default_tenant.clone(),
+            default_namespace: self.default_namespace.clone(),
+            production: self.production,
+            auth: self.auth.clone(),
+        };
+
+        std::iter::once((DEFAULT_CONTEXT.to_string(), default))
+            .chain(
+                self.contexts
+                    .iter()
+                    .filter(|(name, _)| name.as_str() != DEFAULT_CONTEXT)
+                    .map(|(name, context)| (name.clone(), context.clone())),
+            )
+            .collect()
+    }
+
+    pub fn context(&self, name: &str) -> Result<Context> {
+        let contexts = self.contexts();
+        let names = contexts
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .join(", ");
+
+        contexts
+            .iter()
+            .find(|(candidate, _)| candidate == name)
+            .map(|(_, context)| context.clone())
+            .ok_or(anyhow!(
+                "No context '{}' in config, expected one of: {}",
+                name,
+                names
+            ))
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Context {
+    pub pulsar_url: String,
+    pub pulsar_admin_url: String,
+    pub default_tenant: String,
+    pub default_namespace: Option<String>,
+    #[serde(default)]
+    pub production: bool,
+    pub auth: Auth,
+}
+
+// Columns shown in the tables, every one of them when a table is absent. The name column is always
+// shown.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct Columns {
+    pub subscriptions: Option<Vec<String>>,
+    pub consumers: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct StatsExport {
+    pub dir: PathBuf,
+    #[serde(default = "default_stats_export_interval_secs")]
+    pub interval_secs: u64,
+    // `tenant/namespace` pairs to export, the default namespace when empty.
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProtobufMapping {
+    // `tenant/namespace/topic`
+    pub topic: String,
+    // Compiled `FileDescriptorSet`, as written by `protoc --include_imports --descriptor_set_out`.
+    pub descriptor: PathBuf,
+    pub message_type: String,
+}
+
+// Mirrors the `DeadLetterPolicy` a subscription's consumers are built with, which the broker does
+// not know about. Subscriptions without one are assumed to use the client default names.
+#[derive(Deserialize, Debug, Clone)]
+pub struct DeadLetterPolicy {
+    // `tenant/namespace/topic`
+    pub topic: String,
+    pub subscription: String,
+    // `tenant/namespace/topic`
+    pub dead_letter_topic: Option<String>,
+    // `tenant/namespace/topic`
+    pub retry_letter_topic: Option<String>,
+}
+
+fn default_freshness_threshold_secs() -> u64 {
+    60
+}
+
+fn default_cache_ttl_secs() -> u64 {
+    30
+}
+
+fn default_auto_refresh_secs() -> u64 {
+    5
+}
+
+fn default_max_captured_messages() -> usize {
+    10_000
+}
+
+fn default_journal_interval_secs() -> u64 {
+    30
+}
+
+fn default_timestamp_format() -> String {
+    "%H:%M:%S".to_string()
+}
+
+fn default_stats_export_interval_secs() -> u64 {
+    300
+}
+
+#[derive(Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
 #[serde(tag = "type", content = "args")]
 pub enum Auth {
     Token {
@@ -22,6 +195,17 @@ pub enum Auth {
         audience: String,
         credentials_file_url: String,
     },
+    // Shell command printing the token, run again shortly before the token expires.
+    #[serde(rename = "token_cmd", alias = "TokenCmd")]
+    TokenCmd {
+        command: String,
+    },
+}
+
+impl Auth {
+    pub fn refreshable(&self) -> bool {
+        matches!(self, Auth::TokenCmd { .. } | Auth::OAuth { .. })
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -38,16 +222,97 @@ struct AuthData {
     audience: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<i64>,
+}
+
+#[derive(Clone)]
 pub struct Token {
     pub access_token: String,
+    // From the OAuth `expires_in`, else the `exp` claim when the token is a JWT.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl Token {
+    fn new(access_token: String) -> Token {
+        Token { access_token, expires_at: None }
+    }
+
+    pub fn expires_within(&self, margin: Duration) -> bool {
+        self.expires_at.is_some_and(|expires_at| {
+            expires_at - TimeDelta::from_std(margin).unwrap_or_default() <= Utc::now()
+        })
+    }
+}
+
+fn jwt_expiry(token: &str) -> Option<DateTime<Utc>> {
+    #[derive(Deserialize)]
+    struct Claims {
+        exp: i64,
+    }
+
+    let payload = token.split('.').nth(1)?;
+    let claims = URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    let claims: Claims = serde_json::from_slice(&claims).ok()?;
+
+    DateTime::from_timestamp(claims.exp, 0)
+}
+
+// Contents of a `file://` url, or of a base64 `data:` url as keyring credentials are passed on.
+fn read_credentials(url: &str) -> anyhow::Result<String> {
+    let url = Url::parse(url)?;
+    match url.scheme() {
+        "data" => {
+            let (_, data) = url
+                .path()
+                .split_once(',')
+                .ok_or(anyhow!("Invalid credentials data url"))?;
+            Ok(String::from_utf8(STANDARD.decode(data)?)?)
+        }
+        _ => Ok(fs::read_to_string(url.path())?),
+    }
+}
+
+async fn run_token_command(command: &str) -> anyhow::Result<String> {
+    let output = if cfg!(windows) {
+        tokio::process::Command::new("cmd")
+            .args(["/C", command])
+            .output()
+            .await
+    } else {
+        tokio::process::Command::new("sh")
+            .args(["-c", command])
+            .output()
+            .await
+    }
+    .map_err(|err| anyhow!("Failed to run token command '{}': '{}'", command, err))?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Token command '{}' failed with {}: '{}'",
+            command,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let token = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .to_string();
+    if token.is_empty() {
+        return Err(anyhow!("Token command '{}' printed nothing", command));
+    }
+
+    Ok(token)
 }
 
-pub async fn auth(cfg: Config) -> anyhow::Result<Token> {
-    let token = match cfg.auth {
-        Auth::Token { token } => Token {
-            access_token: token,
-        },
+pub async fn auth(auth: &Auth) -> anyhow::Result<Token> {
+    let mut token = match auth.clone() {
+        Auth::Token { token } => Token::new(token),
+        Auth::TokenCmd { command } => Token::new(run_token_command(&command).await?),
         Auth::OAuth {
             issuer_url,
             audience,
@@ -55,9 +320,8 @@ pub async fn auth(cfg: Config) -> anyhow::Result<Token> {
         } => {
             let client = reqwest::Client::new();
 
-            let credentials_url = Url::parse(credentials_file_url.as_str())?;
             let creds: OAuth2PrivateParams =
-                serde_json::from_str(fs::read_to_string(credentials_url.path())?.as_str())?;
+                serde_json::from_str(&read_credentials(&credentials_file_url)?)?;
 
             let auth_data = AuthData {
                 grant_type: "client_credentials".to_string(),
@@ -80,15 +344,353 @@ pub async fn auth(cfg: Config) -> anyhow::Result<Token> {
                 .send()
                 .await?;
 
-            response.json().await?
+            let response: TokenResponse = response.json().await?;
+            Token {
+                access_token: response.access_token,
+                expires_at: response
+                    .expires_in
+                    .map(|expires_in| Utc::now() + TimeDelta::seconds(expires_in)),
+            }
         }
     };
+    token.expires_at = token
+        .expires_at
+        .or_else(|| jwt_expiry(&token.access_token));
 
     Ok(token)
 }
 
 pub fn read_config(path: PathBuf) -> Result<Config> {
-    let contents = fs::read_to_string(path)?;
-    let config: Config = toml::from_str(&contents)?;
+    let contents = fs::read_to_string(&path)
+        .map_err(|err| anyhow!("Failed to read config '{}': '{}'", path.display(), err))?;
+    // Parsed before the expansion too, so shape errors point at their line
+    toml::from_str::<Config>(&contents)
+        .map_err(|err| anyhow!("Invalid config '{}'\n{}", path.display(), err))?;
+
+    let mut value: toml::Value = toml::from_str(&contents)?;
+    expand_env(&mut value, "")
+        .map_err(|err| anyhow!("Invalid config '{}'\n{}", path.display(), err))?;
+    let config: Config = value
+        .try_into()
+        .map_err(|err| anyhow!("Invalid config '{}'\n{}", path.display(), err))?;
+
+    let problems = config.problems();
+    if !problems.is_empty() {
+        return Err(anyhow!(
+            "Invalid config '{}'\n{}",
+            path.display(),
+            problems
+                .iter()
+                .map(|problem| format!("  - {}", problem))
+                .join("\n")
+        ));
+    }
+
     Ok(config)
 }
+
+impl Config {
+    fn problems(&self) -> Vec<String> {
+        let mut problems = vec![];
+        for (name, context) in self.contexts() {
+            let prefix = if name == DEFAULT_CONTEXT {
+                String::new()
+            } else {
+                format!("contexts.{}.", name)
+            };
+            problems.extend(context.problems(&prefix));
+        }
+
+        if StrftimeItems::new(&self.timestamp_format).any(|item| matches!(item, Item::Error)) {
+            problems.push(format!(
+                "timestamp_format: '{}' is not a valid chrono format",
+                self.timestamp_format
+            ));
+        }
+        if let Some(export) = &self.stats_export {
+            if export.interval_secs == 0 {
+                problems.push("stats_export.interval_secs: must be above 0".to_string());
+            }
+            for namespace in &export.namespaces {
+                match namespace.split_once('/') {
+                    Some((tenant, name)) if !tenant.is_empty() && !name.is_empty() => {}
+                    _ => problems.push(format!(
+                        "stats_export.namespaces: '{}' is not a tenant/namespace pair",
+                        namespace
+                    )),
+                }
+            }
+        }
+        let is_topic_path = |topic: &str| {
+            let parts = topic.split('/').collect_vec();
+            parts.len() == 3 && parts.iter().all(|part| !part.is_empty())
+        };
+        for policy in &self.dead_letter {
+            let topics = [
+                ("topic", Some(&policy.topic)),
+                ("dead_letter_topic", policy.dead_letter_topic.as_ref()),
+                ("retry_letter_topic", policy.retry_letter_topic.as_ref()),
+            ];
+            for (key, topic) in topics {
+                if let Some(topic) = topic.filter(|topic| !is_topic_path(topic)) {
+                    problems.push(format!(
+                        "dead_letter.{}: '{}' is not a tenant/namespace/topic path",
+                        key, topic
+                    ));
+                }
+            }
+        }
+
+        problems
+    }
+}
+
+impl Context {
+    fn problems(&self, prefix: &str) -> Vec<String> {
+        let mut problems = vec![];
+        let mut check_url = |key: &str, url: &str, schemes: &[&str]| {
+            let expected = schemes
+                .iter()
+                .map(|scheme| format!("{}://", scheme))
+                .join(" or ");
+            match Url::parse(url) {
+                Ok(parsed) if schemes.contains(&parsed.scheme()) => {}
+                Ok(parsed) => problems.push(format!(
+                    "{}{}: '{}' has scheme '{}', expected {}",
+                    prefix,
+                    key,
+                    url,
+                    parsed.scheme(),
+                    expected
+                )),
+                Err(err) => problems.push(format!(
+                    "{}{}: '{}' is not a url ({}), expected {}",
+                    prefix, key, url, err, expected
+                )),
+            }
+        };
+
+        check_url("pulsar_url", &self.pulsar_url, &["pulsar", "pulsar+ssl"]);
+        check_url(
+            "pulsar_admin_url",
+            &self.pulsar_admin_url,
+            &["http", "https"],
+        );
+        if let Auth::OAuth {
+            issuer_url,
+            credentials_file_url,
+            ..
+        } = &self.auth
+        {
+            check_url("auth.args.issuer_url", issuer_url, &["http", "https"]);
+            check_url(
+                "auth.args.credentials_file_url",
+                credentials_file_url,
+                &["file", "data"],
+            );
+        }
+
+        if self.default_tenant.trim().is_empty() {
+            problems.push(format!("{}default_tenant: must not be empty", prefix));
+        }
+        match &self.auth {
+            Auth::OAuth { audience, .. } if audience.trim().is_empty() => {
+                problems.push(format!("{}auth.args.audience: must not be empty", prefix))
+            }
+            Auth::TokenCmd { command } if command.trim().is_empty() => {
+                problems.push(format!("{}auth.args.command: must not be empty", prefix))
+            }
+            _ => {}
+        }
+
+        problems
+    }
+}
+
+// Replaces `${NAME}` in every string of the config with the environment variable and
+// `keyring:NAME` values with the secret stored by `--store-secret NAME`, so secrets can stay out of
+// the file. `$${` stands for a literal `${`.
+fn expand_env(value: &mut toml::Value, key: &str) -> Result<()> {
+    let child = |name: &str| {
+        if key.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", key, name)
+        }
+    };
+
+    match value {
+        toml::Value::String(string) => {
+            *string = match string.strip_prefix(KEYRING_PREFIX) {
+                // The stored OAuth credentials file is handed on inline
+                Some(name) if key.ends_with("credentials_file_url") => read_secret(name)
+                    .map(|json| format!("data:application/json;base64,{}", STANDARD.encode(json))),
+                _ => resolve_value(string),
+            }
+            .map_err(|err| anyhow!("  - {}: {}", key, err))?
+        }
+        toml::Value::Array(values) => values
+            .iter_mut()
+            .try_for_each(|value| expand_env(value, key))?,
+        toml::Value::Table(table) => table
+            .iter_mut()
+            .try_for_each(|(name, value)| expand_env(value, &child(name)))?,
+        _ => {}
+    }
+
+    Ok(())
+}
+
+// A `keyring:NAME` value read from the keyring, otherwise with its `${NAME}` references expanded.
+pub fn resolve_value(value: &str) -> Result<String> {
+    match value.strip_prefix(KEYRING_PREFIX) {
+        Some(name) => read_secret(name),
+        None => expand_env_vars(value),
+    }
+}
+
+fn read_secret(name: &str) -> Result<String> {
+    keyring::Entry::new(KEYRING_SERVICE, name)
+        .and_then(|entry| entry.get_password())
+        .map_err(|err| anyhow!("secret '{}' can't be read from the keyring: {}", name, err))
+}
+
+pub fn store_secret(name: &str, secret: &str) -> Result<()> {
+    keyring::Entry::new(KEYRING_SERVICE, name)
+        .and_then(|entry| entry.set_password(secret))
+        .map_err(|err| {
+            anyhow!(
+                "Failed to store secret '{}' in the keyring: '{}'",
+                name,
+                err
+            )
+        })
+}
+
+fn expand_env_vars(string: &str) -> Result<String> {
+    let mut expanded = String::new();
+    let mut rest = string;
+    while let Some(start) = rest.find("${") {
+        if rest[..start].ends_with('$') {
+            expanded.push_str(&rest[..start - 1]);
+            expanded.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        expanded.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or(anyhow!("unterminated '${{' in '{}'", string))?;
+        let name = &rest[start + 2..start + end];
+        let value = std::env::var(name)
+            .map_err(|err| anyhow!("environment variable '{}' can't be read: {}", name, err))?;
+        expanded.push_str(&value);
+        rest = &rest[start + end + 1..];
+    }
+    expanded.push_str(rest);
+
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(extra: &str) -> Config {
+        toml::from_str(&format!(
+            r#"
+            pulsar_url = "pulsar://localhost:6650"
+            pulsar_admin_url = "http://localhost:8080"
+            default_tenant = "public"
+            auth = {{ type = "Token", args = {{ token = "secret" }} }}
+            {}
+            "#,
+            extra
+        ))
+        .expect("config must parse")
+    }
+
+    #[test]
+    fn valid_config_has_no_problems() {
+        assert!(config("").problems().is_empty());
+    }
+
+    #[test]
+    fn lists_every_problem_with_its_key() {
+        let config = config(
+            r#"
+            timestamp_format = "%Q"
+
+            [stats_export]
+            dir = "/tmp/lgm"
+            interval_secs = 0
+            namespaces = ["public"]
+
+            [contexts.staging]
+            pulsar_url = "http://localhost:6650"
+            pulsar_admin_url = "localhost"
+            default_tenant = " "
+            auth = { type = "Token", args = { token = "secret" } }
+            "#,
+        );
+
+        let problems = config.problems();
+        let keys = problems
+            .iter()
+            .map(|problem| problem.split(':').next().unwrap_or_default())
+            .collect_vec();
+        assert_eq!(
+            keys,
+            vec![
+                "contexts.staging.pulsar_url",
+                "contexts.staging.pulsar_admin_url",
+                "contexts.staging.default_tenant",
+                "timestamp_format",
+                "stats_export.interval_secs",
+                "stats_export.namespaces",
+            ],
+            "{:?}",
+            problems
+        );
+    }
+
+    #[test]
+    fn expands_environment_variables_in_every_string() {
+        std::env::set_var("LGM_TEST_TOKEN", "from-env");
+        let mut value: toml::Value = toml::from_str(
+            r#"
+            pulsar_url = "pulsar://${LGM_TEST_TOKEN}:6650"
+            literal = "$${LGM_TEST_TOKEN}"
+            [auth.args]
+            tokens = ["${LGM_TEST_TOKEN}"]
+            "#,
+        )
+        .unwrap();
+
+        expand_env(&mut value, "").unwrap();
+
+        assert_eq!(value["pulsar_url"].as_str(), Some("pulsar://from-env:6650"));
+        assert_eq!(value["literal"].as_str(), Some("${LGM_TEST_TOKEN}"));
+        assert_eq!(
+            value["auth"]["args"]["tokens"][0].as_str(),
+            Some("from-env")
+        );
+    }
+
+    #[test]
+    fn names_the_key_of_an_unexpandable_value() {
+        let mut value: toml::Value = toml::from_str(
+            r#"
+            [auth.args]
+            token = "${LGM_TEST_UNSET}"
+            "#,
+        )
+        .unwrap();
+
+        let err = expand_env(&mut value, "")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("auth.args.token"), "{}", err);
+        assert!(expand_env_vars("${LGM_TEST_TOKEN").is_err());
+    }
+}
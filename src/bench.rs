@@ -0,0 +1,484 @@
+use anyhow::anyhow;
+use chrono::Utc;
+use futures::stream::FuturesUnordered;
+use futures::{StreamExt, TryStreamExt};
+use itertools::Itertools;
+use pulsar::consumer::InitialPosition;
+use pulsar::producer::Message;
+use pulsar::{Consumer, ConsumerOptions, Pulsar, TokioExecutor};
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::tasks::ShutdownSignal;
+use crate::AppEvent;
+
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+// Far above what a single producer sustains, keeps the send interval above zero.
+const MAX_RATE: u32 = 1_000_000;
+
+#[derive(Clone, Copy, Debug)]
+pub enum BenchLength {
+    Count(usize),
+    Duration(Duration),
+}
+
+impl BenchLength {
+    pub fn parse(input: &str) -> anyhow::Result<BenchLength> {
+        let duration = |value: &str, unit: u64| {
+            value
+                .parse::<u64>()
+                .map(|value| BenchLength::Duration(Duration::from_secs(value * unit)))
+        };
+        let length = match input {
+            seconds if seconds.ends_with('s') => duration(&seconds[..seconds.len() - 1], 1)?,
+            minutes if minutes.ends_with('m') => duration(&minutes[..minutes.len() - 1], 60)?,
+            count => BenchLength::Count(count.parse()?),
+        };
+
+        match length {
+            BenchLength::Count(0) => Err(anyhow!("Count must be positive")),
+            BenchLength::Duration(duration) if duration.is_zero() => {
+                Err(anyhow!("Duration must be positive"))
+            }
+            length => Ok(length),
+        }
+    }
+
+    fn is_done(&self, sent: usize, elapsed: Duration) -> bool {
+        match self {
+            BenchLength::Count(count) => sent >= *count,
+            BenchLength::Duration(duration) => elapsed >= *duration,
+        }
+    }
+}
+
+// Keys set on the published messages, to exercise key based routing and Key_Shared dispatch.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyDistribution {
+    None,
+    RoundRobin(usize),
+    Random,
+}
+
+impl KeyDistribution {
+    fn key(&self, seq: usize) -> Option<String> {
+        match self {
+            KeyDistribution::None => None,
+            KeyDistribution::RoundRobin(keys) => Some(format!("key-{}", seq % keys)),
+            KeyDistribution::Random => Some(Uuid::new_v4().to_string()),
+        }
+    }
+}
+
+// Parameters of a publish benchmark, entered as
+// `<count|duration> <size bytes> <rate msg/s> [keys=<n>|random] [template=<payload>]`.
+#[derive(Clone, Debug)]
+pub struct BenchSpec {
+    pub length: BenchLength,
+    pub size: usize,
+    pub rate: u32,
+    pub keys: KeyDistribution,
+    // Payload with `{seq}`, `{now}` (epoch millis) and `{uuid}` placeholders, replaces the
+    // fixed size filler.
+    pub template: Option<String>,
+}
+
+impl BenchSpec {
+    pub const DEFAULT_INPUT: &'static str = "1000 1024 100";
+    pub const USAGE: &'static str =
+        "<count|duration> <size bytes> <rate msg/s> [keys=<n>|random] [template=<payload>]";
+
+    pub fn parse(input: &str) -> anyhow::Result<BenchSpec> {
+        // The template may contain spaces, it takes the rest of the input
+        let (input, template) = match input.split_once("template=") {
+            Some((input, template)) => (input, Some(template.to_string())),
+            None => (input, None),
+        };
+        let mut tokens = input.split_whitespace();
+        let (length, size, rate) = tokens.next_tuple().ok_or(anyhow!(
+            "Expected '{}', got '{}'",
+            BenchSpec::USAGE,
+            input
+        ))?;
+
+        let mut spec = BenchSpec {
+            length: BenchLength::parse(length)?,
+            size: size.parse()?,
+            rate: rate.parse()?,
+            keys: KeyDistribution::None,
+            template,
+        };
+
+        for option in tokens {
+            spec.keys = match option.strip_prefix("keys=") {
+                Some("random") => KeyDistribution::Random,
+                Some(keys) => match keys.parse() {
+                    Ok(0) | Err(_) => {
+                        return Err(anyhow!("Expected a positive key count, got '{}'", keys))
+                    }
+                    Ok(keys) => KeyDistribution::RoundRobin(keys),
+                },
+                None => return Err(anyhow!("Unknown option '{}'", option)),
+            };
+        }
+
+        if !(1..=MAX_RATE).contains(&spec.rate) {
+            return Err(anyhow!("Rate must be between 1 and {} msg/s", MAX_RATE));
+        }
+
+        Ok(spec)
+    }
+
+    fn payload(&self, seq: usize) -> Vec<u8> {
+        match &self.template {
+            Some(template) => template
+                .replace("{seq}", &seq.to_string())
+                .replace("{now}", &Utc::now().timestamp_millis().to_string())
+                .replace("{uuid}", &Uuid::new_v4().to_string())
+                .into_bytes(),
+            None => vec![b'x'; self.size],
+        }
+    }
+}
+
+impl fmt::Display for BenchSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let payload = match self.template {
+            Some(_) => "templated".to_string(),
+            None => format!("{}B", self.size),
+        };
+        match self.length {
+            BenchLength::Count(count) => {
+                write!(f, "{} x {} messages at {} msg/s", count, payload, self.rate)?
+            }
+            BenchLength::Duration(duration) => write!(
+                f,
+                "{} messages at {} msg/s for {}s",
+                payload,
+                self.rate,
+                duration.as_secs()
+            )?,
+        }
+        match self.keys {
+            KeyDistribution::None => Ok(()),
+            KeyDistribution::RoundRobin(keys) => write!(f, " over {} keys", keys),
+            KeyDistribution::Random => write!(f, " with random keys"),
+        }
+    }
+}
+
+pub struct BenchReport {
+    pub topic: String,
+    // Messages handed to the producer, `acked` of them were persisted.
+    pub sent: usize,
+    pub acked: usize,
+    pub failed: usize,
+    pub bytes: usize,
+    pub elapsed: Duration,
+    pub latencies: Vec<Duration>,
+}
+
+// Expects sorted latencies.
+fn percentile(latencies: &[Duration], percentile: usize) -> Duration {
+    latencies
+        .get((latencies.len() * percentile / 100).min(latencies.len().saturating_sub(1)))
+        .copied()
+        .unwrap_or_default()
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let seconds = self.elapsed.as_secs_f64().max(f64::EPSILON);
+        let rate = self.acked as f64 / seconds;
+
+        write!(
+            f,
+            "Bench '{}': sent {}, acked {}, failed {} in {:.1}s, {:.0} msg/s, {:.2} MB/s",
+            self.topic,
+            self.sent,
+            self.acked,
+            self.failed,
+            seconds,
+            rate,
+            self.bytes as f64 / seconds / 1_000_000.0
+        )?;
+
+        if !self.latencies.is_empty() {
+            write!(
+                f,
+                ", latency p50 {:?}, p95 {:?}, p99 {:?}, max {:?}",
+                percentile(&self.latencies, 50),
+                percentile(&self.latencies, 95),
+                percentile(&self.latencies, 99),
+                self.latencies.last().copied().unwrap_or_default()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// Send latencies are measured until the broker receipt.
+pub async fn publish(
+    topic_fqn: String,
+    spec: BenchSpec,
+    pulsar: Arc<Mutex<Pulsar<TokioExecutor>>>,
+    event_sender: UnboundedSender<AppEvent>,
+    mut shutdown: ShutdownSignal,
+) {
+    tokio::select! {
+        report = run_publish(topic_fqn, spec, pulsar, event_sender.clone()) => {
+            let _ = event_sender.send(AppEvent::BenchFinished(report));
+        }
+        _ = shutdown.wait() => {}
+    }
+}
+
+async fn run_publish(
+    topic_fqn: String,
+    spec: BenchSpec,
+    pulsar: Arc<Mutex<Pulsar<TokioExecutor>>>,
+    event_sender: UnboundedSender<AppEvent>,
+) -> anyhow::Result<BenchReport> {
+    let mut producer = pulsar
+        .lock()
+        .await
+        .producer()
+        .with_topic(topic_fqn.clone())
+        .with_name(format!("lgm-bench-{}", Uuid::new_v4()))
+        .build()
+        .await?;
+
+    let mut interval = tokio::time::interval(Duration::from_secs_f64(1.0 / spec.rate as f64));
+    // Receipts are drained while sending, so only the unacked ones are held
+    let mut receipts = FuturesUnordered::new();
+    let mut latencies = vec![];
+    let mut bytes = 0;
+    let mut sent = 0;
+    let started = Instant::now();
+    let mut reported_at = started;
+
+    while !spec.length.is_done(sent, started.elapsed()) {
+        tokio::select! {
+            Some(receipt) = receipts.next(), if !receipts.is_empty() => {
+                if let Ok((latency, size)) = receipt {
+                    latencies.push(latency);
+                    bytes += size;
+                }
+                continue;
+            }
+            _ = interval.tick() => {}
+        }
+
+        let payload = spec.payload(sent);
+        let size = payload.len();
+        let sent_at = Instant::now();
+        let receipt = producer
+            .send_non_blocking(Message {
+                payload,
+                partition_key: spec.keys.key(sent),
+                ..Default::default()
+            })
+            .await?;
+        receipts.push(async move { receipt.await.map(|_| (sent_at.elapsed(), size)) });
+        sent += 1;
+
+        if reported_at.elapsed() >= PROGRESS_INTERVAL {
+            reported_at = Instant::now();
+            let _ = event_sender.send(AppEvent::BenchProgress(format!(
+                "Bench '{}': sent {}, acked {} in {}s{}",
+                topic_fqn,
+                sent,
+                latencies.len(),
+                started.elapsed().as_secs(),
+                match spec.length {
+                    BenchLength::Duration(duration) => format!(" of {}s", duration.as_secs()),
+                    BenchLength::Count(count) => format!(", {}%", sent * 100 / count),
+                }
+            )));
+        }
+    }
+
+    while let Some(receipt) = receipts.next().await {
+        if let Ok((latency, size)) = receipt {
+            latencies.push(latency);
+            bytes += size;
+        }
+    }
+    let elapsed = started.elapsed();
+    producer.close().await?;
+
+    Ok(BenchReport {
+        topic: topic_fqn,
+        sent,
+        acked: latencies.len(),
+        failed: sent - latencies.len(),
+        bytes,
+        elapsed,
+        latencies: latencies.into_iter().sorted().collect(),
+    })
+}
+
+pub struct ConsumeReport {
+    pub topic: String,
+    pub received: usize,
+    pub bytes: usize,
+    pub elapsed: Duration,
+    pub latencies: Vec<Duration>,
+}
+
+impl ConsumeReport {
+    pub fn rows(&self) -> Vec<(&'static str, String)> {
+        let seconds = self.elapsed.as_secs_f64().max(f64::EPSILON);
+
+        vec![
+            ("topic", self.topic.clone()),
+            ("duration", format!("{:.1}s", seconds)),
+            ("received", self.received.to_string()),
+            (
+                "rate",
+                format!("{:.0} msg/s", self.received as f64 / seconds),
+            ),
+            (
+                "throughput",
+                format!("{:.2} MB/s", self.bytes as f64 / seconds / 1_000_000.0),
+            ),
+            (
+                "latency p50",
+                format!("{:?}", percentile(&self.latencies, 50)),
+            ),
+            (
+                "latency p95",
+                format!("{:?}", percentile(&self.latencies, 95)),
+            ),
+            (
+                "latency p99",
+                format!("{:?}", percentile(&self.latencies, 99)),
+            ),
+            (
+                "latency max",
+                format!("{:?}", self.latencies.last().copied().unwrap_or_default()),
+            ),
+        ]
+    }
+}
+
+// End-to-end latencies depend on the producer and lgm clocks being in sync.
+pub async fn consume(
+    topic_fqn: String,
+    duration: Duration,
+    pulsar: Arc<Mutex<Pulsar<TokioExecutor>>>,
+    event_sender: UnboundedSender<AppEvent>,
+    mut shutdown: ShutdownSignal,
+) {
+    tokio::select! {
+        report = run_consume(topic_fqn, duration, pulsar, event_sender.clone()) => {
+            let _ = event_sender.send(AppEvent::ConsumeBenchFinished(report));
+        }
+        _ = shutdown.wait() => {}
+    }
+}
+
+async fn run_consume(
+    topic_fqn: String,
+    duration: Duration,
+    pulsar: Arc<Mutex<Pulsar<TokioExecutor>>>,
+    event_sender: UnboundedSender<AppEvent>,
+) -> anyhow::Result<ConsumeReport> {
+    let mut consumer: Consumer<Vec<u8>, TokioExecutor> = pulsar
+        .lock()
+        .await
+        .consumer()
+        .with_topic(topic_fqn.clone())
+        .with_subscription(format!("lgm-bench-{}", Uuid::new_v4()))
+        .with_options(
+            ConsumerOptions::default()
+                .durable(false)
+                .with_initial_position(InitialPosition::Latest),
+        )
+        .build()
+        .await?;
+
+    let mut latencies = vec![];
+    let mut bytes = 0;
+    let started = Instant::now();
+    let deadline = tokio::time::Instant::now() + duration;
+    let mut reported_at = started;
+
+    loop {
+        let message = match tokio::time::timeout_at(deadline, consumer.try_next()).await {
+            Ok(Ok(Some(message))) => message,
+            Ok(Ok(None)) | Err(_) => break,
+            Ok(Err(err)) => return Err(err.into()),
+        };
+        let received_at = Utc::now().timestamp_millis().max(0) as u64;
+        latencies.push(Duration::from_millis(
+            received_at.saturating_sub(message.payload.metadata.publish_time),
+        ));
+        bytes += message.payload.data.len();
+        consumer.ack(&message).await?;
+
+        if reported_at.elapsed() >= PROGRESS_INTERVAL {
+            reported_at = Instant::now();
+            let _ = event_sender.send(AppEvent::BenchProgress(format!(
+                "Consume bench '{}': received {} in {}s of {}s",
+                topic_fqn,
+                latencies.len(),
+                started.elapsed().as_secs(),
+                duration.as_secs()
+            )));
+        }
+    }
+
+    let elapsed = started.elapsed();
+    let _ = consumer.close().await;
+    latencies.sort();
+
+    Ok(ConsumeReport {
+        topic: topic_fqn,
+        received: latencies.len(),
+        bytes,
+        elapsed,
+        latencies,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_counts_durations_and_options() {
+        let spec = BenchSpec::parse("500 64 10 keys=4").unwrap();
+        assert!(matches!(spec.length, BenchLength::Count(500)));
+        assert_eq!((spec.size, spec.rate), (64, 10));
+        assert_eq!(spec.keys, KeyDistribution::RoundRobin(4));
+
+        let spec = BenchSpec::parse("5m 0 100 keys=random template={\"seq\": {seq}}").unwrap();
+        assert!(
+            matches!(spec.length, BenchLength::Duration(duration) if duration.as_secs() == 300)
+        );
+        assert_eq!(spec.keys, KeyDistribution::Random);
+        assert_eq!(spec.payload(7), b"{\"seq\": 7}".to_vec());
+    }
+
+    #[test]
+    fn rejects_invalid_specs() {
+        assert!(BenchSpec::parse("1000 1024").is_err());
+        assert!(BenchSpec::parse("0 1024 100").is_err());
+        assert!(BenchSpec::parse("0s 1024 100").is_err());
+        assert!(BenchSpec::parse("1000 1024 100 keys=0").is_err());
+        assert!(BenchSpec::parse("1000 1024 100 batch=10").is_err());
+    }
+
+    #[test]
+    fn bounds_the_rate() {
+        assert!(BenchSpec::parse("1000 1024 0").is_err());
+        assert!(BenchSpec::parse("1000 1024 4000000000").is_err());
+        assert!(BenchSpec::parse(&format!("1000 1024 {}", MAX_RATE)).is_ok());
+    }
+}
@@ -0,0 +1,99 @@
+use crate::auth::{Auth, Token};
+use pulsar::authentication::oauth2::{OAuth2Authentication, OAuth2Params};
+use pulsar::{Authentication, Pulsar, TokioExecutor};
+use pulsar_admin_sdk::apis::configuration::Configuration;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct ConnectionKey {
+    url: String,
+    auth: Auth,
+}
+
+// Pulsar clients and admin HTTP pools, shared between profiles that point at the same cluster
+// with the same credentials.
+#[derive(Default)]
+pub struct Connections {
+    clients: HashMap<ConnectionKey, Arc<Mutex<Pulsar<TokioExecutor>>>>,
+    admin_configs: HashMap<ConnectionKey, Configuration>,
+}
+
+impl Connections {
+    pub async fn pulsar_client(
+        &mut self,
+        url: &str,
+        auth: &Auth,
+        token: &Token,
+    ) -> anyhow::Result<Arc<Mutex<Pulsar<TokioExecutor>>>> {
+        let key = ConnectionKey {
+            url: url.to_string(),
+            auth: auth.clone(),
+        };
+
+        if let Some(client) = self.clients.get(&key) {
+            return Ok(client.clone());
+        }
+
+        let builder = match auth {
+            Auth::Token { .. } | Auth::TokenCmd { .. } => Pulsar::builder(url, TokioExecutor)
+                .with_auth(Authentication {
+                    name: "token".to_string(),
+                    data: token.access_token.clone().into_bytes(),
+                }),
+            Auth::OAuth {
+                issuer_url,
+                audience,
+                credentials_file_url,
+            } => Pulsar::builder(url, TokioExecutor).with_auth_provider(
+                OAuth2Authentication::client_credentials(OAuth2Params {
+                    issuer_url: issuer_url.clone(),
+                    credentials_url: credentials_file_url.clone(),
+                    audience: Some(audience.clone()),
+                    scope: None,
+                }),
+            ),
+        };
+
+        let client = Arc::new(Mutex::new(builder.build().await?));
+        self.clients.insert(key, client.clone());
+
+        Ok(client)
+    }
+
+    // Builds the client again with a refreshed command token, the cached one keeps presenting
+    // the token it was built with.
+    pub async fn rebuild_pulsar_client(
+        &mut self,
+        url: &str,
+        auth: &Auth,
+        token: &Token,
+    ) -> anyhow::Result<Arc<Mutex<Pulsar<TokioExecutor>>>> {
+        self.clients.remove(&ConnectionKey {
+            url: url.to_string(),
+            auth: auth.clone(),
+        });
+
+        self.pulsar_client(url, auth, token).await
+    }
+
+    pub fn admin_config(&mut self, admin_url: &str, auth: &Auth, token: &Token) -> Configuration {
+        let key = ConnectionKey {
+            url: admin_url.to_string(),
+            auth: auth.clone(),
+        };
+
+        let config = self
+            .admin_configs
+            .entry(key)
+            .or_insert_with(|| Configuration {
+                base_path: format!("{}/admin/v2", admin_url),
+                ..Configuration::default()
+            });
+
+        Configuration {
+            bearer_access_token: Some(token.access_token.clone()),
+            ..config.clone()
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use anyhow::anyhow;
+use native_tls::TlsConnector;
+use reqwest::{StatusCode, Url};
+use std::fmt;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use crate::auth::{auth, Auth, Context};
+use crate::connections::Connections;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+pub enum Outcome {
+    Passed(String),
+    Failed(String),
+    Skipped(String),
+}
+
+pub struct Check {
+    pub layer: &'static str,
+    pub outcome: Outcome,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (status, detail) = match &self.outcome {
+            Outcome::Passed(detail) => ("ok", detail),
+            Outcome::Failed(detail) => ("FAIL", detail),
+            Outcome::Skipped(detail) => ("skip", detail),
+        };
+        write!(f, "  {:<5} {:<20} {}", status, self.layer, detail)
+    }
+}
+
+// Checks of one endpoint, each layer only runs once the one below it passed.
+pub struct EndpointReport {
+    pub name: &'static str,
+    pub url: String,
+    pub checks: Vec<Check>,
+}
+
+impl EndpointReport {
+    fn failed(&self) -> bool {
+        self.checks
+            .iter()
+            .any(|check| matches!(check.outcome, Outcome::Failed(_)))
+    }
+
+    fn push(&mut self, layer: &'static str, result: anyhow::Result<String>) {
+        let outcome = if self.failed() {
+            Outcome::Skipped("a previous check failed".to_string())
+        } else {
+            match result {
+                Ok(detail) => Outcome::Passed(detail),
+                Err(err) => Outcome::Failed(err.to_string()),
+            }
+        };
+        self.checks.push(Check { layer, outcome });
+    }
+
+    fn skip(&mut self, layer: &'static str, reason: &str) {
+        self.checks.push(Check {
+            layer,
+            outcome: Outcome::Skipped(reason.to_string()),
+        });
+    }
+}
+
+impl fmt::Display for EndpointReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} ({})", self.name, self.url)?;
+        for check in &self.checks {
+            writeln!(f, "{}", check)?;
+        }
+        Ok(())
+    }
+}
+
+pub async fn diagnose(context: Context) -> Vec<EndpointReport> {
+    let mut broker = EndpointReport {
+        name: "broker",
+        url: context.pulsar_url.clone(),
+        checks: vec![],
+    };
+    let mut admin = EndpointReport {
+        name: "admin",
+        url: context.pulsar_admin_url.clone(),
+        checks: vec![],
+    };
+
+    probe_transport(&mut broker).await;
+    probe_transport(&mut admin).await;
+
+    let pulsar_url = context.pulsar_url.clone();
+    let pulsar_admin_url = context.pulsar_admin_url.clone();
+    let auth_config = context.auth.clone();
+    let mut credentials = EndpointReport {
+        name: "auth",
+        url: match &auth_config {
+            Auth::Token { .. } => "static token".to_string(),
+            Auth::OAuth { issuer_url, .. } => issuer_url.clone(),
+            Auth::TokenCmd { command } => format!("command '{}'", command),
+        },
+        checks: vec![],
+    };
+
+    let token = auth(&auth_config).await;
+    credentials.push(
+        "token",
+        token
+            .as_ref()
+            .map(|_| "obtained".to_string())
+            .map_err(|err| anyhow!("Failed to obtain token: '{}'", err)),
+    );
+
+    match &token {
+        Ok(_) if admin.failed() => credentials.skip("admin accepts token", "admin unreachable"),
+        Ok(token) => credentials.push(
+            "admin accepts token",
+            check_admin_token(&pulsar_admin_url, &token.access_token).await,
+        ),
+        Err(_) => credentials.skip("admin accepts token", "no token"),
+    }
+
+    match &token {
+        _ if broker.failed() => broker.push("pulsar handshake", Ok(String::new())),
+        Ok(token) => {
+            let started = Instant::now();
+            let handshake = tokio::time::timeout(
+                TIMEOUT * 2,
+                Connections::default().pulsar_client(&pulsar_url, &auth_config, token),
+            )
+            .await
+            .map_err(|_| anyhow!("Timed out after {}s", (TIMEOUT * 2).as_secs()))
+            .and_then(|client| client.map_err(|err| anyhow!("Rejected: '{}'", err)))
+            .map(|_| format!("connected in {} ms", started.elapsed().as_millis()));
+            broker.push("pulsar handshake", handshake);
+        }
+        Err(_) => broker.skip("pulsar handshake", "no token"),
+    }
+
+    vec![broker, admin, credentials]
+}
+
+async fn probe_transport(report: &mut EndpointReport) {
+    let url = report.url.clone();
+    let (host, port, tls) = match parse_endpoint(&url) {
+        Ok(endpoint) => endpoint,
+        Err(err) => {
+            report.push("url", Err(err));
+            return;
+        }
+    };
+
+    let resolution = {
+        let host = host.clone();
+        tokio::task::spawn_blocking(move || resolve(&host, port))
+            .await
+            .map_err(|err| anyhow!("Failed to resolve: '{}'", err))
+            .and_then(|addresses| addresses)
+    };
+    let address = resolution
+        .as_ref()
+        .ok()
+        .map(|addresses| addresses[0]);
+    report.push(
+        "DNS resolution",
+        resolution.map(|addresses| {
+            addresses
+                .iter()
+                .map(|address| address.ip().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        }),
+    );
+
+    let Some(address) = address else {
+        report.skip("TCP connect", "no address");
+        report.skip("TLS handshake", "no address");
+        return;
+    };
+
+    let transport = tokio::task::spawn_blocking(move || connect(address, &host, tls))
+        .await
+        .unwrap_or_else(|err| (Err(anyhow!("Failed to connect: '{}'", err)), None));
+    report.push("TCP connect", transport.0);
+    match transport.1 {
+        Some(handshake) => report.push("TLS handshake", handshake),
+        None if !tls => report.skip("TLS handshake", "plain text endpoint"),
+        None => report.push("TLS handshake", Ok(String::new())),
+    }
+}
+
+// Brokers default to 6650, or 6651 with TLS.
+fn parse_endpoint(url: &str) -> anyhow::Result<(String, u16, bool)> {
+    let parsed = Url::parse(url).map_err(|err| anyhow!("Invalid url '{}': '{}'", url, err))?;
+    let host = parsed
+        .host_str()
+        .ok_or(anyhow!("Url '{}' has no host", url))?
+        .to_string();
+    let (default_port, tls) = match parsed.scheme() {
+        "pulsar" => (6650, false),
+        "pulsar+ssl" => (6651, true),
+        "http" => (80, false),
+        "https" => (443, true),
+        scheme => return Err(anyhow!("Unsupported scheme '{}' in '{}'", scheme, url)),
+    };
+
+    Ok((host, parsed.port().unwrap_or(default_port), tls))
+}
+
+fn resolve(host: &str, port: u16) -> anyhow::Result<Vec<SocketAddr>> {
+    let addresses: Vec<SocketAddr> = (host, port)
+        .to_socket_addrs()
+        .map_err(|err| anyhow!("Failed to resolve '{}': '{}'", host, err))?
+        .collect();
+
+    if addresses.is_empty() {
+        Err(anyhow!("'{}' resolved to no addresses", host))
+    } else {
+        Ok(addresses)
+    }
+}
+
+fn connect(
+    address: SocketAddr,
+    host: &str,
+    tls: bool,
+) -> (anyhow::Result<String>, Option<anyhow::Result<String>>) {
+    let started = Instant::now();
+    let stream = match TcpStream::connect_timeout(&address, TIMEOUT) {
+        Ok(stream) => stream,
+        Err(err) => {
+            return (
+                Err(anyhow!("Failed to connect to {}: '{}'", address, err)),
+                None,
+            )
+        }
+    };
+    let connected = Ok(format!(
+        "{} in {} ms",
+        address,
+        started.elapsed().as_millis()
+    ));
+    if !tls {
+        return (connected, None);
+    }
+
+    let _ = stream.set_read_timeout(Some(TIMEOUT));
+    let _ = stream.set_write_timeout(Some(TIMEOUT));
+    let started = Instant::now();
+    let handshake = TlsConnector::new()
+        .map_err(|err| anyhow!("Failed to set up TLS: '{}'", err))
+        .and_then(|connector| {
+            connector
+                .connect(host, stream)
+                .map_err(|err| anyhow!("Handshake failed: '{}'", err))
+        })
+        .map(|_| format!("completed in {} ms", started.elapsed().as_millis()));
+
+    (connected, Some(handshake))
+}
+
+async fn check_admin_token(admin_url: &str, token: &str) -> anyhow::Result<String> {
+    let response = reqwest::Client::new()
+        .get(format!("{}/admin/v2/clusters", admin_url))
+        .bearer_auth(token)
+        .timeout(TIMEOUT)
+        .send()
+        .await
+        .map_err(|err| anyhow!("Request failed: '{}'", err))?;
+
+    match response.status() {
+        status if status.is_success() => Ok(status.to_string()),
+        status @ (StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) => {
+            Err(anyhow!("Token rejected: {}", status))
+        }
+        status => Err(anyhow!("Unexpected response: {}", status)),
+    }
+}
+
+// Prints the report, returns whether every check passed.
+pub fn print(reports: &[EndpointReport]) -> bool {
+    for report in reports {
+        println!("{}", report);
+    }
+
+    !reports.iter().any(|report| report.failed())
+}
@@ -1,20 +1,31 @@
-use std::usize;
-
+use chrono::{Local, TimeZone};
+use itertools::Itertools;
 use ratatui::layout::Rect;
 use ratatui::style::Modifier;
 use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::block::{Position, Title};
 use ratatui::widgets::{Cell, Row, Table, TableState, Wrap};
+use std::collections::BTreeSet;
+use std::time::Duration;
 
 use ratatui::{
     prelude::{Alignment, Constraint, Direction, Layout},
     style::{Color, Style},
-    widgets::{Block, BorderType, Borders, Clear, List, ListState, Padding, Paragraph},
+    widgets::{
+        Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph, Sparkline,
+    },
     Frame,
 };
 
+use crate::bench::ConsumeReport;
+use crate::filter::{Expression, KeyFilter, PartitionFilter, PropertyFilter, SearchPattern};
+use crate::keymap::Keymap;
+use crate::probe::LatencyProbe;
+use crate::pulsar_listener::MessageMeta;
 use crate::update::{
-    ConfirmationModal, Consumers, Listening, Namespaces, Subscription, Subscriptions, Tenants,
-    Topics,
+    AckState, ActionMenu, Comparison, ConfirmationModal, Consumer, Consumers, InputModal,
+    ListFilter, Listening, NamespaceLimits, Namespaces, Overview, PreviewMode, ProduceModal,
+    Subscription, Subscriptions, TableSort, Tenants, Topics,
 };
 use crate::{App, Resource, SelectedPanel};
 
@@ -28,6 +39,7 @@ struct LayoutChunks {
     header: HeaderLayout,
     message: Option<Rect>,
     main: Rect,
+    probe: Option<Rect>,
 }
 
 pub fn draw_new(frame: &mut Frame, app: &App) {
@@ -39,11 +51,54 @@ pub fn draw_new(frame: &mut Frame, app: &App) {
         layout,
         Info {
             cluster_name: LabeledItem::info("cluster:", &app.cluster_name),
+            context: context_line(&app.context, app.production),
+            decoder: match app.active_resource {
+                Resource::Listening { .. } => Some(LabeledItem::info(
+                    "decoder:",
+                    &format!(
+                        "{} ({})",
+                        app.resources.listening.decoder,
+                        if app.resources.listening.decoder_overridden {
+                            "manual"
+                        } else {
+                            "auto"
+                        }
+                    ),
+                )),
+                _ => None,
+            },
+            rate: match app.active_resource {
+                Resource::Listening { .. } => {
+                    let (messages, bytes) = app.resources.listening.receive_rate();
+                    Some(LabeledItem::info(
+                        "rate:",
+                        &format!("{:.1} msg/s, {:.1} KB/s", messages, bytes / 1000.0),
+                    ))
+                }
+                _ => None,
+            },
         },
     );
 
+    draw_help(
+        frame,
+        layout,
+        app.available_bindings()
+            .into_iter()
+            .filter(|binding| binding.in_header)
+            .map(|binding| {
+                LabeledItem::help(&binding.keys_label(), binding.label.unwrap_or_default())
+            })
+            .collect(),
+    );
+
+    let filter = app.list_filter.as_ref();
+    let visible = app
+        .resources
+        .visible_items(&app.active_resource, filter);
+
     match &app.active_resource {
-        Resource::Tenants => draw_tenants(frame, layout, &app.resources.tenants),
+        Resource::Tenants => draw_tenants(frame, layout, &app.resources.tenants, &visible, filter),
 
         Resource::Namespaces => draw_namespaces(
             frame,
@@ -53,6 +108,8 @@ pub fn draw_new(frame: &mut Frame, app: &App) {
                 .map(|tenant| tenant.name.clone())
                 .unwrap_or("".to_string()),
             &app.resources.namespaces,
+            &visible,
+            filter,
         ),
 
         Resource::Topics => draw_topics(
@@ -63,6 +120,9 @@ pub fn draw_new(frame: &mut Frame, app: &App) {
                 .map(|tenant| tenant.name.clone())
                 .unwrap_or("".to_string()),
             &app.resources.topics,
+            &app.resources.namespaces.limits,
+            &visible,
+            filter,
         ),
 
         Resource::Subscriptions => draw_subscriptions(
@@ -73,6 +133,8 @@ pub fn draw_new(frame: &mut Frame, app: &App) {
                 .map(|tenant| tenant.name.clone())
                 .unwrap_or("".to_string()),
             &app.resources.subscriptions,
+            &visible,
+            filter,
         ),
 
         Resource::Consumers => draw_consumers(
@@ -82,9 +144,26 @@ pub fn draw_new(frame: &mut Frame, app: &App) {
                 .selected_subscription()
                 .map(|sub| sub.name.clone())
                 .unwrap_or("".to_string()),
+            app.resources
+                .selected_subscription()
+                .map(|sub| sub.sub_type.clone())
+                .unwrap_or("".to_string()),
             &app.resources.consumers,
+            app.resources.consumer_limit_warning(),
         ),
 
+        Resource::Comparison => draw_comparison(
+            frame,
+            layout,
+            app.resources
+                .selected_topic()
+                .map(|topic| topic.name.clone())
+                .unwrap_or("".to_string()),
+            &app.resources.comparison,
+        ),
+
+        Resource::Overview => draw_overview(frame, layout, &app.resources.overview),
+
         Resource::Listening { .. } => draw_listening(
             frame,
             layout,
@@ -93,12 +172,274 @@ pub fn draw_new(frame: &mut Frame, app: &App) {
                 .selected_topic()
                 .map(|topic| topic.name.clone())
                 .unwrap_or("".to_string()),
+            app.ui_state.listening_split,
         ),
     }
 
+    if let (Some(probe), Some(rect)) = (app.probe.as_ref(), layout.probe) {
+        draw_probe(frame, probe, rect)
+    }
+
+    if let Some(age) = app.data_age() {
+        let auto_refresh = app
+            .auto_refresh
+            .then_some(app.auto_refresh_interval);
+        draw_staleness(frame, layout, age, app.freshness_threshold, auto_refresh)
+    }
+
     if let Some(modal) = app.confirmation_modal.as_ref() {
         draw_confirmation_modal(frame, modal)
     }
+
+    if let Some(modal) = app.input_modal.as_ref() {
+        draw_input_modal(frame, modal)
+    }
+
+    if let Some(modal) = app.produce_modal.as_ref() {
+        draw_produce_modal(frame, modal)
+    }
+
+    if let Some(report) = app.consume_report.as_ref() {
+        draw_consume_report(frame, report)
+    }
+
+    if let Some(menu) = app.action_menu.as_ref() {
+        draw_action_menu(frame, menu)
+    }
+
+    if let Some(scroll) = app.help_scroll {
+        draw_help_overlay(frame, &app.keymap, scroll)
+    }
+}
+
+// Every labeled binding, one section per view, also measured to bound the overlay scroll.
+pub fn help_lines(keymap: &Keymap) -> Vec<Line<'static>> {
+    let sections = keymap.sections();
+    let key_width = sections
+        .iter()
+        .flat_map(|(_, bindings)| bindings.iter())
+        .map(|binding| binding.keys_label().len())
+        .max()
+        .unwrap_or(0);
+
+    sections
+        .into_iter()
+        .filter(|(_, bindings)| !bindings.is_empty())
+        .flat_map(|(title, bindings)| {
+            std::iter::once(Line::styled(
+                title,
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .chain(bindings.into_iter().map(move |binding| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("  {:<key_width$}  ", binding.keys_label()),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::raw(binding.label.unwrap_or_default()),
+                ])
+            }))
+            .chain(std::iter::once(Line::default()))
+        })
+        .collect()
+}
+
+fn draw_help_overlay(frame: &mut Frame, keymap: &Keymap, scroll: u16) {
+    let paragraph = Paragraph::new(help_lines(keymap))
+        .scroll((scroll, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Double)
+                .title("Key bindings")
+                .title(
+                    Title::from(" j/k to scroll | <esc> to close ")
+                        .position(Position::Bottom)
+                        .alignment(Alignment::Center),
+                )
+                .title_alignment(Alignment::Center)
+                .title_style(Style::default().fg(Color::Green))
+                .padding(Padding::new(2, 2, 1, 0)),
+        );
+    let rect = centered_rect(50, 80, frame.size());
+
+    frame.render_widget(Clear, rect);
+    frame.render_widget(paragraph, rect)
+}
+
+fn draw_consume_report(frame: &mut Frame, report: &ConsumeReport) {
+    let rows = report.rows();
+    let rect = centered_rect(50, 50, frame.size());
+    let table = Table::new(
+        rows.into_iter()
+            .map(|(label, value)| Row::new(vec![Cell::new(label), Cell::new(value)])),
+        [Constraint::Length(14), Constraint::Percentage(100)],
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .title("Consume bench results")
+            .title(
+                Title::from(" <esc> to close ")
+                    .position(Position::Bottom)
+                    .alignment(Alignment::Center),
+            )
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().fg(Color::Green))
+            .padding(Padding::new(2, 2, 1, 1)),
+    );
+
+    frame.render_widget(Clear, rect);
+    frame.render_widget(table, rect);
+}
+
+fn draw_action_menu(frame: &mut Frame, menu: &ActionMenu) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .title(if menu.query.is_empty() {
+            menu.title.to_string()
+        } else {
+            format!("{} matching '{}'", menu.title, menu.query)
+        })
+        .title_alignment(Alignment::Center)
+        .title_style(Style::default().fg(Color::Green))
+        .title(
+            Title::from(if menu.shortcuts {
+                " press a key | <esc> to close | <enter> to run "
+            } else {
+                " type to search | <esc> to close | <enter> to run "
+            })
+            .position(Position::Bottom)
+            .alignment(Alignment::Center),
+        )
+        .padding(Padding::new(1, 1, 1, 0));
+    let actions = menu.matching();
+    let key_width = actions
+        .iter()
+        .map(|action| action.key.len())
+        .max()
+        .unwrap_or(0);
+    let list = List::new(actions.iter().map(|action| {
+        Line::from(vec![
+            Span::styled(
+                format!("{:<key_width$}  ", action.key),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(action.label.as_str()),
+        ])
+    }))
+    .block(block)
+    .highlight_style(Style::default().bg(Color::Green).fg(Color::Black));
+    let mut state = ListState::default().with_selected(Some(menu.cursor));
+    let rect = centered_rect(30, 50, frame.size());
+
+    frame.render_widget(Clear, rect);
+    frame.render_stateful_widget(list, rect, &mut state)
+}
+
+fn draw_staleness(
+    frame: &mut Frame,
+    layout: &LayoutChunks,
+    age: Duration,
+    threshold: Duration,
+    auto_refresh: Option<Duration>,
+) {
+    let is_stale = age > threshold;
+    if is_stale {
+        frame
+            .buffer_mut()
+            .set_style(layout.main, Style::default().add_modifier(Modifier::DIM));
+    }
+
+    let label = Title::from(
+        Line::from(match auto_refresh {
+            Some(interval) => format!(
+                " updated {}s ago, refreshing every {}s ",
+                age.as_secs(),
+                interval.as_secs()
+            ),
+            None => format!(" updated {}s ago ", age.as_secs()),
+        })
+        .style(if is_stale {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::Gray)
+        }),
+    )
+    .alignment(Alignment::Right);
+
+    frame.render_widget(Block::default().title(label), layout.main);
+}
+
+fn draw_input_modal(frame: &mut Frame, modal: &InputModal) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .title(modal.title.clone())
+        .title_alignment(Alignment::Center)
+        .title_style(Style::default().fg(Color::Green))
+        .padding(Padding::new(1, 1, 1, 0));
+    let text = Text::from(vec![
+        Line::from(modal.input.clone()),
+        Line::from(""),
+        Line::from(" <esc> to cancel | <enter> to submit").style(Style::default().fg(Color::Gray)),
+    ]);
+    let paragraph = Paragraph::new(text).block(block);
+    let rect = centered_rect(50, 12, frame.size());
+
+    frame.render_widget(Clear, rect);
+    frame.render_widget(paragraph, rect)
+}
+
+fn draw_produce_modal(frame: &mut Frame, modal: &ProduceModal) {
+    let rect = centered_rect(60, 40, frame.size());
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .title(format!("Produce to {}", modal.topic.name))
+        .title(
+            Title::from(" <tab> next field | <esc> to cancel | <enter> to publish ")
+                .position(Position::Bottom)
+                .alignment(Alignment::Center),
+        )
+        .title_alignment(Alignment::Center)
+        .title_style(Style::default().fg(Color::Green))
+        .padding(Padding::new(1, 1, 1, 0));
+    let inner = block.inner(rect);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+        ])
+        .split(inner);
+
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+    for (index, (label, value)) in ProduceModal::LABELS
+        .iter()
+        .zip(modal.fields.iter())
+        .enumerate()
+    {
+        let field = Paragraph::new(value.clone())
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(if index == modal.focused {
+                        BorderType::Double
+                    } else {
+                        BorderType::Plain
+                    })
+                    .title(*label),
+            );
+        frame.render_widget(field, chunks[index]);
+    }
 }
 
 fn draw_confirmation_modal(frame: &mut Frame, modal: &ConfirmationModal) {
@@ -137,28 +478,84 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     .split(popup_layout[1])[1]
 }
 
-fn draw_tenants(frame: &mut Frame, layout: &LayoutChunks, tenants: &Tenants) {
-    let tenants_help = vec![LabeledItem::help("<enter>", "namespaces")];
-    draw_help(frame, layout, tenants_help);
+fn filter_title(filter: Option<&ListFilter>) -> Title<'static> {
+    match filter {
+        Some(ListFilter { query, pattern: Ok(_), .. }) => {
+            Title::from(format!(" filter: {} ", query))
+        }
+        Some(ListFilter { pattern: Err(err), .. }) => Title::from(format!(" filter: {} ", err)),
+        None => Title::from(""),
+    }
+    .position(Position::Bottom)
+    .alignment(Alignment::Left)
+}
+
+fn visible_cursor(cursor: Option<usize>, visible: &[usize]) -> Option<usize> {
+    cursor.and_then(|cursor| visible.iter().position(|index| *index == cursor))
+}
+
+// The visible items within a screen of the cursor, only their rows are built so lists of tens
+// of thousands of topics draw as fast as short ones.
+struct ListWindow<'a> {
+    items: &'a [usize],
+    selected: Option<usize>,
+    position: Option<usize>,
+    total: usize,
+}
+
+impl ListWindow<'_> {
+    fn new<'a>(visible: &'a [usize], cursor: Option<usize>, area: Rect) -> ListWindow<'a> {
+        let position = visible_cursor(cursor, visible);
+        let rows = area.height as usize;
+        let start = position.unwrap_or(0).saturating_sub(rows);
+        let end = (position.unwrap_or(0) + rows).min(visible.len());
+
+        ListWindow {
+            items: &visible[start..end],
+            selected: position.map(|position| position - start),
+            position,
+            total: visible.len(),
+        }
+    }
 
+    fn title(&self) -> Title<'static> {
+        Title::from(match self.position {
+            Some(position) => format!(" row {} of {} ", position + 1, self.total),
+            None => format!(" {} rows ", self.total),
+        })
+        .position(Position::Bottom)
+        .alignment(Alignment::Right)
+    }
+}
+
+fn draw_tenants(
+    frame: &mut Frame,
+    layout: &LayoutChunks,
+    tenants: &Tenants,
+    visible: &[usize],
+    filter: Option<&ListFilter>,
+) {
+    let window = ListWindow::new(visible, tenants.cursor, layout.main);
     let content_block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Plain)
         .title("Tenants".to_string())
+        .title(filter_title(filter))
+        .title(window.title())
         .title_alignment(Alignment::Center)
         .title_style(Style::default().fg(Color::Green))
         .padding(Padding::new(2, 2, 1, 1));
 
     let content_list = List::new(
-        tenants
-            .tenants
+        window
+            .items
             .iter()
-            .map(|tenant| tenant.name.to_string()),
+            .map(|index| tenants.tenants[*index].name.to_string()),
     )
     .block(content_block)
     .highlight_style(Style::default().bg(Color::Green).fg(Color::Black));
 
-    let mut state = ListState::default().with_selected(tenants.cursor);
+    let mut state = ListState::default().with_selected(window.selected);
 
     frame.render_stateful_widget(content_list, layout.main, &mut state);
 }
@@ -168,63 +565,117 @@ fn draw_namespaces(
     layout: &LayoutChunks,
     tenant: String,
     namespaces: &Namespaces,
+    visible: &[usize],
+    filter: Option<&ListFilter>,
 ) {
-    let help = vec![
-        LabeledItem::help("<esc>", "back"),
-        LabeledItem::help("<enter>", "topics"),
-    ];
-    draw_help(frame, layout, help);
-
+    let window = ListWindow::new(visible, namespaces.cursor, layout.main);
     let content_block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Plain)
         .title(format!("Namespaces of {}", tenant))
+        .title(filter_title(filter))
+        .title(window.title())
         .title_alignment(Alignment::Center)
         .title_style(Style::default().fg(Color::Green))
         .padding(Padding::new(2, 2, 1, 1));
 
-    let content_list = List::new(
-        namespaces
-            .namespaces
-            .iter()
-            .map(|namespace| namespace.name.to_string()),
+    let table = Table::new(
+        window.items.iter().map(|index| {
+            let namespace = &namespaces.namespaces[*index];
+            let name = Cell::new(namespace.name.to_string());
+            let Some(stats) = namespaces.stats.get(&namespace.name) else {
+                return Row::new(vec![name]);
+            };
+
+            Row::new(vec![
+                name,
+                Cell::new(stats.topics.to_string()),
+                style_backlog_cell(stats.backlog),
+            ])
+        }),
+        [
+            Constraint::Percentage(70),
+            Constraint::Percentage(15),
+            Constraint::Percentage(15),
+        ],
     )
+    .header(Row::new(vec!["name", "topics", "backlog"]))
     .block(content_block)
     .highlight_style(Style::default().bg(Color::Green).fg(Color::Black));
 
-    let mut state = ListState::default().with_selected(namespaces.cursor);
+    let mut state = TableState::default().with_selected(window.selected);
 
-    frame.render_stateful_widget(content_list, layout.main, &mut state);
+    frame.render_stateful_widget(table, layout.main, &mut state);
 }
 
-fn draw_topics(frame: &mut Frame, layout: &LayoutChunks, namespace: String, topics: &Topics) {
-    let help = vec![
-        LabeledItem::help("<esc>", "back"),
-        LabeledItem::help("<enter>", "subs"),
-        LabeledItem::help("<c-s>", "listen"),
-    ];
-    draw_help(frame, layout, help);
-
+fn draw_topics(
+    frame: &mut Frame,
+    layout: &LayoutChunks,
+    namespace: String,
+    topics: &Topics,
+    limits: &NamespaceLimits,
+    visible: &[usize],
+    filter: Option<&ListFilter>,
+) {
+    let window = ListWindow::new(visible, topics.cursor, layout.main);
     let content_block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Plain)
-        .title(format!("Topics of {}", namespace))
+        .title(if limits.is_empty() {
+            format!("Topics of {}", namespace)
+        } else {
+            format!("Topics of {} ({})", namespace, limits)
+        })
+        .title(filter_title(filter))
+        .title(window.title())
         .title_alignment(Alignment::Center)
         .title_style(Style::default().fg(Color::Green))
         .padding(Padding::new(2, 2, 1, 1));
 
-    let content_list = List::new(
-        topics
-            .topics
-            .iter()
-            .map(|topic| topic.name.to_string()),
+    let table = Table::new(
+        window.items.iter().map(|index| {
+            let topic = &topics.topics[*index];
+            let name = Cell::new(if topics.is_bookmarked(topic) {
+                format!("* {}", topic.name)
+            } else {
+                topic.name.to_string()
+            });
+            // Stats are still loading or the topic has none
+            let Some(stats) = topics.stats.get(&topic.name) else {
+                return Row::new(vec![name]);
+            };
+
+            Row::new(vec![
+                name,
+                Cell::new(stats.producers.to_string()),
+                Cell::new(stats.subscriptions.to_string()),
+                Cell::new(format!("{:.1}", stats.msg_rate_in)),
+                Cell::new(format!("{:.1}", stats.msg_rate_out)),
+                Cell::new(format_size(stats.storage_size.max(0) as usize)),
+                style_backlog_cell(stats.backlog),
+            ])
+        }),
+        [
+            Constraint::Percentage(40),
+            Constraint::Percentage(10),
+            Constraint::Percentage(10),
+            Constraint::Percentage(10),
+            Constraint::Percentage(10),
+            Constraint::Percentage(10),
+            Constraint::Percentage(10),
+        ],
     )
+    .header(sorted_header(
+        Topics::COLUMNS,
+        topics.sort,
+        &BTreeSet::new(),
+    ))
     .block(content_block)
     .highlight_style(Style::default().bg(Color::Green).fg(Color::Black));
 
-    let mut state = ListState::default().with_selected(topics.cursor);
+    let mut state = TableState::default().with_selected(window.selected);
 
-    frame.render_stateful_widget(content_list, layout.main, &mut state);
+    frame.render_stateful_widget(table, layout.main, &mut state);
 }
 
 fn draw_subscriptions(
@@ -232,108 +683,603 @@ fn draw_subscriptions(
     layout: &LayoutChunks,
     topic: String,
     subscriptions: &Subscriptions,
+    visible: &[usize],
+    filter: Option<&ListFilter>,
 ) {
-    let help = vec![
-        LabeledItem::help("<esc>", "back"),
-        LabeledItem::help("<enter>", "consumers"),
-        LabeledItem::help("<c-d>", "delete"),
-        LabeledItem::help("<c-p>", "skip backlog"),
-        LabeledItem::help("u", "seek 1h"),
-        LabeledItem::help("i", "seek 24h"),
-        LabeledItem::help("o", "seek 1 week"),
-    ];
-    draw_help(frame, layout, help);
-
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+        .split(layout.main);
+    let window = ListWindow::new(visible, subscriptions.cursor, chunks[0]);
     let content_block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Plain)
-        .title(format!("Subscriptions of {}", topic))
+        .title(match &subscriptions.compare_with {
+            Some(compare_with) => {
+                format!(
+                    "Subscriptions of {} (comparing with '{}')",
+                    topic, compare_with
+                )
+            }
+            None if !subscriptions.marked.is_empty() => format!(
+                "Subscriptions of {} ({} marked)",
+                topic,
+                subscriptions.marked.len()
+            ),
+            None => format!("Subscriptions of {}", topic),
+        })
+        .title(filter_title(filter))
+        .title(window.title())
         .title_alignment(Alignment::Center)
         .title_style(Style::default().fg(Color::Green))
         .padding(Padding::new(2, 2, 1, 1));
 
-    let widths = [
-        Constraint::Ratio(1, 4),
-        Constraint::Ratio(1, 4),
-        Constraint::Ratio(1, 4),
-        Constraint::Ratio(1, 4),
-    ];
+    let hidden = &subscriptions.hidden_columns;
+    let shown = Subscriptions::COLUMNS.len() - hidden.len();
+    let widths = shown_columns(
+        vec![Constraint::Ratio(1, shown as u32); Subscriptions::COLUMNS.len()],
+        hidden,
+    );
 
     let table = Table::new(
-        subscriptions.subscriptions.iter().map(|sub| {
-            Row::new(vec![
-                Cell::new(sub.name.clone()),
+        window.items.iter().map(|index| {
+            let sub = &subscriptions.subscriptions[*index];
+            let is_marked = subscriptions
+                .marked
+                .iter()
+                .any(|path| path.topic == topic && path.sub_name == sub.name);
+            let cells = vec![
+                Cell::new(if is_marked {
+                    format!("+ {}", sub.name)
+                } else {
+                    sub.name.clone()
+                }),
                 Cell::new(sub.sub_type.clone()),
                 Cell::new(sub.consumer_count.to_string()),
                 style_backlog_cell(sub.backlog_size),
-            ])
+                match (sub.throttling(), &sub.dispatch_limit) {
+                    (Some(_), _) => {
+                        Cell::new("throttled").style(Style::default().fg(Color::Yellow))
+                    }
+                    (None, Some(_)) => Cell::new("limited"),
+                    (None, None) => Cell::new("-"),
+                },
+            ];
+
+            Row::new(shown_columns(cells, hidden)).style(if is_marked {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default()
+            })
         }),
         widths,
     )
-    .header(Row::new(vec![
-        "name".to_string(),
-        "type".to_string(),
-        "consumers".to_string(),
-        "backlog".to_string(),
-    ]))
+    .header(sorted_header(
+        Subscriptions::COLUMNS,
+        subscriptions.sort,
+        hidden,
+    ))
     .block(content_block)
     .highlight_style(Style::default().bg(Color::Green).fg(Color::Black));
 
-    let mut state = TableState::default().with_selected(subscriptions.cursor);
+    let mut state = TableState::default().with_selected(window.selected);
 
-    frame.render_stateful_widget(table, layout.main, &mut state);
+    frame.render_stateful_widget(table, chunks[0], &mut state);
+    draw_subscription_details(frame, chunks[1], subscriptions);
 }
 
-fn draw_consumers(
+fn draw_subscription_details(frame: &mut Frame, rect: Rect, subscriptions: &Subscriptions) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Plain)
+        .title("Details")
+        .title_alignment(Alignment::Center)
+        .title_style(Style::default().fg(Color::Green))
+        .padding(Padding::new(2, 2, 1, 1));
+
+    let lines: Vec<Line> = match subscriptions
+        .cursor
+        .and_then(|cursor| subscriptions.subscriptions.get(cursor))
+    {
+        Some(subscription) if !subscription.properties.is_empty() => subscription
+            .properties
+            .iter()
+            .map(|(key, value)| {
+                Line::from(vec![
+                    Span::raw(key.clone()).style(Style::default().fg(Color::Green)),
+                    Span::raw(": "),
+                    Span::raw(value.clone()),
+                ])
+            })
+            .collect(),
+        _ => vec![Line::from("no properties")],
+    };
+
+    let ack_lines = subscriptions
+        .cursor
+        .and_then(|cursor| subscriptions.subscriptions.get(cursor))
+        .map(|subscription| {
+            draw_ack_state(
+                &subscription.ack_state,
+                rect.width.saturating_sub(6) as usize,
+            )
+        })
+        .unwrap_or_default();
+    let dispatch_lines = subscriptions
+        .cursor
+        .and_then(|cursor| subscriptions.subscriptions.get(cursor))
+        .map(draw_dispatch_state)
+        .unwrap_or_default();
+    let lines = lines
+        .into_iter()
+        .chain(ack_lines)
+        .chain(dispatch_lines)
+        .collect_vec();
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, rect);
+}
+
+fn draw_dispatch_state(subscription: &Subscription) -> Vec<Line<'static>> {
+    let Some(limit) = &subscription.dispatch_limit else {
+        return vec![];
+    };
+
+    let limits = [
+        limit
+            .msg_per_sec
+            .map(|msg_per_sec| format!("{:.1} msg/s", msg_per_sec)),
+        limit
+            .bytes_per_sec
+            .map(|bytes_per_sec| format!("{:.0} B/s", bytes_per_sec)),
+    ]
+    .into_iter()
+    .flatten()
+    .join(", ");
+
+    vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("dispatch limit").style(Style::default().fg(Color::Green)),
+            Span::raw(format!(": {} ({} policy)", limits, limit.level)),
+        ]),
+        Line::from(vec![
+            Span::raw("dispatching").style(Style::default().fg(Color::Green)),
+            Span::raw(format!(
+                ": {:.1} msg/s, {:.0} B/s",
+                subscription.msg_rate_out, subscription.msg_throughput_out
+            )),
+        ]),
+        match subscription.throttling() {
+            Some(throttling) => Line::from(throttling).style(Style::default().fg(Color::Yellow)),
+            None => Line::from("not throttled"),
+        },
+    ]
+}
+
+fn draw_ack_state(ack_state: &AckState, width: usize) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::raw("ack state").style(Style::default().fg(Color::Green))),
+        Line::from(format!(
+            "mark-delete: {}",
+            ack_state
+                .mark_delete_position
+                .clone()
+                .unwrap_or("unknown".to_string())
+        )),
+    ];
+
+    if ack_state.segments.is_empty() {
+        lines.push(Line::from("no ack holes"));
+        return lines;
+    }
+
+    let total: u64 = ack_state
+        .segments
+        .iter()
+        .map(|segment| segment.entries)
+        .sum();
+    let bar = ack_state
+        .segments
+        .iter()
+        .map(|segment| {
+            let cells = (segment.entries as usize * width / total.max(1) as usize).max(1);
+            let color = if segment.acked {
+                Color::Green
+            } else {
+                Color::Red
+            };
+            Span::raw("█".repeat(cells)).style(Style::default().fg(color))
+        })
+        .collect_vec();
+
+    lines.push(Line::from(bar));
+    lines.push(Line::from(format!(
+        "{} holes, {} unacked entries",
+        ack_state.holes(),
+        ack_state.unacked_entries()
+    )));
+
+    lines
+}
+
+fn draw_comparison(
     frame: &mut Frame,
     layout: &LayoutChunks,
-    subscription: String,
-    consumers: &Consumers,
+    topic: String,
+    comparison: &Comparison,
 ) {
-    let help = vec![LabeledItem::help("<esc>", "back")];
-    draw_help(frame, layout, help);
-
     let content_block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Plain)
-        .title(format!("Consumers of {subscription}"))
+        .title(format!("Subscription comparison of {topic}"))
         .title_alignment(Alignment::Center)
         .title_style(Style::default().fg(Color::Green))
         .padding(Padding::new(2, 2, 1, 1));
 
+    let metrics: Vec<(&str, Vec<String>)> = vec![
+        (
+            "type",
+            comparison
+                .subscriptions
+                .iter()
+                .map(|sub| sub.sub_type.clone())
+                .collect(),
+        ),
+        (
+            "backlog",
+            comparison
+                .subscriptions
+                .iter()
+                .map(|sub| sub.backlog_size.to_string())
+                .collect(),
+        ),
+        (
+            "unacked",
+            comparison
+                .subscriptions
+                .iter()
+                .map(|sub| sub.unacked_messages.to_string())
+                .collect(),
+        ),
+        (
+            "mark delete position",
+            comparison
+                .subscriptions
+                .iter()
+                .map(|sub| sub.mark_delete_position.clone())
+                .collect(),
+        ),
+        (
+            "read position",
+            comparison
+                .subscriptions
+                .iter()
+                .map(|sub| sub.read_position.clone())
+                .collect(),
+        ),
+        (
+            "msg rate out",
+            comparison
+                .subscriptions
+                .iter()
+                .map(|sub| format!("{:.2}/s", sub.msg_rate_out))
+                .collect(),
+        ),
+        (
+            "throughput out",
+            comparison
+                .subscriptions
+                .iter()
+                .map(|sub| format_throughput(sub.msg_throughput_out))
+                .collect(),
+        ),
+        (
+            "ack rate",
+            comparison
+                .subscriptions
+                .iter()
+                .map(|sub| format!("{:.2}/s", sub.message_ack_rate))
+                .collect(),
+        ),
+        (
+            "redeliver rate",
+            comparison
+                .subscriptions
+                .iter()
+                .map(|sub| format!("{:.2}/s", sub.msg_rate_redeliver))
+                .collect(),
+        ),
+        (
+            "consumers",
+            comparison
+                .subscriptions
+                .iter()
+                .map(|sub| sub.consumers.join(", "))
+                .collect(),
+        ),
+    ];
+
     let widths = [
-        Constraint::Ratio(1, 3),
-        Constraint::Ratio(1, 3),
-        Constraint::Ratio(1, 3),
+        Constraint::Ratio(1, 5),
+        Constraint::Ratio(2, 5),
+        Constraint::Ratio(2, 5),
     ];
 
     let table = Table::new(
-        consumers
-            .consumers
-            .clone()
+        metrics.into_iter().map(|(metric, values)| {
+            let differs = values.iter().unique().count() > 1;
+            let style = if differs {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            };
+
+            Row::new(
+                std::iter::once(Cell::new(metric.to_string())).chain(
+                    values
+                        .into_iter()
+                        .map(|value| Cell::new(value).style(style)),
+                ),
+            )
+        }),
+        widths,
+    )
+    .header(Row::new(
+        std::iter::once("".to_string()).chain(
+            comparison
+                .subscriptions
+                .iter()
+                .map(|sub| sub.name.clone()),
+        ),
+    ))
+    .block(content_block);
+
+    frame.render_widget(table, layout.main);
+}
+
+fn draw_overview(frame: &mut Frame, layout: &LayoutChunks, overview: &Overview) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(6), Constraint::Min(0)])
+        .split(layout.main);
+    let lists = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(30),
+            Constraint::Percentage(35),
+            Constraint::Percentage(35),
+        ])
+        .split(chunks[1]);
+    let block = |title: String| {
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Plain)
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().fg(Color::Green))
+            .padding(Padding::new(2, 2, 0, 0))
+    };
+
+    let loading = overview.namespaces.len() - overview.namespace_stats.len();
+    let (msg_rate_in, msg_rate_out) = overview.msg_rates();
+    let summary = vec![
+        Line::from(if loading > 0 {
+            format!(
+                "namespaces: {}, still loading {}",
+                overview.namespaces.len(),
+                loading
+            )
+        } else {
+            format!("namespaces: {}", overview.namespaces.len())
+        }),
+        Line::from(format!("topics: {}", overview.topic_count())),
+        Line::from(vec![
+            Span::raw("backlog: "),
+            Span::styled(
+                overview.backlog().to_string(),
+                if overview.backlog() > 0 {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                },
+            ),
+        ]),
+        Line::from(format!(
+            "in: {:.1} msg/s, out: {:.1} msg/s",
+            msg_rate_in, msg_rate_out
+        )),
+    ];
+    frame.render_widget(
+        Paragraph::new(summary).block(block(format!("Overview of {}", overview.tenant))),
+        chunks[0],
+    );
+
+    let namespaces = Table::new(
+        overview.namespaces.iter().map(|namespace| {
+            let name = Cell::new(namespace.clone());
+            match overview.namespace_stats.get(namespace) {
+                Some(stats) => Row::new(vec![
+                    name,
+                    Cell::new(stats.topics.to_string()),
+                    style_backlog_cell(stats.backlog),
+                ]),
+                None => Row::new(vec![name, Cell::new("...")]),
+            }
+        }),
+        [
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+        ],
+    )
+    .header(Row::new(vec!["namespace", "topics", "backlog"]))
+    .block(block("Namespaces".to_string()));
+    frame.render_widget(namespaces, lists[0]);
+
+    let by_backlog = Table::new(
+        overview
+            .top_by_backlog()
             .into_iter()
-            .map(|consumer| {
+            .map(|(topic, stats)| {
+                Row::new(vec![
+                    Cell::new(topic.clone()),
+                    style_backlog_cell(stats.backlog),
+                ])
+            }),
+        [Constraint::Percentage(75), Constraint::Percentage(25)],
+    )
+    .header(Row::new(vec!["topic", "backlog"]))
+    .block(block(format!("Top {} by backlog", Overview::TOP_TOPICS)));
+    frame.render_widget(by_backlog, lists[1]);
+
+    let by_throughput = Table::new(
+        overview
+            .top_by_throughput()
+            .into_iter()
+            .map(|(topic, stats)| {
                 Row::new(vec![
-                    Cell::new(consumer.name),
-                    Cell::new(consumer.connected_since),
-                    Cell::new(consumer.unacked_messages.to_string()),
+                    Cell::new(topic.clone()),
+                    Cell::new(format!("{:.1}", stats.msg_rate_in)),
+                    Cell::new(format!("{:.1}", stats.msg_rate_out)),
                 ])
             }),
+        [
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+        ],
+    )
+    .header(Row::new(vec!["topic", "in msg/s", "out msg/s"]))
+    .block(block(format!("Top {} by throughput", Overview::TOP_TOPICS)));
+    frame.render_widget(by_throughput, lists[2]);
+}
+
+fn draw_consumers(
+    frame: &mut Frame,
+    layout: &LayoutChunks,
+    subscription: String,
+    sub_type: String,
+    consumers: &Consumers,
+    limit_warning: Option<String>,
+) {
+    let content_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Plain)
+        .title(if consumers.consumers.len() > Consumers::PAGE_SIZE {
+            let page = consumers.page_range();
+            format!(
+                "Consumers of {subscription} ({}-{} of {})",
+                page.start + 1,
+                page.end,
+                consumers.consumers.len()
+            )
+        } else {
+            format!(
+                "Consumers of {subscription} ({})",
+                consumers.consumers.len()
+            )
+        })
+        .title(
+            Title::from(
+                Line::from(limit_warning.unwrap_or_default())
+                    .style(Style::default().fg(Color::Red)),
+            )
+            .position(Position::Bottom)
+            .alignment(Alignment::Center),
+        )
+        .title(Title::from(format!(" {} ", sub_type)).alignment(Alignment::Right))
+        .title_alignment(Alignment::Center)
+        .title_style(Style::default().fg(Color::Green))
+        .padding(Padding::new(2, 2, 1, 1));
+
+    let hidden = &consumers.hidden_columns;
+    let weights = shown_columns(vec![3, 3, 2, 3, 2, 2, 2, 2, 4], hidden);
+    let total: u32 = weights.iter().sum();
+    let widths = weights
+        .into_iter()
+        .map(|weight| Constraint::Ratio(weight, total));
+
+    let page = consumers.page_range();
+    let table = Table::new(
+        consumers.consumers[page.clone()]
+            .iter()
+            .cloned()
+            .map(|consumer| {
+                let assignment = draw_assignment(&sub_type, &consumer);
+                Row::new(shown_columns(
+                    vec![
+                        Cell::new(consumer.name),
+                        Cell::new(consumer.address),
+                        Cell::new(consumer.client_version),
+                        Cell::new(consumer.connected_since),
+                        Cell::new(consumer.unacked_messages.to_string()),
+                        style_permits_cell(consumer.available_permits),
+                        Cell::new(format!("{:.2}/s", consumer.msg_rate_out)),
+                        Cell::new(format_throughput(consumer.msg_throughput_out)),
+                        assignment,
+                    ],
+                    hidden,
+                ))
+            }),
         widths,
     )
-    .header(Row::new(vec![
-        "name".to_string(),
-        "connected since".to_string(),
-        "unacked messages".to_string(),
-    ]))
+    .header(sorted_header(Consumers::COLUMNS, consumers.sort, hidden))
     .block(content_block)
     .highlight_style(Style::default().bg(Color::Green).fg(Color::Black));
 
-    let mut state = TableState::default().with_selected(consumers.cursor);
+    let mut state =
+        TableState::default().with_selected(consumers.cursor.map(|cursor| cursor - page.start));
 
     frame.render_stateful_widget(table, layout.main, &mut state);
 }
 
+// Which messages the broker routes to the consumer, only meaningful for Failover and Key_Shared.
+fn draw_assignment(sub_type: &str, consumer: &Consumer) -> Cell<'static> {
+    match sub_type {
+        "Failover" if consumer.active => Cell::new("active").style(
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        "Failover" => Cell::new("standby").style(Style::default().fg(Color::DarkGray)),
+        "Key_Shared" if !consumer.key_hash_ranges.is_empty() => {
+            Cell::new(consumer.key_hash_ranges.join(" "))
+        }
+        _ => Cell::new("-"),
+    }
+}
+
+fn shown_columns<T>(values: Vec<T>, hidden: &BTreeSet<usize>) -> Vec<T> {
+    values
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| !hidden.contains(index))
+        .map(|(_, value)| value)
+        .collect()
+}
+
+fn sorted_header(columns: &[&str], sort: TableSort, hidden: &BTreeSet<usize>) -> Row<'static> {
+    let titles = columns.iter().enumerate().map(|(index, column)| {
+        if index != sort.column {
+            Cell::new(column.to_string())
+        } else {
+            Cell::new(format!(
+                "{} {}",
+                column,
+                if sort.descending { "v" } else { "^" }
+            ))
+            .style(Style::default().add_modifier(Modifier::BOLD))
+        }
+    });
+
+    Row::new(shown_columns(titles.collect(), hidden))
+}
+
 fn style_backlog_cell(backlog: i64) -> Cell<'static> {
     let style = match backlog {
         backlog if backlog > 100 => Style::default()
@@ -346,6 +1292,122 @@ fn style_backlog_cell(backlog: i64) -> Cell<'static> {
     Cell::new(format!("{}", backlog)).style(style)
 }
 
+fn format_throughput(bytes_per_second: f64) -> String {
+    match bytes_per_second {
+        rate if rate >= 1024.0 * 1024.0 => format!("{:.2} MB/s", rate / (1024.0 * 1024.0)),
+        rate if rate >= 1024.0 => format!("{:.2} KB/s", rate / 1024.0),
+        rate => format!("{:.0} B/s", rate),
+    }
+}
+
+fn format_size(bytes: usize) -> String {
+    match bytes as f64 {
+        size if size >= 1024.0 * 1024.0 * 1024.0 => {
+            format!("{:.1} GB", size / (1024.0 * 1024.0 * 1024.0))
+        }
+        size if size >= 1024.0 * 1024.0 => format!("{:.1} MB", size / (1024.0 * 1024.0)),
+        size if size >= 1024.0 => format!("{:.1} KB", size / 1024.0),
+        size => format!("{:.0} B", size),
+    }
+}
+
+// A consumer without flow permits won't get any messages dispatched
+fn style_permits_cell(permits: i32) -> Cell<'static> {
+    let style = match permits {
+        permits if permits <= 0 => Style::default()
+            .fg(Color::Red)
+            .add_modifier(Modifier::BOLD),
+        _ => Style::default(),
+    };
+
+    Cell::new(format!("{}", permits)).style(style)
+}
+
+const OUTLIER_SIZE_FACTOR: usize = 10;
+
+fn style_message_size(size: usize, is_outlier: bool) -> Style {
+    if is_outlier {
+        return Style::default()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD);
+    }
+
+    match size {
+        size if size > 1024 * 1024 => Style::default()
+            .fg(Color::Red)
+            .add_modifier(Modifier::BOLD),
+        size if size > 100 * 1024 => Style::default().fg(Color::Yellow),
+        _ => Style::default(),
+    }
+}
+
+fn draw_histogram(frame: &mut Frame, listening: &Listening, rect: Option<Rect>) {
+    if let (Some(window), Some(rect)) = (listening.histogram.duration(), rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Plain)
+            .title(format!("Published, {}", listening.histogram))
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().fg(Color::Green));
+        let buckets = block.inner(rect).width.max(1) as usize;
+        let counts = listening.publish_histogram(window, buckets);
+        let peak = counts.iter().max().copied().unwrap_or(0);
+
+        let sparkline = Sparkline::default()
+            .block(
+                block.title(
+                    Title::from(format!(
+                        " peak {} per {:.1}s ",
+                        peak,
+                        window.as_secs_f64() / buckets as f64
+                    ))
+                    .alignment(Alignment::Right),
+                ),
+            )
+            .data(&counts)
+            .style(Style::default().fg(Color::Green));
+
+        frame.render_widget(sparkline, rect);
+    }
+}
+
+fn draw_probe(frame: &mut Frame, probe: &LatencyProbe, rect: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Plain)
+        .title(format!("Latency probe via {}", probe.topic_fqn))
+        .title(Title::from(probe.summary()).alignment(Alignment::Right))
+        .title_alignment(Alignment::Center)
+        .title_style(Style::default().fg(Color::Green));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(block.inner(rect));
+    frame.render_widget(block, rect);
+
+    // Lost pings have no latency to plot, they are marked in the row below their column instead
+    let latencies = probe
+        .samples
+        .iter()
+        .map(|sample| sample.map_or(0, |latency| latency.as_millis() as u64))
+        .collect_vec();
+    let losses = probe
+        .samples
+        .iter()
+        .map(|sample| match sample {
+            Some(_) => Span::raw(" "),
+            None => Span::raw("×").style(Style::default().fg(Color::Red)),
+        })
+        .collect_vec();
+
+    let sparkline = Sparkline::default()
+        .data(&latencies)
+        .style(Style::default().fg(Color::Cyan));
+
+    frame.render_widget(sparkline, chunks[0]);
+    frame.render_widget(Paragraph::new(Line::from(losses)), chunks[1]);
+}
+
 fn draw_search(frame: &mut Frame, listening: &Listening, rect: Option<Rect>) {
     if let (Some(search), Some(rect)) = (listening.search.clone(), rect) {
         let block = Block::default()
@@ -355,7 +1417,31 @@ fn draw_search(frame: &mut Frame, listening: &Listening, rect: Option<Rect>) {
             } else {
                 BorderType::Plain
             })
-            .title("Search".to_string())
+            .title(if Expression::is_expression(&search) {
+                match Expression::parse(&search) {
+                    Ok(_) => "Filter expression".to_string(),
+                    Err(err) => format!("Filter expression: {}", err),
+                }
+            } else if PropertyFilter::is_property_filter(&search) {
+                match PropertyFilter::parse(&search) {
+                    Ok(_) => "Property filter".to_string(),
+                    Err(err) => format!("Property filter: {}", err),
+                }
+            } else if PartitionFilter::is_partition_filter(&search) {
+                match PartitionFilter::parse(&search) {
+                    Ok(_) => "Partition filter".to_string(),
+                    Err(err) => format!("Partition filter: {}", err),
+                }
+            } else if KeyFilter::is_key_filter(&search) {
+                "Key filter".to_string()
+            } else if SearchPattern::is_regex(&search) {
+                match SearchPattern::parse(&search) {
+                    Ok(_) => "Regex search".to_string(),
+                    Err(err) => format!("Regex search: {}", err),
+                }
+            } else {
+                "Search".to_string()
+            })
             .title_alignment(Alignment::Center)
             .title_style(Style::default().fg(Color::Green));
 
@@ -365,26 +1451,25 @@ fn draw_search(frame: &mut Frame, listening: &Listening, rect: Option<Rect>) {
     }
 }
 
+const MIN_PANEL_WIDTH: u16 = 30;
+
 fn draw_listening(
     frame: &mut Frame,
     layout: &LayoutChunks,
     listening: &Listening,
     topic_name: String,
+    split: u16,
 ) {
-    let help = vec![
-        LabeledItem::help("<esc>", "back"),
-        LabeledItem::help("<tab>", "cycle panels"),
-        LabeledItem::help("u", "seek 1h"),
-        LabeledItem::help("i", "seek 24h"),
-        LabeledItem::help("o", "seek 1 week"),
-        LabeledItem::help("y", "copy to clipboard"),
-        LabeledItem::help("/", "toggle search"),
-    ];
-    draw_help(frame, layout, help);
-
+    let width = layout.main.width;
+    let list_width = (width as u32 * split as u32 / 100) as u16;
+    let list_width = if width >= MIN_PANEL_WIDTH * 2 {
+        list_width.clamp(MIN_PANEL_WIDTH, width - MIN_PANEL_WIDTH)
+    } else {
+        width / 2
+    };
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .constraints([Constraint::Length(list_width), Constraint::Min(0)])
         .split(layout.main);
 
     let (left_rect, search_rect) = match listening.search {
@@ -399,53 +1484,130 @@ fn draw_listening(
         None => (chunks[0], None),
     };
 
-    let horizontal_space: usize = (left_rect.width - 10).into();
+    let (left_rect, histogram_rect) = match listening.histogram.duration() {
+        Some(_) => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(100), Constraint::Length(8)])
+                .split(left_rect);
+
+            (chunks[0], Some(chunks[1]))
+        }
+        None => (left_rect, None),
+    };
+
+    let horizontal_space: usize = left_rect.width.saturating_sub(10).into();
     let right_rect = chunks[1];
 
     let content_block = Block::default()
         .borders(Borders::ALL)
-        .border_type(if matches!(listening.panel, SelectedPanel::Left { .. }) {
+        .border_type(if matches!(listening.panel, SelectedPanel::Left) {
             BorderType::Double
         } else {
             BorderType::Plain
         })
-        .title(format!("Messages of {topic_name}"))
+        .title(format!(
+            "{} of {topic_name} ({} captured, {} / {} dropped)",
+            if listening.quarantine_only {
+                "Quarantined messages"
+            } else {
+                "Messages"
+            },
+            listening.messages.len(),
+            format_size(
+                listening
+                    .messages
+                    .iter()
+                    .map(|message| message.size)
+                    .sum()
+            ),
+            listening.dropped
+        ))
+        .title(
+            Title::from(if listening.paused { " paused " } else { "" }).alignment(Alignment::Left),
+        )
+        .title(
+            Title::from(if listening.follow { " following " } else { "" })
+                .alignment(Alignment::Left),
+        )
+        .title(match listening.decode_errors.get(&topic_name) {
+            Some(errors) => Title::from(
+                Line::from(format!(" {} decode errors ", errors))
+                    .style(Style::default().fg(Color::Red)),
+            )
+            .alignment(Alignment::Right),
+            None => Title::from(""),
+        })
         .title_alignment(Alignment::Center)
         .title_style(Style::default().fg(Color::Green))
         .padding(Padding::new(2, 2, 1, 1));
 
     let filtered_messages = listening.filtered_messages.clone();
 
+    let median_size = listening.median_message_size();
+    // Plain searches ignore spaces when filtering, the list highlights what the filter matched
+    let list_pattern = search_highlight(listening.search.as_deref(), true);
+
     let content_list = List::new(filtered_messages.iter().map(|message| {
-        if message.body.len() > horizontal_space {
+        let is_outlier = median_size
+            .is_some_and(|median| message.size > median.saturating_mul(OUTLIER_SIZE_FACTOR));
+        let timestamp = Local
+            .timestamp_millis_opt(message.meta.publish_time as i64)
+            .single()
+            .map(|time| format!("{} ", time.format(&listening.timestamp_format)))
+            .unwrap_or_default();
+        let timestamp = match message.meta.partition {
+            Some(partition) => format!("{timestamp}p{partition} "),
+            None => timestamp,
+        };
+        let timestamp = format!("{timestamp}{:>8} ", format_size(message.size));
+        let timestamp = if listening.is_pinned(message) {
+            format!("* {timestamp}")
+        } else {
+            timestamp
+        };
+        let marker = if message.decode_error.is_some() {
+            format!("{timestamp}? ")
+        } else if is_outlier {
+            format!("{timestamp}! ")
+        } else {
+            timestamp
+        };
+        let space = horizontal_space.saturating_sub(marker.len());
+
+        let text = if message.body.len() > space {
             format!(
-                "{}...",
+                "{marker}{}...",
                 &message
                     .body
                     .chars()
-                    .take(horizontal_space)
+                    .take(space)
                     .collect::<String>()
             )
         } else {
-            message.body.to_string()
-        }
+            format!("{marker}{}", message.body)
+        };
+
+        let style = if message.decode_error.is_some() {
+            Style::default().fg(Color::Red)
+        } else {
+            style_message_size(message.size, is_outlier)
+        };
+
+        ListItem::new(highlight_matches(
+            text,
+            list_pattern.as_ref(),
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow),
+        ))
+        .style(style)
     }))
     .block(content_block)
     .highlight_style(Style::default().bg(Color::Green).fg(Color::Black));
 
     let mut state = ListState::default().with_selected(listening.cursor);
 
-    let message_body = listening
-        .cursor
-        .and_then(|cursor| filtered_messages.get(cursor))
-        .and_then(|message| serde_json::from_str::<serde_json::Value>(&message.body).ok())
-        .and_then(|body_as_json| serde_json::to_string_pretty(&body_as_json).ok());
-
-    let message_properties = listening
-        .cursor
-        .and_then(|cursor| filtered_messages.get(cursor))
-        .map(|message| message.properties.join("\n"));
-
     let preview_block = Block::default()
         .borders(Borders::ALL)
         .border_type(if matches!(listening.panel, SelectedPanel::Right { .. }) {
@@ -453,41 +1615,51 @@ fn draw_listening(
         } else {
             BorderType::Plain
         })
-        .title("Preview")
+        .title(format!(
+            "Preview ({}{})",
+            listening.preview_mode,
+            if listening.preview_wrap {
+                ""
+            } else {
+                ", no wrap"
+            }
+        ))
+        .title(match &listening.preview_search {
+            Some(search) => {
+                let matches = listening.preview_match_lines().len();
+                Title::from(if matches == 0 {
+                    format!(" '{}': no matches ", search.query)
+                } else {
+                    format!(
+                        " '{}': {}/{} lines | n/N ",
+                        search.query,
+                        search.current.min(matches - 1) + 1,
+                        matches
+                    )
+                })
+                .alignment(Alignment::Right)
+            }
+            None => Title::from(""),
+        })
         .title_alignment(Alignment::Center)
         .title_style(Style::default().fg(Color::Green))
         .padding(Padding::new(2, 2, 1, 1));
 
-    let content = message_properties
-        .and_then(|properties| {
-            message_body
-                .as_ref()
-                .map(|body| properties + "\n\n" + body)
-        })
-        .unwrap_or(String::from("nothing to show"));
+    let content = preview_content(listening).unwrap_or(String::from("nothing to show"));
 
+    // The preview search takes over the highlighting from the list search
+    let preview_pattern = match &listening.preview_search {
+        Some(search) => Some(search.pattern.clone()),
+        None => search_highlight(listening.search.as_deref(), false),
+    };
     let text: Vec<Line<'_>> = content
         .lines()
         .map(|line| {
-            if let Some(search) = listening.search.clone() {
-                if line.contains(&search) && !search.is_empty() {
-                    if let Some((first_half, second_half)) = line.split_once(&search) {
-                        Line::from(vec![
-                            Span::raw(first_half),
-                            Span::raw(search.clone())
-                                .style(Style::default().fg(Color::Black).bg(Color::Green)),
-                            Span::raw(second_half),
-                        ])
-                    //TODO: Fix this yikes
-                    } else {
-                        Line::from(line)
-                    }
-                } else {
-                    Line::from(line)
-                }
-            } else {
-                Line::from(line)
-            }
+            highlight_matches(
+                line.to_string(),
+                preview_pattern.as_ref(),
+                Style::default().fg(Color::Black).bg(Color::Green),
+            )
         })
         .collect();
 
@@ -499,16 +1671,158 @@ fn draw_listening(
 
     let preview_paragraph = Paragraph::new(text)
         .block(preview_block)
-        .wrap(Wrap { trim: false })
         .scroll(scroll_offset);
+    let preview_paragraph = if listening.preview_wrap {
+        preview_paragraph.wrap(Wrap { trim: false })
+    } else {
+        preview_paragraph
+    };
 
     draw_search(frame, listening, search_rect);
+    draw_histogram(frame, listening, histogram_rect);
     frame.render_stateful_widget(content_list, left_rect, &mut state);
     frame.render_widget(preview_paragraph, right_rect);
 }
 
+pub fn preview_content(listening: &Listening) -> Option<String> {
+    let message = listening
+        .cursor
+        .and_then(|cursor| listening.filtered_messages.get(cursor))?;
+
+    let body = match (listening.preview_mode, &message.decode_error) {
+        (PreviewMode::Decoded, Some(error)) => format!("{}\n\n{}", error, hex_dump(&message.raw)),
+        (PreviewMode::Decoded, None) => serde_json::from_str::<serde_json::Value>(&message.body)
+            .ok()
+            .and_then(|body_as_json| serde_json::to_string_pretty(&body_as_json).ok())
+            .unwrap_or(message.body.clone()),
+        (PreviewMode::Utf8, _) => String::from_utf8_lossy(&message.raw).to_string(),
+        (PreviewMode::Hex, _) => hex_dump(&message.raw),
+    };
+    Some(
+        draw_message_meta(&message.meta)
+            + &draw_properties(&message.property_pairs())
+            + "\n"
+            + &body,
+    )
+}
+
+// Structured filters have nothing to highlight.
+fn search_highlight(search: Option<&str>, strip_spaces: bool) -> Option<SearchPattern> {
+    let search = search?;
+    if Expression::is_expression(search)
+        || PropertyFilter::is_property_filter(search)
+        || KeyFilter::is_key_filter(search)
+        || PartitionFilter::is_partition_filter(search)
+    {
+        None
+    } else if SearchPattern::is_regex(search) {
+        SearchPattern::parse(search).ok()
+    } else if strip_spaces {
+        Some(SearchPattern::Substring(search.replace(' ', "")))
+    } else {
+        Some(SearchPattern::Substring(search.to_string()))
+    }
+}
+
+fn highlight_matches(text: String, pattern: Option<&SearchPattern>, style: Style) -> Line<'static> {
+    let Some(pattern) = pattern else {
+        return Line::from(text);
+    };
+
+    let mut spans = vec![];
+    let mut end = 0;
+    for range in pattern.find_all(&text) {
+        spans.push(Span::raw(text[end..range.start].to_string()));
+        spans.push(Span::styled(text[range.clone()].to_string(), style));
+        end = range.end;
+    }
+    spans.push(Span::raw(text[end..].to_string()));
+
+    Line::from(spans)
+}
+
+fn draw_message_meta(meta: &MessageMeta) -> String {
+    let format_time = |millis: u64| {
+        Local
+            .timestamp_millis_opt(millis as i64)
+            .single()
+            .map(|time| time.format("%Y-%m-%d %H:%M:%S%.3f").to_string())
+            .unwrap_or(millis.to_string())
+    };
+
+    [
+        Some(format!("message id: {}", meta.message_id)),
+        Some(format!("publish time: {}", format_time(meta.publish_time))),
+        meta.event_time
+            .map(|event_time| format!("event time: {}", format_time(event_time))),
+        meta.key
+            .as_ref()
+            .map(|key| format!("key: {}", key)),
+        Some(format!("producer: {}", meta.producer_name)),
+        meta.partition
+            .map(|partition| format!("partition: {}", partition)),
+        meta.chunks
+            .map(|chunks| format!("chunked: reassembled from {} chunks", chunks)),
+        meta.compression
+            .as_ref()
+            .map(|compression| format!("compression: {}", compression)),
+        meta.batch_index
+            .zip(meta.batch_size)
+            .map(|(index, size)| {
+                format!("batch: message {} of {} (acked together)", index + 1, size)
+            }),
+    ]
+    .into_iter()
+    .flatten()
+    .join("\n")
+        + "\n"
+}
+
+fn draw_properties(properties: &[(&str, &str)]) -> String {
+    if properties.is_empty() {
+        return String::new();
+    }
+    let width = properties
+        .iter()
+        .map(|(key, _)| key.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    std::iter::once("\nproperties:".to_string())
+        .chain(
+            properties
+                .iter()
+                .map(|(key, value)| format!("  {:<width$}  {}", key, value)),
+        )
+        .join("\n")
+        + "\n"
+}
+
+fn hex_dump(data: &[u8]) -> String {
+    data.chunks(16)
+        .enumerate()
+        .map(|(line, bytes)| {
+            let hex = bytes
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .join(" ");
+            let ascii: String = bytes
+                .iter()
+                .map(|byte| {
+                    if byte.is_ascii_graphic() || *byte == b' ' {
+                        *byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!("{:08x}  {:<47}  {}", line * 16, hex, ascii)
+        })
+        .join("\n")
+}
+
 fn make_layout(frame: &mut Frame, app: &App) -> LayoutChunks {
-    match app.info_to_show {
+    let layout = match app.info_to_show {
         Some(_) => {
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -522,21 +1836,24 @@ fn make_layout(frame: &mut Frame, app: &App) -> LayoutChunks {
             let header_chunks = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints([
-                    Constraint::Percentage(20),
-                    Constraint::Percentage(20),
-                    Constraint::Percentage(20),
-                    Constraint::Percentage(40),
+                    Constraint::Percentage(16),
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                    Constraint::Length(44),
                 ])
                 .split(chunks[0]);
 
             LayoutChunks {
                 header: HeaderLayout {
                     info_rect: header_chunks[0],
-                    help_rects: vec![header_chunks[1], header_chunks[2]],
-                    logo: header_chunks[3],
+                    help_rects: header_chunks[1..5].to_vec(),
+                    logo: header_chunks[5],
                 },
                 message: Some(chunks[2]),
                 main: chunks[1],
+                probe: None,
             }
         }
         None => {
@@ -548,10 +1865,12 @@ fn make_layout(frame: &mut Frame, app: &App) -> LayoutChunks {
             let header_chunks = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints([
-                    Constraint::Percentage(20),
-                    Constraint::Percentage(20),
-                    Constraint::Percentage(20),
-                    Constraint::Percentage(40),
+                    Constraint::Percentage(16),
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                    Constraint::Length(44),
                 ])
                 .split(chunks[0]);
 
@@ -560,13 +1879,30 @@ fn make_layout(frame: &mut Frame, app: &App) -> LayoutChunks {
             LayoutChunks {
                 header: HeaderLayout {
                     info_rect: header_chunks[0],
-                    help_rects: vec![header_chunks[1], header_chunks[2]],
-                    logo: header_chunks[3],
+                    help_rects: header_chunks[1..5].to_vec(),
+                    logo: header_chunks[5],
                 },
                 message: None,
                 main,
+                probe: None,
+            }
+        }
+    };
+
+    match app.probe {
+        Some(_) => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(100), Constraint::Length(7)])
+                .split(layout.main);
+
+            LayoutChunks {
+                main: chunks[0],
+                probe: Some(chunks[1]),
+                ..layout
             }
         }
+        None => layout,
     }
 }
 
@@ -590,13 +1926,36 @@ fn draw_info(frame: &mut Frame, layout: &LayoutChunks, info: Info) {
     let help_block = Block::default()
         .borders(Borders::NONE)
         .padding(Padding::new(1, 1, 1, 1));
-    //TODO: add more info lines to a vector, once we have more than just cluster name to show
-    let items = vec![Line::from(info.cluster_name)];
+    let items: Vec<Line> = std::iter::once(info.context)
+        .chain(
+            std::iter::once(info.cluster_name)
+                .chain(info.decoder)
+                .chain(info.rate)
+                .map(Line::from),
+        )
+        .collect();
     let paragraph = Paragraph::new(Text::from(items)).block(help_block.clone());
 
     frame.render_widget(paragraph, layout.header.info_rect);
 }
 
+fn context_line(context: &str, production: bool) -> Line<'static> {
+    Line::from(vec![
+        Span::raw("context:").style(
+            Style::default()
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::raw(format!(" {} ", context)).style(
+            Style::default()
+                .fg(Color::White)
+                .bg(if production { Color::Red } else { Color::Blue })
+                .add_modifier(Modifier::BOLD),
+        ),
+    ])
+}
+
 fn draw_help(frame: &mut Frame, layout: &LayoutChunks, help_items: Vec<LabeledItem>) {
     let help_block = Block::default()
         .borders(Borders::NONE)
@@ -607,8 +1966,8 @@ fn draw_help(frame: &mut Frame, layout: &LayoutChunks, help_items: Vec<LabeledIt
     lines
         .chunks(5)
         .map(|lines| Paragraph::new(Text::from(lines.to_vec())).block(help_block.clone()))
-        .enumerate()
-        .for_each(|(i, p)| frame.render_widget(p, layout.header.help_rects[i]));
+        .zip(layout.header.help_rects.iter())
+        .for_each(|(p, rect)| frame.render_widget(p, *rect));
 }
 
 fn draw_notification(frame: &mut Frame, app: &App, layout: &LayoutChunks) {
@@ -634,6 +1993,9 @@ fn draw_notification(frame: &mut Frame, app: &App, layout: &LayoutChunks) {
 #[derive(Clone)]
 struct Info {
     cluster_name: LabeledItem,
+    context: Line<'static>,
+    decoder: Option<LabeledItem>,
+    rate: Option<LabeledItem>,
 }
 
 #[derive(Clone)]
@@ -0,0 +1,399 @@
+use anyhow::anyhow;
+use regex::Regex;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Field(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+// A jq-like filter over JSON message bodies: a path such as `.order.items[0].sku`, optionally
+// compared with a JSON literal, e.g. `.order.status == "FAILED"`. A bare path keeps messages
+// where it resolves to anything but `null` or `false`.
+#[derive(Debug, Clone)]
+pub struct Expression {
+    path: Vec<Segment>,
+    comparison: Option<(Operator, Value)>,
+}
+
+impl Expression {
+    pub fn is_expression(input: &str) -> bool {
+        input.trim_start().starts_with('.')
+    }
+
+    pub fn parse(input: &str) -> anyhow::Result<Expression> {
+        let input = input.trim();
+        let mut rest = input
+            .strip_prefix('.')
+            .ok_or(anyhow!("Expression must start with '.'"))?;
+        let mut path = vec![];
+
+        loop {
+            if let Some(index) = rest.strip_prefix('[') {
+                let (index, remaining) = index
+                    .split_once(']')
+                    .ok_or(anyhow!("Unclosed '[' in '{}'", input))?;
+                path.push(Segment::Index(
+                    index
+                        .trim()
+                        .parse()
+                        .map_err(|_| anyhow!("Invalid index '{}'", index))?,
+                ));
+                rest = remaining;
+            } else {
+                let end = rest
+                    .find(|char: char| !(char.is_alphanumeric() || char == '_' || char == '-'))
+                    .unwrap_or(rest.len());
+                if end > 0 {
+                    path.push(Segment::Field(rest[..end].to_string()));
+                }
+                rest = &rest[end..];
+            }
+
+            match rest.strip_prefix('.') {
+                Some(remaining) => rest = remaining,
+                None if rest.starts_with('[') => {}
+                None => break,
+            }
+        }
+
+        let rest = rest.trim();
+        if rest.is_empty() {
+            return Ok(Expression { path, comparison: None });
+        }
+
+        let (operator, literal) = [
+            ("==", Operator::Eq),
+            ("!=", Operator::Ne),
+            (">=", Operator::Ge),
+            ("<=", Operator::Le),
+            (">", Operator::Gt),
+            ("<", Operator::Lt),
+        ]
+        .into_iter()
+        .find_map(|(symbol, operator)| {
+            rest.strip_prefix(symbol)
+                .map(|literal| (operator, literal))
+        })
+        .ok_or(anyhow!(
+            "Expected a comparison after the path, got '{}'",
+            rest
+        ))?;
+        let literal = serde_json::from_str(literal.trim())
+            .map_err(|_| anyhow!("Invalid JSON literal '{}'", literal.trim()))?;
+
+        Ok(Expression {
+            path,
+            comparison: Some((operator, literal)),
+        })
+    }
+
+    pub fn matches(&self, json: &Value) -> bool {
+        let value = self
+            .path
+            .iter()
+            .try_fold(json, |value, segment| match segment {
+                Segment::Field(field) => value.get(field),
+                Segment::Index(index) => value.get(index),
+            });
+
+        match (&self.comparison, value) {
+            (None, value) => !matches!(value, None | Some(Value::Null) | Some(Value::Bool(false))),
+            (Some((Operator::Ne, literal)), value) => {
+                compare(value.unwrap_or(&Value::Null), literal) != Some(Ordering::Equal)
+            }
+            (Some(_), None) => false,
+            (Some((operator, literal)), Some(value)) => match compare(value, literal) {
+                Some(ordering) => match operator {
+                    Operator::Eq => ordering == Ordering::Equal,
+                    Operator::Ne => ordering != Ordering::Equal,
+                    Operator::Gt => ordering == Ordering::Greater,
+                    Operator::Ge => ordering != Ordering::Less,
+                    Operator::Lt => ordering == Ordering::Less,
+                    Operator::Le => ordering != Ordering::Greater,
+                },
+                None => false,
+            },
+        }
+    }
+}
+
+// Numbers compare numerically and strings lexicographically, other values only by equality.
+fn compare(value: &Value, literal: &Value) -> Option<Ordering> {
+    match (value, literal) {
+        (Value::Number(value), Value::Number(literal)) => value
+            .as_f64()
+            .zip(literal.as_f64())
+            .and_then(|(value, literal)| value.partial_cmp(&literal)),
+        (Value::String(value), Value::String(literal)) => Some(value.cmp(literal)),
+        (value, literal) if value == literal => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+// Plain searches match substrings, searches prefixed with `~` are regular expressions, e.g.
+// `~^orders-(eu|us)-v2$`.
+#[derive(Debug, Clone)]
+pub enum SearchPattern {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl SearchPattern {
+    pub fn is_regex(input: &str) -> bool {
+        input.starts_with('~')
+    }
+
+    pub fn parse(input: &str) -> anyhow::Result<SearchPattern> {
+        match input.strip_prefix('~') {
+            Some(regex) => Regex::new(regex)
+                .map(SearchPattern::Regex)
+                .map_err(|_| anyhow!("Invalid regex '{}'", regex)),
+            None => Ok(SearchPattern::Substring(input.to_string())),
+        }
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        match self {
+            SearchPattern::Substring(substring) => text.contains(substring.as_str()),
+            SearchPattern::Regex(regex) => regex.is_match(text),
+        }
+    }
+
+    pub fn find_all(&self, text: &str) -> Vec<Range<usize>> {
+        match self {
+            SearchPattern::Substring(substring) if substring.is_empty() => vec![],
+            SearchPattern::Substring(substring) => text
+                .match_indices(substring.as_str())
+                .map(|(start, matched)| start..start + matched.len())
+                .collect(),
+            SearchPattern::Regex(regex) => regex
+                .find_iter(text)
+                .filter(|matched| !matched.is_empty())
+                .map(|matched| matched.range())
+                .collect(),
+        }
+    }
+}
+
+// Matches a single message property, `prop:traceId=abc123` by exact value or `prop:traceId` by
+// presence, so property searches never match body text.
+#[derive(Debug, Clone)]
+pub struct PropertyFilter {
+    key: String,
+    value: Option<String>,
+}
+
+impl PropertyFilter {
+    const PREFIX: &'static str = "prop:";
+
+    pub fn is_property_filter(input: &str) -> bool {
+        input.trim_start().starts_with(Self::PREFIX)
+    }
+
+    pub fn parse(input: &str) -> anyhow::Result<PropertyFilter> {
+        let filter = input
+            .trim()
+            .strip_prefix(Self::PREFIX)
+            .ok_or(anyhow!(
+                "Property filter must start with '{}'",
+                Self::PREFIX
+            ))?;
+        let (key, value) = match filter.split_once('=') {
+            Some((key, value)) => (key, Some(value.trim().to_string())),
+            None => (filter, None),
+        };
+        let key = key.trim();
+
+        if key.is_empty() {
+            Err(anyhow!("Property key is missing"))
+        } else {
+            Ok(PropertyFilter { key: key.to_string(), value })
+        }
+    }
+
+    // Properties are kept as `key:value` strings.
+    pub fn matches(&self, properties: &[String]) -> bool {
+        properties
+            .iter()
+            .filter_map(|property| property.split_once(':'))
+            .any(|(key, value)| {
+                key == self.key
+                    && self
+                        .value
+                        .as_ref()
+                        .is_none_or(|expected| value == expected)
+            })
+    }
+}
+
+// Matches the message key, `key:order-42` exactly or `key:order-*` by prefix.
+#[derive(Debug, Clone)]
+pub struct KeyFilter {
+    key: String,
+    prefix: bool,
+}
+
+impl KeyFilter {
+    const PREFIX: &'static str = "key:";
+
+    pub fn is_key_filter(input: &str) -> bool {
+        input.trim_start().starts_with(Self::PREFIX)
+    }
+
+    pub fn parse(input: &str) -> anyhow::Result<KeyFilter> {
+        let key = input
+            .trim()
+            .strip_prefix(Self::PREFIX)
+            .ok_or(anyhow!("Key filter must start with '{}'", Self::PREFIX))?
+            .trim();
+
+        Ok(match key.strip_suffix('*') {
+            Some(prefix) => KeyFilter {
+                key: prefix.to_string(),
+                prefix: true,
+            },
+            None => KeyFilter {
+                key: key.to_string(),
+                prefix: false,
+            },
+        })
+    }
+
+    pub fn matches(&self, key: Option<&str>) -> bool {
+        key.is_some_and(|key| {
+            if self.prefix {
+                key.starts_with(&self.key)
+            } else {
+                key == self.key
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartitionFilter {
+    partition: i32,
+}
+
+impl PartitionFilter {
+    const PREFIX: &'static str = "partition:";
+
+    pub fn is_partition_filter(input: &str) -> bool {
+        input.trim_start().starts_with(Self::PREFIX)
+    }
+
+    pub fn parse(input: &str) -> anyhow::Result<PartitionFilter> {
+        let partition = input
+            .trim()
+            .strip_prefix(Self::PREFIX)
+            .ok_or(anyhow!(
+                "Partition filter must start with '{}'",
+                Self::PREFIX
+            ))?
+            .trim();
+
+        partition
+            .parse()
+            .map(|partition| PartitionFilter { partition })
+            .map_err(|_| anyhow!("Invalid partition '{}'", partition))
+    }
+
+    pub fn matches(&self, partition: Option<i32>) -> bool {
+        partition == Some(self.partition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn matches(expression: &str, json: Value) -> bool {
+        Expression::parse(expression)
+            .expect("expression must parse")
+            .matches(&json)
+    }
+
+    #[test]
+    fn parses_fields_and_indexes() {
+        let expression = Expression::parse(".order.items[0].sku").unwrap();
+        assert_eq!(
+            expression.path,
+            vec![
+                Segment::Field("order".to_string()),
+                Segment::Field("items".to_string()),
+                Segment::Index(0),
+                Segment::Field("sku".to_string()),
+            ]
+        );
+        assert!(expression.comparison.is_none());
+
+        let expression = Expression::parse(".matrix[1][2] == 3").unwrap();
+        assert_eq!(
+            expression.path,
+            vec![
+                Segment::Field("matrix".to_string()),
+                Segment::Index(1),
+                Segment::Index(2),
+            ]
+        );
+        assert_eq!(expression.comparison, Some((Operator::Eq, json!(3))));
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        assert!(Expression::parse("order").is_err());
+        assert!(Expression::parse(".items[0").is_err());
+        assert!(Expression::parse(".items[x]").is_err());
+        assert!(Expression::parse(".status ~ 1").is_err());
+        assert!(Expression::parse(".status == FAILED").is_err());
+    }
+
+    #[test]
+    fn matches_paths_by_truthiness() {
+        let json = json!({"order": {"items": [{"sku": "a-1"}], "paid": false, "note": null}});
+
+        assert!(matches(".order.items[0].sku", json.clone()));
+        assert!(!matches(".order.items[1].sku", json.clone()));
+        assert!(!matches(".order.paid", json.clone()));
+        assert!(!matches(".order.note", json.clone()));
+        assert!(!matches(".order.missing", json));
+    }
+
+    #[test]
+    fn compares_values() {
+        let json = json!({"status": "FAILED", "attempts": 3});
+
+        assert!(matches(".status == \"FAILED\"", json.clone()));
+        assert!(!matches(".status == \"OK\"", json.clone()));
+        assert!(matches(".attempts > 2", json.clone()));
+        assert!(matches(".attempts >= 3.0", json.clone()));
+        assert!(!matches(".attempts < 3", json.clone()));
+        assert!(!matches(".status > 1", json));
+    }
+
+    #[test]
+    fn not_equal_treats_missing_paths_as_null() {
+        let json = json!({"status": "FAILED"});
+
+        assert!(matches(".missing != \"FAILED\"", json.clone()));
+        assert!(!matches(".missing != null", json.clone()));
+        assert!(!matches(".missing == null", json.clone()));
+        assert!(!matches(".status != \"FAILED\"", json.clone()));
+        assert!(matches(".status != 1", json));
+    }
+}
@@ -0,0 +1,272 @@
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{DirBuilder, File, OpenOptions};
+use std::io::{self, Write};
+#[cfg(unix)]
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+
+use crate::update::SubMessage;
+use crate::AppEvent;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Checkpoint {
+    pub saved_at: DateTime<Utc>,
+    pub topic: Option<String>,
+    pub bookmarks: Vec<String>,
+    pub messages: Vec<SubMessage>,
+}
+
+// Checkpoints the capture buffer into the per-user state dir, removed again on a clean exit so a
+// leftover file means a session did not end well. Every instance writes its own file and keeps a
+// lock file next to it locked while running, so only checkpoints of instances that are gone are
+// offered for recovery.
+pub struct Journal {
+    dir: Option<PathBuf>,
+    interval: Duration,
+    saved_at: Instant,
+    saved_total: usize,
+    session: Option<Session>,
+    // Files of a gone instance picked by `load`, locked until cleared.
+    recovered: Option<Session>,
+    writing: Option<JoinHandle<()>>,
+}
+
+struct Session {
+    path: PathBuf,
+    lock: File,
+}
+
+impl Session {
+    fn create(dir: &Path) -> anyhow::Result<Session> {
+        let mut builder = DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        builder.mode(0o700);
+        builder.create(dir).map_err(|err| {
+            anyhow!(
+                "Failed to create journal dir '{}': '{}'",
+                dir.display(),
+                err
+            )
+        })?;
+
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let path = dir.join(format!(
+            "{}{}-{}.json",
+            FILE_PREFIX,
+            std::process::id(),
+            millis
+        ));
+        let lock = open_private(&path.with_extension("lock"), false)
+            .and_then(|lock| {
+                lock.try_lock()
+                    .map(|_| lock)
+                    .map_err(io::Error::from)
+            })
+            .map_err(|err| anyhow!("Failed to lock journal '{}': '{}'", path.display(), err))?;
+
+        Ok(Session { path, lock })
+    }
+
+    // The files of an instance that is gone, `None` while the owner still holds the lock.
+    fn orphan(path: PathBuf) -> Option<Session> {
+        let lock = open_private(&path.with_extension("lock"), false).ok()?;
+        lock.try_lock().ok()?;
+
+        Some(Session { path, lock })
+    }
+
+    fn remove(self) {
+        let _ = std::fs::remove_file(&self.path);
+        let _ = std::fs::remove_file(self.path.with_extension("lock"));
+        drop(self.lock);
+    }
+}
+
+const FILE_PREFIX: &str = "journal-";
+
+impl Journal {
+    // Without a state dir or with a zero interval checkpointing is disabled.
+    pub fn new(dir: Option<PathBuf>, interval: Duration) -> Journal {
+        Journal {
+            dir,
+            interval,
+            saved_at: Instant::now(),
+            saved_total: 0,
+            session: None,
+            recovered: None,
+            writing: None,
+        }
+    }
+
+    pub fn load(&mut self) -> Option<Checkpoint> {
+        let dir = self.dir.as_ref()?;
+        let (session, checkpoint) = std::fs::read_dir(dir)
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "json")
+                    && path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(FILE_PREFIX))
+            })
+            .filter_map(Session::orphan)
+            .filter_map(|session| {
+                let content = std::fs::read_to_string(&session.path).ok()?;
+                let checkpoint = serde_json::from_str::<Checkpoint>(&content).ok()?;
+                Some((session, checkpoint))
+            })
+            .max_by_key(|(_, checkpoint)| checkpoint.saved_at)?;
+        self.recovered = Some(session);
+
+        Some(checkpoint)
+    }
+
+    // Whether a checkpoint is due, `total` counts every message received so far and tells if
+    // anything changed since the last one.
+    pub fn is_due(&self, total: usize) -> bool {
+        self.dir.is_some()
+            && !self.interval.is_zero()
+            && total != self.saved_total
+            && self.saved_at.elapsed() >= self.interval
+            && self
+                .writing
+                .as_ref()
+                .is_none_or(|writing| writing.is_finished())
+    }
+
+    // Writes in a blocking task, a capture of thousands of messages takes a while to serialize.
+    // Failures are sent as `AppEvent::JournalFailed`.
+    pub fn save(
+        &mut self,
+        checkpoint: Checkpoint,
+        total: usize,
+        sender: UnboundedSender<AppEvent>,
+    ) {
+        self.saved_at = Instant::now();
+        self.saved_total = total;
+
+        let path = match (&self.session, &self.dir) {
+            (Some(session), _) => session.path.clone(),
+            (None, Some(dir)) => match Session::create(dir) {
+                Ok(session) => self.session.insert(session).path.clone(),
+                Err(err) => {
+                    let _ = sender.send(AppEvent::JournalFailed(err));
+                    return;
+                }
+            },
+            (None, None) => return,
+        };
+
+        self.writing = Some(tokio::task::spawn_blocking(move || {
+            if let Err(err) = write(&path, &checkpoint) {
+                let _ = sender.send(AppEvent::JournalFailed(err));
+            }
+        }));
+    }
+
+    // Removes the checkpoints of this instance and the recovered one, after the write in flight.
+    pub async fn clear(&mut self) {
+        if let Some(writing) = self.writing.take() {
+            let _ = writing.await;
+        }
+        if let Some(session) = self.session.take() {
+            session.remove();
+        }
+        if let Some(session) = self.recovered.take() {
+            session.remove();
+        }
+    }
+}
+
+fn write(path: &Path, checkpoint: &Checkpoint) -> anyhow::Result<()> {
+    let content = serde_json::to_vec(checkpoint)?;
+
+    // Written next to the journal and renamed over it, so a crash mid-write keeps the last one
+    let partial = path.with_extension("json.partial");
+    open_private(&partial, true)
+        .and_then(|mut file| file.write_all(&content))
+        .and_then(|_| std::fs::rename(&partial, path))
+        .map_err(|err| anyhow!("Failed to write journal '{}': '{}'", path.display(), err))
+}
+
+// Messages may carry sensitive payloads, only the owner can read them
+fn open_private(path: &Path, truncate: bool) -> io::Result<File> {
+    let mut options = OpenOptions::new();
+    options
+        .write(true)
+        .create(true)
+        .truncate(truncate);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    options.open(path)
+}
+
+pub fn save_capture(path: &str, checkpoint: &Checkpoint) -> anyhow::Result<()> {
+    let content = serde_json::to_string(checkpoint)?;
+    std::fs::write(path, content)
+        .map_err(|err| anyhow!("Failed to save capture to '{}': '{}'", path, err))
+}
+
+pub fn load_capture(path: &str) -> anyhow::Result<Checkpoint> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|err| anyhow!("Failed to read capture '{}': '{}'", path, err))?;
+    serde_json::from_str(&content)
+        .map_err(|err| anyhow!("Failed to parse capture '{}': '{}'", path, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn checkpoint() -> Checkpoint {
+        Checkpoint {
+            saved_at: Utc::now(),
+            topic: Some("persistent://public/default/orders".to_string()),
+            bookmarks: vec!["public/default/orders".to_string()],
+            messages: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn recovers_only_checkpoints_of_gone_instances() {
+        let dir = std::env::temp_dir().join(format!("lgm-journal-test-{}", uuid::Uuid::new_v4()));
+        let (sender, _receiver) = unbounded_channel();
+
+        let mut crashed = Journal::new(Some(dir.clone()), Duration::from_secs(1));
+        crashed.save(checkpoint(), 1, sender);
+        crashed
+            .writing
+            .take()
+            .expect("save must start a write")
+            .await
+            .unwrap();
+
+        // Still running, its checkpoint is not up for recovery
+        let mut other = Journal::new(Some(dir.clone()), Duration::from_secs(1));
+        assert!(other.load().is_none());
+
+        drop(crashed);
+        let recovered = other
+            .load()
+            .expect("checkpoint must be recovered");
+        assert_eq!(recovered.topic, checkpoint().topic);
+        assert_eq!(recovered.bookmarks, checkpoint().bookmarks);
+
+        other.clear().await;
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+        let _ = std::fs::remove_dir(&dir);
+    }
+}
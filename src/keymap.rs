@@ -0,0 +1,575 @@
+use anyhow::anyhow;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use itertools::Itertools;
+use std::fmt;
+
+use crate::update::Resource;
+use crate::{CompanionTopic, ControlEvent, ResetLength};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Key {
+    code: KeyCode,
+    ctrl: bool,
+    // Makes the key the second of a two key sequence like `gg`.
+    after: Option<KeyCode>,
+}
+
+impl Key {
+    const fn plain(code: KeyCode) -> Key {
+        Key { code, ctrl: false, after: None }
+    }
+
+    const fn twice(char: char) -> Key {
+        Key {
+            after: Some(KeyCode::Char(char)),
+            ..Key::char(char)
+        }
+    }
+
+    const fn char(char: char) -> Key {
+        Key::plain(KeyCode::Char(char))
+    }
+
+    const fn ctrl(char: char) -> Key {
+        Key {
+            code: KeyCode::Char(char),
+            ctrl: true,
+            after: None,
+        }
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(KeyCode::Char(char)) = self.after {
+            write!(f, "{}", char)?;
+        }
+        match (self.code, self.ctrl) {
+            (KeyCode::Char(char), true) => write!(f, "<c-{}>", char),
+            (KeyCode::Char(' '), false) => write!(f, "<space>"),
+            (KeyCode::Char(char), false) => write!(f, "{}", char),
+            (KeyCode::Enter, _) => write!(f, "<enter>"),
+            (KeyCode::Esc, _) => write!(f, "<esc>"),
+            (KeyCode::Tab, _) => write!(f, "<tab>"),
+            (KeyCode::Backspace, _) => write!(f, "<bs>"),
+            (KeyCode::PageUp, _) => write!(f, "<pgup>"),
+            (KeyCode::PageDown, _) => write!(f, "<pgdn>"),
+            (code, _) => write!(f, "<{}>", format!("{:?}", code).to_lowercase()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum View {
+    Tenants,
+    Namespaces,
+    Topics,
+    Subscriptions,
+    Consumers,
+    Comparison,
+    Overview,
+    Listening,
+}
+
+impl View {
+    pub fn of(resource: &Resource) -> View {
+        match resource {
+            Resource::Tenants => View::Tenants,
+            Resource::Namespaces => View::Namespaces,
+            Resource::Topics => View::Topics,
+            Resource::Subscriptions => View::Subscriptions,
+            Resource::Consumers => View::Consumers,
+            Resource::Comparison => View::Comparison,
+            Resource::Overview => View::Overview,
+            Resource::Listening { .. } => View::Listening,
+        }
+    }
+}
+
+const ALL: &[View] = &[
+    View::Tenants,
+    View::Namespaces,
+    View::Topics,
+    View::Subscriptions,
+    View::Consumers,
+    View::Comparison,
+    View::Overview,
+    View::Listening,
+];
+const BACK: &[View] = &[
+    View::Namespaces,
+    View::Topics,
+    View::Subscriptions,
+    View::Consumers,
+    View::Comparison,
+    View::Overview,
+    View::Listening,
+];
+const LISTS: &[View] = &[
+    View::Tenants,
+    View::Namespaces,
+    View::Topics,
+    View::Subscriptions,
+];
+const NAVIGABLE: &[View] = &[
+    View::Tenants,
+    View::Namespaces,
+    View::Topics,
+    View::Subscriptions,
+    View::Consumers,
+    View::Listening,
+];
+const REFRESHABLE: &[View] = &[
+    View::Tenants,
+    View::Namespaces,
+    View::Topics,
+    View::Subscriptions,
+    View::Consumers,
+    View::Comparison,
+    View::Overview,
+];
+const TABLES: &[View] = &[View::Topics, View::Subscriptions, View::Consumers];
+const SEEK: &[View] = &[View::Subscriptions, View::Listening];
+
+// Bindings of a modal layer take precedence over the base ones while it is open, keys it does not
+// bind fall through to the base layer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Layer {
+    Base,
+    Confirmation,
+    Input,
+}
+
+#[derive(Clone)]
+pub struct Binding {
+    pub keys: Vec<Key>,
+    pub event: ControlEvent,
+    pub layer: Layer,
+    // Unlabeled bindings are dispatched but not listed in the help or the action menu.
+    pub label: Option<&'static str>,
+    pub views: &'static [View],
+    pub in_menu: bool,
+    pub in_header: bool,
+}
+
+impl Binding {
+    fn new(keys: &[Key], event: ControlEvent) -> Binding {
+        Binding {
+            keys: keys.to_vec(),
+            event,
+            layer: Layer::Base,
+            label: None,
+            views: ALL,
+            in_menu: true,
+            in_header: true,
+        }
+    }
+
+    fn listed(self, label: &'static str, views: &'static [View]) -> Binding {
+        Binding {
+            label: Some(label),
+            views,
+            ..self
+        }
+    }
+
+    fn in_layer(self, layer: Layer) -> Binding {
+        Binding { layer, ..self }
+    }
+
+    fn help_only(self) -> Binding {
+        Binding { in_menu: false, ..self }
+    }
+
+    // Listed only in the `?` overlay, for keys too common to spend header space on.
+    fn overlay_only(self) -> Binding {
+        Binding {
+            in_menu: false,
+            in_header: false,
+            ..self
+        }
+    }
+
+    pub fn keys_label(&self) -> String {
+        self.keys.iter().join("/")
+    }
+
+    // Companion bindings only apply when the selected subscription has such a topic.
+    pub fn companion(&self) -> Option<CompanionTopic> {
+        match self.event {
+            ControlEvent::JumpToCompanion(companion)
+            | ControlEvent::ListenToCompanion(companion) => Some(companion),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Keymap {
+    pub fn new() -> anyhow::Result<Keymap> {
+        let keymap = Keymap { bindings: default_bindings() };
+        keymap.check_conflicts()?;
+
+        Ok(keymap)
+    }
+
+    // A key may map to several events only in different layers or views.
+    fn check_conflicts(&self) -> anyhow::Result<()> {
+        for (first, second) in self.bindings.iter().tuple_combinations() {
+            if first.event == second.event
+                || first.layer != second.layer
+                || !first
+                    .views
+                    .iter()
+                    .any(|view| second.views.contains(view))
+            {
+                continue;
+            }
+            if let Some(key) = first
+                .keys
+                .iter()
+                .find(|key| second.keys.contains(key))
+            {
+                return Err(anyhow!(
+                    "Key '{}' is bound to both {:?} and {:?}",
+                    key,
+                    first.event,
+                    second.event
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Bindings of the view win, a key bound only in other views still resolves to their event.
+    // Unmodified characters typed into an input are text, they never fall through to the base
+    // layer.
+    fn find(&self, key: Key, view: View, layer: Layer) -> Option<ControlEvent> {
+        let in_layer = |layer: Layer| {
+            let bound = self
+                .bindings
+                .iter()
+                .filter(|binding| binding.layer == layer && binding.keys.contains(&key))
+                .collect_vec();
+            bound
+                .iter()
+                .find(|binding| binding.views.contains(&view))
+                .or(bound.first())
+                .map(|binding| binding.event.clone())
+        };
+
+        let text = !key.ctrl && matches!(key.code, KeyCode::Char(_));
+        in_layer(layer).or_else(|| {
+            (layer != Layer::Input || !text)
+                .then(|| in_layer(Layer::Base))
+                .flatten()
+        })
+    }
+
+    // `previous` is the key pressed before, it is cleared once it completed a sequence so a
+    // third press starts over.
+    pub fn resolve(
+        &self,
+        event: &KeyEvent,
+        previous: &mut Option<KeyCode>,
+        view: View,
+        layer: Layer,
+    ) -> Option<ControlEvent> {
+        let ctrl = event.modifiers.contains(KeyModifiers::CONTROL);
+        let key = Key {
+            code: event.code,
+            ctrl,
+            after: None,
+        };
+
+        let sequence = (!ctrl)
+            .then(|| {
+                previous.and_then(|previous| {
+                    self.find(Key { after: Some(previous), ..key }, view, layer)
+                })
+            })
+            .flatten();
+        *previous = match sequence {
+            Some(_) => None,
+            None => Some(event.code),
+        };
+
+        sequence
+            .or_else(|| self.find(key, view, layer))
+            .or_else(|| {
+                ctrl.then(|| self.find(Key::plain(event.code), view, layer))
+                    .flatten()
+            })
+    }
+
+    // Labeled bindings grouped for the `?` overlay, the ones of every view come first as general.
+    pub fn sections(&self) -> Vec<(String, Vec<&Binding>)> {
+        let general = self
+            .bindings
+            .iter()
+            .filter(|binding| binding.label.is_some() && binding.views == ALL)
+            .collect();
+
+        std::iter::once(("general".to_string(), general))
+            .chain(ALL.iter().map(|view| {
+                (
+                    format!("{:?}", view).to_lowercase(),
+                    self.listed(*view)
+                        .filter(|binding| binding.views != ALL)
+                        .collect(),
+                )
+            }))
+            .collect()
+    }
+
+    pub fn listed(&self, view: View) -> impl Iterator<Item = &Binding> {
+        self.bindings
+            .iter()
+            .filter(move |binding| binding.label.is_some() && binding.views.contains(&view))
+    }
+}
+
+fn default_bindings() -> Vec<Binding> {
+    use ControlEvent::*;
+
+    vec![
+        Binding::new(&[Key::ctrl('a')], Accept)
+            .listed("accept confirmation", ALL)
+            .overlay_only(),
+        Binding::new(&[Key::char('n')], Refuse)
+            .in_layer(Layer::Confirmation)
+            .listed("cancel confirmation", ALL)
+            .overlay_only(),
+        Binding::new(&[Key::ctrl('c'), Key::ctrl('q')], Terminate)
+            .listed("quit", ALL)
+            .overlay_only(),
+        Binding::new(&[Key::ctrl('u')], ClearInput)
+            .in_layer(Layer::Input)
+            .listed("clear input", ALL)
+            .overlay_only(),
+        Binding::new(&[Key::plain(KeyCode::Backspace)], BackSpace)
+            .listed("delete input char", ALL)
+            .overlay_only(),
+        Binding::new(&[Key::char('j'), Key::plain(KeyCode::Down)], Down)
+            .listed("down", ALL)
+            .overlay_only(),
+        Binding::new(&[Key::char('k'), Key::plain(KeyCode::Up)], Up)
+            .listed("up", ALL)
+            .overlay_only(),
+        Binding::new(&[Key::char('h'), Key::plain(KeyCode::Left)], Back)
+            .listed("back", ALL)
+            .overlay_only(),
+        Binding::new(&[Key::plain(KeyCode::PageUp), Key::ctrl('b')], PageUp)
+            .listed("page up", NAVIGABLE)
+            .help_only(),
+        Binding::new(&[Key::plain(KeyCode::PageDown), Key::ctrl('f')], PageDown)
+            .listed("page down", NAVIGABLE)
+            .help_only(),
+        Binding::new(&[Key::twice('g'), Key::plain(KeyCode::Home)], Top)
+            .listed("top", NAVIGABLE)
+            .help_only(),
+        Binding::new(&[Key::char('G'), Key::plain(KeyCode::End)], Bottom)
+            .listed("bottom", NAVIGABLE)
+            .help_only(),
+        Binding::new(&[Key::plain(KeyCode::Enter)], Enter),
+        Binding::new(&[Key::plain(KeyCode::Esc)], Esc)
+            .listed("back", BACK)
+            .help_only(),
+        Binding::new(&[Key::plain(KeyCode::Tab)], CycleSide)
+            .listed("cycle panels", &[View::Listening])
+            .help_only(),
+        Binding::new(&[Key::plain(KeyCode::Enter)], Enter).listed("namespaces", &[View::Tenants]),
+        Binding::new(&[Key::plain(KeyCode::Enter)], Enter).listed("topics", &[View::Namespaces]),
+        Binding::new(&[Key::plain(KeyCode::Enter)], Enter).listed("subs", &[View::Topics]),
+        Binding::new(&[Key::plain(KeyCode::Enter)], Enter)
+            .listed("consumers", &[View::Subscriptions]),
+        Binding::new(&[Key::ctrl('s')], Subscribe).listed("listen", &[View::Topics]),
+        Binding::new(&[Key::char('P')], Produce).listed("produce", &[View::Topics]),
+        Binding::new(&[Key::char('F')], FetchMessage)
+            .listed("fetch by id", &[View::Topics, View::Subscriptions]),
+        Binding::new(&[Key::char('b')], Bench).listed("bench publish", &[View::Topics]),
+        Binding::new(&[Key::char('B')], ConsumeBench).listed("bench consume", &[View::Topics]),
+        Binding::new(&[Key::char('L')], ToggleProbe)
+            .listed("latency probe", &[View::Namespaces, View::Topics]),
+        Binding::new(&[Key::char('l')], EditLimits).listed("edit limits", &[View::Topics]),
+        Binding::new(&[Key::char('m')], Migrate).listed("migrate", &[View::Topics]),
+        Binding::new(&[Key::char('R')], RecoverTopic).listed("recover deleted", &[View::Topics]),
+        Binding::new(&[Key::ctrl('d')], Delete).listed("delete", &[View::Subscriptions]),
+        Binding::new(&[Key::ctrl('d')], HalfPageDown)
+            .listed("preview half page down", &[View::Listening])
+            .help_only(),
+        Binding::new(&[Key::ctrl('u')], HalfPageUp)
+            .listed("preview half page up", &[View::Listening])
+            .help_only(),
+        Binding::new(&[Key::char(' ')], Mark).listed("mark", &[View::Subscriptions]),
+        Binding::new(&[Key::ctrl('p')], Skip).listed("skip backlog", &[View::Subscriptions]),
+        Binding::new(&[Key::char('u')], ResetSubscription(ResetLength::OneHour))
+            .listed("seek 1h", SEEK),
+        Binding::new(
+            &[Key::char('i')],
+            ResetSubscription(ResetLength::TwentyFourHours),
+        )
+        .listed("seek 24h", SEEK),
+        Binding::new(&[Key::char('o')], ResetSubscription(ResetLength::Week))
+            .listed("seek 1 week", SEEK),
+        Binding::new(&[Key::char('s')], SeekTo).listed("seek to...", &[View::Subscriptions]),
+        Binding::new(&[Key::char('s')], SeekTo).listed("seek subs matching...", &[View::Topics]),
+        Binding::new(&[Key::char('p')], SetProperty).listed("set property", &[View::Subscriptions]),
+        Binding::new(&[Key::char('y')], Yank).listed("copy...", &[View::Listening]),
+        Binding::new(&[Key::char('Y')], YankProperty).listed("copy property", &[View::Listening]),
+        Binding::new(&[Key::ctrl('y')], YankCommand)
+            .listed("copy as pulsar-client", &[View::Listening]),
+        Binding::new(&[Key::char('w')], ExportMessage).listed("export message", &[View::Listening]),
+        Binding::new(&[Key::char('W')], ExportCapture).listed("export all", &[View::Listening]),
+        Binding::new(&[Key::char('W')], ExportCapture).listed("export csv", TABLES),
+        Binding::new(&[Key::char('C')], SaveCapture).listed("save capture", &[View::Listening]),
+        Binding::new(&[Key::char('/')], Search).listed("toggle search", &[View::Listening]),
+        Binding::new(&[Key::char('n')], NextMatch).listed("next match", &[View::Listening]),
+        Binding::new(&[Key::char('N')], PreviousMatch).listed("prev match", &[View::Listening]),
+        Binding::new(&[Key::char('/')], Search).listed("filter", LISTS),
+        Binding::new(&[Key::char('t')], CycleDecoder).listed("cycle decoder", &[View::Listening]),
+        Binding::new(&[Key::char('v')], CyclePreview).listed("cycle preview", &[View::Listening]),
+        Binding::new(&[Key::char('<')], ResizeSplit(-5))
+            .listed("shrink list", &[View::Listening])
+            .help_only(),
+        Binding::new(&[Key::char('>')], ResizeSplit(5))
+            .listed("grow list", &[View::Listening])
+            .help_only(),
+        Binding::new(&[Key::char('V')], ToggleWrap).listed("wrap preview", &[View::Listening]),
+        Binding::new(&[Key::char('H')], CycleHistogram)
+            .listed("publish histogram", &[View::Listening]),
+        Binding::new(&[Key::char('z')], TogglePause).listed("pause/resume", SEEK),
+        Binding::new(&[Key::char('*')], TogglePin).listed("pin", &[View::Listening]),
+        Binding::new(&[Key::char('f')], ToggleFollow).listed("follow", &[View::Listening]),
+        Binding::new(&[Key::char('x')], ToggleQuarantine).listed("quarantine", &[View::Listening]),
+        Binding::new(&[Key::char('A')], ToggleAutoRefresh).listed("auto-refresh", REFRESHABLE),
+        Binding::new(&[Key::char('c')], Compare).listed("compare", &[View::Subscriptions]),
+        Binding::new(&[Key::char('I')], Overview).listed("overview", &[View::Tenants]),
+        Binding::new(&[Key::char('r')], Refresh).listed("refresh", REFRESHABLE),
+        Binding::new(&[Key::char('T')], CycleSort).listed("sort column", TABLES),
+        Binding::new(&[Key::ctrl('t')], ReverseSort).listed("reverse sort", TABLES),
+        Binding::new(&[Key::char('=')], Columns)
+            .listed("columns...", &[View::Subscriptions, View::Consumers]),
+        Binding::new(&[Key::char('[')], PreviousPage).listed("prev page", &[View::Consumers]),
+        Binding::new(&[Key::char(']')], NextPage).listed("next page", &[View::Consumers]),
+        Binding::new(
+            &[Key::char('d')],
+            JumpToCompanion(CompanionTopic::DeadLetter),
+        )
+        .listed("dlq subs", &[View::Subscriptions]),
+        Binding::new(
+            &[Key::char('D')],
+            ListenToCompanion(CompanionTopic::DeadLetter),
+        )
+        .listed("dlq listen", &[View::Subscriptions]),
+        Binding::new(&[Key::char('e')], JumpToCompanion(CompanionTopic::Retry))
+            .listed("retry subs", &[View::Subscriptions]),
+        Binding::new(&[Key::char('E')], ListenToCompanion(CompanionTopic::Retry))
+            .listed("retry listen", &[View::Subscriptions]),
+        Binding::new(&[Key::char('S')], ExportSession).listed("export session", ALL),
+        Binding::new(&[Key::char('O')], OpenCapture).listed("open capture", ALL),
+        Binding::new(&[Key::char('a')], Actions)
+            .listed("actions", ALL)
+            .help_only(),
+        Binding::new(&[Key::char('M')], ToggleBookmark)
+            .listed("bookmark", &[View::Topics, View::Subscriptions]),
+        Binding::new(&[Key::char('\'')], Bookmarks).listed("bookmarks", ALL),
+        Binding::new(&[Key::char('X')], Contexts).listed("contexts", ALL),
+        Binding::new(&[Key::char(':')], Palette)
+            .listed("command", ALL)
+            .help_only(),
+        Binding::new(&[Key::char('?')], Help)
+            .listed("help", ALL)
+            .help_only(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keymap(bindings: Vec<Binding>) -> Keymap {
+        Keymap { bindings }
+    }
+
+    fn press(keymap: &Keymap, code: KeyCode, view: View, layer: Layer) -> Option<ControlEvent> {
+        keymap.resolve(
+            &KeyEvent::new(code, KeyModifiers::NONE),
+            &mut None,
+            view,
+            layer,
+        )
+    }
+
+    #[test]
+    fn default_bindings_do_not_conflict() {
+        assert!(Keymap::new().is_ok());
+    }
+
+    #[test]
+    fn rejects_a_key_bound_to_two_events_in_the_same_view() {
+        let conflicting = keymap(vec![
+            Binding::new(&[Key::char('x')], ControlEvent::Refresh).listed("refresh", ALL),
+            Binding::new(&[Key::char('x')], ControlEvent::Mark)
+                .listed("mark", &[View::Subscriptions]),
+        ]);
+
+        assert!(conflicting.check_conflicts().is_err());
+    }
+
+    #[test]
+    fn allows_a_key_reused_across_views_and_layers() {
+        let reused = keymap(vec![
+            Binding::new(&[Key::char('x')], ControlEvent::Refresh)
+                .listed("refresh", &[View::Topics]),
+            Binding::new(&[Key::char('x')], ControlEvent::Mark)
+                .listed("mark", &[View::Subscriptions]),
+            Binding::new(&[Key::char('x')], ControlEvent::Refuse)
+                .in_layer(Layer::Confirmation)
+                .listed("cancel", ALL),
+        ]);
+
+        assert!(reused.check_conflicts().is_ok());
+        assert_eq!(
+            press(
+                &reused,
+                KeyCode::Char('x'),
+                View::Subscriptions,
+                Layer::Base
+            ),
+            Some(ControlEvent::Mark)
+        );
+        assert_eq!(
+            press(
+                &reused,
+                KeyCode::Char('x'),
+                View::Topics,
+                Layer::Confirmation
+            ),
+            Some(ControlEvent::Refuse)
+        );
+    }
+
+    #[test]
+    fn input_layer_keeps_characters_as_text() {
+        let keymap = Keymap::new().unwrap();
+
+        assert_eq!(
+            press(&keymap, KeyCode::Char('j'), View::Listening, Layer::Input),
+            None
+        );
+        assert_eq!(
+            press(&keymap, KeyCode::Enter, View::Listening, Layer::Input),
+            Some(ControlEvent::Enter)
+        );
+        assert_eq!(
+            press(&keymap, KeyCode::Char('j'), View::Listening, Layer::Base),
+            Some(ControlEvent::Down)
+        );
+    }
+}
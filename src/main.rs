@@ -1,35 +1,60 @@
+pub mod admin_cache;
 pub mod auth;
+pub mod bench;
+pub mod connections;
+pub mod diagnostics;
 pub mod draw;
+pub mod filter;
+pub mod journal;
+pub mod keymap;
+pub mod message_export;
+pub mod migration;
+pub mod palette;
+mod probe;
+pub mod produce;
 pub mod pulsar_admin;
 pub mod pulsar_listener;
+pub mod recovery;
+pub mod session;
+pub mod setup;
+pub mod stats_export;
+pub mod table_export;
+pub mod tasks;
+pub mod token_refresh;
+pub mod ui_state;
 pub mod update;
 
 use crate::update::update;
 
-use auth::{auth, read_config};
+use admin_cache::AdminCache;
+use anyhow::anyhow;
+use auth::{auth, read_config, Auth, Config, Context, Token, DEFAULT_CONTEXT};
+use bench::{BenchReport, ConsumeReport};
 use clap::Parser;
-use pulsar::authentication::oauth2::{OAuth2Authentication, OAuth2Params};
-use pulsar::{Pulsar, TokioExecutor};
-use pulsar_admin::{fetch_clusters, fetch_namespaces};
-use pulsar_admin_sdk::apis::configuration::Configuration;
-use pulsar_listener::TopicEvent;
+use connections::Connections;
+use futures::StreamExt;
+use itertools::Itertools;
+use journal::Journal;
+use keymap::Keymap;
+use pulsar_admin::{fetch_clusters, fetch_namespace_limits};
+use pulsar_listener::{load_protobuf_descriptor, Decoder, SchemaDefinitions, TopicEvent};
+use session::SessionLog;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::io;
 use std::path::PathBuf;
-use std::{
-    io,
-    sync::{
-        mpsc::{channel, Receiver, Sender},
-        Arc,
-    },
-    thread,
-};
-use tokio::sync::Mutex;
+use std::time::{Duration, Instant};
+use tasks::TaskRegistry;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use ui_state::UiState;
 use update::{
-    App, ConfirmedCommand, Consumers, Listening, Namespace, Namespaces, PulsarApp, Resource,
-    Resources, SelectedPanel, Subscriptions, Tenant, Tenants, Topics,
+    App, Comparison, ConfirmationModal, ConfirmedCommand, Consumers, HistogramWindow, Listening,
+    Namespace, NamespaceLimits, NamespaceStats, Namespaces, Overview, PreviewMode, PulsarApp,
+    RefreshedView, Resource, Resources, SelectedPanel, Subscriptions, TableSort, Tenant, Tenants,
+    TopicStats, Topics,
 };
 
 use crossterm::{
-    event::{self, Event, KeyCode, KeyModifiers},
+    event::{Event, EventStream, KeyCode, KeyEvent},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -40,72 +65,293 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 struct Args {
     #[arg(short, long)]
     config: Option<PathBuf>,
+    /// Named context of the config to connect to instead of the top level cluster
+    #[arg(long)]
+    context: Option<String>,
+    /// Test connectivity to the configured cluster layer by layer and exit
+    #[arg(long)]
+    diagnose: bool,
+    /// Topic of the starting namespace to open the subscriptions of
+    #[arg(long)]
+    topic: Option<String>,
+    /// Start listening to --topic right away
+    #[arg(long, requires = "topic")]
+    listen: bool,
+    /// Store a secret read from stdin in the system keyring and exit, config values
+    /// `keyring:<NAME>` are then read from there
+    #[arg(long, value_name = "NAME")]
+    store_secret: Option<String>,
+    #[command(flatten)]
+    overrides: Overrides,
+}
+
+// Connection settings of an ad-hoc cluster, they take precedence over the selected context.
+#[derive(clap::Args, Debug)]
+struct Overrides {
+    /// Broker url, e.g. pulsar://localhost:6650
+    #[arg(long)]
+    pulsar_url: Option<String>,
+    /// Admin API url, e.g. http://localhost:8080
+    #[arg(long)]
+    admin_url: Option<String>,
+    /// Tenant to start in, the default namespace of the config is not used with it
+    #[arg(long)]
+    tenant: Option<String>,
+    /// Namespace to start in
+    #[arg(long)]
+    namespace: Option<String>,
+    /// Static token used instead of the configured auth
+    #[arg(long)]
+    token: Option<String>,
+}
+
+impl Overrides {
+    fn apply(&self, context: Context) -> Context {
+        Context {
+            pulsar_url: self
+                .pulsar_url
+                .clone()
+                .unwrap_or(context.pulsar_url),
+            pulsar_admin_url: self
+                .admin_url
+                .clone()
+                .unwrap_or(context.pulsar_admin_url),
+            default_namespace: match (&self.namespace, &self.tenant) {
+                (Some(namespace), _) => Some(namespace.clone()),
+                (None, Some(_)) => None,
+                (None, None) => context.default_namespace,
+            },
+            default_tenant: self
+                .tenant
+                .clone()
+                .unwrap_or(context.default_tenant),
+            production: context.production,
+            auth: match &self.token {
+                Some(token) => Auth::Token { token: token.clone() },
+                None => context.auth,
+            },
+        }
+    }
 }
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
 
-    match run(args).await {
+    if let Some(name) = &args.store_secret {
+        match setup::store_secret(name) {
+            Ok(()) => println!(
+                "Stored '{}', use it in the config as \"keyring:{}\".",
+                name, name
+            ),
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1)
+            }
+        }
+        return;
+    }
+    let config_path = match config_path(args.config.clone()) {
+        Ok(path) => path,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1)
+        }
+    };
+    if let Err(error) = setup::offer(&config_path).await {
+        eprintln!("{}", error);
+        std::process::exit(1)
+    }
+    let config = match read_config(config_path.clone()) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1)
+        }
+    };
+
+    if args.diagnose {
+        let context = args.context.as_deref().unwrap_or(DEFAULT_CONTEXT);
+        match config.context(context) {
+            Ok(context) => {
+                let context = args.overrides.apply(context);
+                if !diagnostics::print(&diagnostics::diagnose(context).await) {
+                    std::process::exit(1)
+                }
+            }
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1)
+            }
+        }
+        return;
+    }
+
+    match run(args, config_path, config).await {
         Ok(_) => println!("bye!"),
-        Err(error) => eprintln!("Failed unexpectedlly. Reason: {:?}", error),
+        Err(error) => eprintln!(
+            "Failed unexpectedlly. Reason: {:?}\nRun with --diagnose to see which connection layer fails.",
+            error
+        ),
     }
 }
 
-async fn run(args: Args) -> anyhow::Result<()> {
-    let config_path = args.config.unwrap_or_else(|| {
-        println!("Config not provided, reading from '$HOME/.config/lgm/config.toml'");
+fn config_path(path: Option<PathBuf>) -> anyhow::Result<PathBuf> {
+    match path {
+        Some(path) => Ok(path),
+        None => {
+            let path = default_config_dir()?
+                .join("lgm")
+                .join("config.toml");
+            println!("Config not provided, reading from '{}'", path.display());
+            Ok(path)
+        }
+    }
+}
 
-        #[allow(deprecated)] // XXX: Warning regarding Windows, we don't care now
-        std::env::home_dir()
-            .expect("Home dir not found")
-            .join(".config/lgm/config.toml")
-    });
-    let config = read_config(config_path)?;
-
-    let url = &config.pulsar_url.clone();
-
-    let builder = match &config.auth {
-        //TODO: Add token as auth here
-        auth::Auth::Token { token: _ } => Pulsar::builder(url, TokioExecutor),
-        auth::Auth::OAuth {
-            issuer_url,
-            audience,
-            credentials_file_url,
-        } => Pulsar::builder(url, TokioExecutor).with_auth_provider(
-            OAuth2Authentication::client_credentials(OAuth2Params {
-                issuer_url: issuer_url.clone(),
-                credentials_url: credentials_file_url.clone(),
-                audience: Some(audience.clone()),
-                scope: None,
-            }),
-        ),
+fn default_config_dir() -> anyhow::Result<PathBuf> {
+    let absolute = |name: &str| {
+        std::env::var_os(name)
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+    };
+
+    if cfg!(windows) {
+        absolute("APPDATA").ok_or(anyhow!("APPDATA is not set, pass the config with --config"))
+    } else {
+        absolute("XDG_CONFIG_HOME")
+            .or_else(|| absolute("HOME").map(|home| home.join(".config")))
+            .ok_or(anyhow!("HOME is not set, pass the config with --config"))
+    }
+}
+
+fn default_state_dir() -> Option<PathBuf> {
+    let absolute = |name: &str| {
+        std::env::var_os(name)
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
     };
 
-    let pulsar = builder.build().await?;
-    let pulsar = Arc::new(Mutex::new(pulsar));
+    if cfg!(windows) {
+        absolute("LOCALAPPDATA")
+    } else {
+        absolute("XDG_STATE_HOME")
+            .or_else(|| absolute("HOME").map(|home| home.join(".local").join("state")))
+    }
+}
+
+async fn run(args: Args, config_path: PathBuf, config: Config) -> anyhow::Result<()> {
+    let ui_state_path = UiState::path(&config_path);
+    let ui_state = UiState::load(&ui_state_path);
 
-    let default_tenant = config.default_tenant.clone();
+    let context_name = args
+        .context
+        .unwrap_or_else(|| DEFAULT_CONTEXT.to_string());
+    let context = args
+        .overrides
+        .apply(config.context(&context_name)?);
 
-    let pulsar_admin_url = config.pulsar_admin_url.clone();
-    let token = auth(config).await?;
-    let conf = Configuration {
-        base_path: format!("{}/admin/v2", pulsar_admin_url),
-        bearer_access_token: Some(token.access_token.clone()),
-        ..Configuration::default()
+    let token = auth(&context.auth).await?;
+    let mut connections = Connections::default();
+    let pulsar = connections
+        .pulsar_client(&context.pulsar_url, &context.auth, &token)
+        .await?;
+
+    let default_tenant = context.default_tenant.clone();
+    let default_namespace = context.default_namespace.clone();
+    // Queued as `:` commands once the app is up, as if typed in the command line
+    let deep_link = match &args.topic {
+        Some(topic) => {
+            let namespace = default_namespace.as_ref().ok_or(anyhow!(
+                "--topic needs --namespace or a default_namespace in the config"
+            ))?;
+            let mut commands = vec![format!("{}/{}/{}", default_tenant, namespace, topic)];
+            if args.listen {
+                commands.push(format!("listen {}", topic));
+            }
+            commands
+        }
+        None => vec![],
     };
+    let bookmarks = config
+        .bookmarks
+        .iter()
+        .chain(&ui_state.bookmarks)
+        .unique()
+        .cloned()
+        .collect();
+    let production = context.production;
+    let typed_confirmation = config.typed_confirmation;
+    let freshness_threshold = Duration::from_secs(config.freshness_threshold_secs);
+    let cache = AdminCache::new(Duration::from_secs(config.cache_ttl_secs));
+    let auto_refresh = config.auto_refresh;
+    let auto_refresh_interval = Duration::from_secs(config.auto_refresh_secs);
+    let stats_export = config.stats_export.clone();
+    let max_captured_messages = config.max_captured_messages;
+    let timestamp_format = config.timestamp_format.clone();
+    let hidden_subscription_columns = match &config.columns.subscriptions {
+        Some(shown) => update::hidden_columns(Subscriptions::COLUMNS, shown)
+            .map_err(|err| anyhow!("Invalid subscriptions columns in config: {}", err))?,
+        None => BTreeSet::new(),
+    };
+    let hidden_consumer_columns = match &config.columns.consumers {
+        Some(shown) => update::hidden_columns(Consumers::COLUMNS, shown)
+            .map_err(|err| anyhow!("Invalid consumers columns in config: {}", err))?,
+        None => BTreeSet::new(),
+    };
+    let protobuf_descriptors = config
+        .protobuf
+        .iter()
+        .map(|mapping| Ok((mapping.topic.clone(), load_protobuf_descriptor(mapping)?)))
+        .collect::<anyhow::Result<BTreeMap<_, _>>>()?;
+    let journal = Journal::new(
+        default_state_dir().map(|dir| dir.join("lgm")),
+        Duration::from_secs(config.journal_interval_secs),
+    );
+
+    let contexts = config
+        .contexts()
+        .into_iter()
+        .map(|(name, configured)| {
+            let context = if name == context_name {
+                context.clone()
+            } else {
+                configured
+            };
+            (name, context)
+        })
+        .collect();
+    let conf = connections.admin_config(&context.pulsar_admin_url, &context.auth, &token);
 
-    let (sender, receiver): (Sender<AppEvent>, Receiver<AppEvent>) = channel();
-    let control_sender = sender.clone();
-    //can we use tokio thread here?
-    let _handle = thread::spawn(move || listen_input(control_sender));
-    let namespaces: Vec<Namespace> = fetch_namespaces(&default_tenant, &conf).await?;
+    let keymap = Keymap::new()?;
+    let (sender, receiver) = unbounded_channel();
+    tokio::spawn(listen_input(sender.clone()));
+    let namespaces: Vec<Namespace> = cache.namespaces(&default_tenant, &conf).await?;
     let cluster_name: String = fetch_clusters(&conf)
         .await?
         .first()
         .cloned()
         .unwrap_or("unknown cluster".to_string());
 
+    let landing_namespace = default_namespace.and_then(|default_namespace| {
+        namespaces
+            .iter()
+            .position(|namespace| namespace.name == default_namespace)
+    });
+    let (active_resource, topics, limits) = match landing_namespace {
+        Some(position) => {
+            let namespace = &namespaces[position].name;
+            let topics = cache
+                .topics(&default_tenant, namespace, &conf)
+                .await?;
+            let limits = fetch_namespace_limits(&default_tenant, namespace, &conf)
+                .await
+                .unwrap_or_default();
+            (Resource::Topics, topics, limits)
+        }
+        None => (Resource::Namespaces, vec![], NamespaceLimits::default()),
+    };
+
     let mut app = App {
         pulsar: PulsarApp {
             receiver,
@@ -113,10 +359,24 @@ async fn run(args: Args) -> anyhow::Result<()> {
             client: pulsar,
             token,
             active_sub_handle: None,
+            active_pause_handle: None,
         },
         confirmation_modal: None,
+        input_modal: None,
+        action_menu: None,
+        list_filter: None,
+        produce_modal: None,
+        consume_report: None,
+        help_scroll: None,
+        filter_due: None,
+        stats_fetch: None,
+        view_refresh: None,
+        token_refresh: None,
+        probe: None,
+        keymap,
+        previous_key: None,
         info_to_show: None,
-        active_resource: Resource::Namespaces,
+        active_resource,
         resources: Resources {
             tenants: Tenants {
                 tenants: vec![Tenant { name: default_tenant }],
@@ -124,28 +384,145 @@ async fn run(args: Args) -> anyhow::Result<()> {
             },
             namespaces: Namespaces {
                 namespaces,
-                cursor: Some(0), // FIXME: will crash on empty namespaces
+                cursor: Some(landing_namespace.unwrap_or(0)), // FIXME: will crash on empty namespaces
+                limits,
+                stats: BTreeMap::new(),
+            },
+            topics: Topics {
+                cursor: if topics.is_empty() { None } else { Some(0) },
+                topics,
+                bookmarks,
+                stats: BTreeMap::new(),
+                sort: TableSort::default(),
             },
-            topics: Topics { topics: vec![], cursor: None },
             subscriptions: Subscriptions {
                 subscriptions: vec![],
                 cursor: None,
+                compare_with: None,
+                marked: BTreeSet::new(),
+                sort: TableSort::default(),
+                hidden_columns: hidden_subscription_columns,
+                dead_letter_policies: config.dead_letter.clone(),
             },
             consumers: Consumers {
                 consumers: vec![],
                 cursor: None,
+                sort: TableSort::default(),
+                hidden_columns: hidden_consumer_columns,
             },
+            comparison: Comparison { subscriptions: vec![] },
+            overview: Overview::default(),
             listening: Listening {
-                messages: vec![],
+                messages: VecDeque::new(),
+                pinned: vec![],
                 filtered_messages: vec![],
                 panel: SelectedPanel::Left,
                 cursor: None,
                 search: None,
+                filtered_search: None,
+                compiled_search: None,
+                decoder: Decoder::Json,
+                decoder_overridden: false,
+                schema_definitions: SchemaDefinitions::default(),
+                paused: false,
+                follow: false,
+                preview_wrap: true,
+                max_messages: max_captured_messages,
+                dropped: 0,
+                timestamp_format,
+                quarantine_only: false,
+                preview_mode: PreviewMode::Decoded,
+                histogram: HistogramWindow::Hidden,
+                preview_search: None,
+                browse_only: false,
+                protobuf_descriptors,
+                decode_errors: BTreeMap::new(),
+                receipts: VecDeque::new(),
+                message_sizes: BTreeMap::new(),
             },
         },
         pulsar_admin_cfg: conf,
+        cache,
+        connections,
+        contexts,
+        context: context_name,
         cluster_name,
+        production,
+        typed_confirmation,
+        fetched_at: Instant::now(),
+        freshness_threshold,
+        auto_refresh,
+        auto_refresh_interval,
+        auto_refreshed_at: Instant::now(),
+        tasks: TaskRegistry::default(),
+        journal,
+        session: SessionLog::default(),
+        snapshot_dir: stats_export
+            .as_ref()
+            .map(|export| export.dir.clone()),
+        ui_state,
+        ui_state_path,
     };
+    app.resources.topics.sort();
+    update::spawn_token_refresh(&mut app);
+
+    if let Some(export) = stats_export {
+        let namespaces = if export.namespaces.is_empty() {
+            app.resources
+                .selected_tenant_name()
+                .zip(app.resources.selected_namespace_name())
+                .map(|(tenant, namespace)| vec![(tenant.to_string(), namespace.to_string())])
+                .unwrap_or_default()
+        } else {
+            export
+                .namespaces
+                .iter()
+                .filter_map(|namespace| namespace.split_once('/'))
+                .map(|(tenant, namespace)| (tenant.to_string(), namespace.to_string()))
+                .collect()
+        };
+        let cfg = app.pulsar_admin_cfg.clone();
+        let auth = context.auth.clone();
+        let token = app.pulsar.token.clone();
+        let sender = app.pulsar.sender.clone();
+        app.tasks.spawn("stats export", |shutdown| {
+            stats_export::run(
+                namespaces,
+                export.dir,
+                Duration::from_secs(export.interval_secs),
+                (cfg, auth, token),
+                sender,
+                shutdown,
+            )
+        });
+    }
+
+    match app.active_resource {
+        Resource::Namespaces => update::load_namespace_stats(&mut app),
+        Resource::Topics => update::load_topic_stats(&mut app),
+        _ => {}
+    }
+
+    for input in deep_link {
+        let _ = app
+            .pulsar
+            .sender
+            .send(AppEvent::Palette { input, resumed: false });
+    }
+
+    match app.journal.load() {
+        Some(checkpoint) if !checkpoint.messages.is_empty() => {
+            app.confirmation_modal = Some(ConfirmationModal {
+                message: format!(
+                    "The previous session did not exit cleanly. Recover {} captured messages of '{}'?",
+                    checkpoint.messages.len(),
+                    checkpoint.topic.as_deref().unwrap_or("unknown topic")
+                ),
+                command: ConfirmedCommand::RecoverJournal { checkpoint },
+            })
+        }
+        _ => app.journal.clear().await,
+    }
 
     let mut stdout = io::stdout();
 
@@ -155,18 +532,37 @@ async fn run(args: Args) -> anyhow::Result<()> {
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
 
     let result = update(&mut terminal, &mut app).await;
+    let aborted_tasks = app.tasks.shutdown(Duration::from_secs(3)).await;
+    if result.is_ok() {
+        app.journal.clear().await;
+    }
 
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
+    if !aborted_tasks.is_empty() {
+        eprintln!(
+            "Background tasks did not stop in time and were aborted: {}",
+            aborted_tasks.join(", ")
+        );
+    }
+
     result
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum ControlEvent {
+    Actions,
+    ToggleBookmark,
+    Bookmarks,
+    OpenBookmark(String),
+    Palette,
+    Help,
     Enter,
     CycleSide,
     Yank,
+    YankPart(YankPart),
     Back,
     Esc,
     BackSpace,
@@ -181,82 +577,147 @@ pub enum ControlEvent {
     Accept,
     Refuse,
     ResetSubscription(ResetLength),
+    SetProperty,
+    Compare,
+    Overview,
+    Contexts,
+    SwitchContext(String),
+    Refresh,
+    Columns,
+    ToggleColumn(usize),
+    CycleSort,
+    ReverseSort,
+    Mark,
+    SeekTo,
+    EditLimits,
+    TogglePause,
+    ToggleFollow,
+    ToggleWrap,
+    // Grows the Listening message list by the given percent, negative shrinks it.
+    ResizeSplit(i16),
+    ToggleQuarantine,
+    ExportSession,
+    ToggleAutoRefresh,
+    NextPage,
+    PreviousPage,
+    Migrate,
+    RecoverTopic,
+    Bench,
+    ConsumeBench,
+    ToggleProbe,
+    PageUp,
+    PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    Top,
+    Bottom,
+    CycleDecoder,
+    CyclePreview,
+    CycleHistogram,
+    NextMatch,
+    PreviousMatch,
+    TogglePin,
+    YankProperty,
+    YankCommand,
+    ExportMessage,
+    ExportCapture,
+    SaveCapture,
+    OpenCapture,
+    FetchMessage,
+    Produce,
+    JumpToCompanion(CompanionTopic),
+    ListenToCompanion(CompanionTopic),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum YankPart {
+    Body,
+    Properties,
+    MessageId,
+    Message,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum ResetLength {
     OneHour,
     TwentyFourHours,
     Week,
 }
 
+// Topics created next to a subscription by the default client-side dead letter policy, named
+// `{topic}-{subscription}-DLQ` and `{topic}-{subscription}-RETRY`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompanionTopic {
+    DeadLetter,
+    Retry,
+}
+
+impl CompanionTopic {
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            CompanionTopic::DeadLetter => "DLQ",
+            CompanionTopic::Retry => "RETRY",
+        }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
 pub enum AppEvent {
     Input(KeyCode),
+    // Resolved into a `Control` event by the keymap, against the view and modal it arrives in.
+    Key(KeyEvent),
     Control(ControlEvent),
     Command(ConfirmedCommand),
+    NamespaceStats {
+        tenant: String,
+        namespace: String,
+        stats: NamespaceStats,
+    },
+    OverviewStats {
+        tenant: String,
+        namespace: String,
+        topics: usize,
+        stats: BTreeMap<String, TopicStats>,
+    },
+    TopicStats {
+        namespace: String,
+        stats: BTreeMap<String, TopicStats>,
+    },
+    // A `:` command, `resumed` once it was queued again after walking to the list it picks from.
+    Palette {
+        input: String,
+        resumed: bool,
+    },
     SubscriptionEvent(TopicEvent),
+    BenchProgress(String),
+    BenchFinished(anyhow::Result<BenchReport>),
+    ConsumeBenchFinished(anyhow::Result<ConsumeReport>),
+    ProbeSample(anyhow::Result<Option<Duration>>),
+    MigrationProgress(anyhow::Result<String>),
+    StatsExportFailed(anyhow::Error),
+    JournalFailed(anyhow::Error),
+    ViewRefreshed {
+        path: String,
+        view: anyhow::Result<RefreshedView>,
+    },
+    TokenRefreshed {
+        context: String,
+        token: anyhow::Result<Token>,
+    },
 }
 
-fn listen_input(sender: Sender<AppEvent>) {
-    loop {
-        let event = event::read().unwrap();
-
-        let input_event = match &event {
-            Event::Key(key) => Some(AppEvent::Input(key.code)),
-            _ => None,
-        };
-
-        if let Some(input_event) = input_event {
-            sender.send(input_event).unwrap()
-        }
-
-        let control_event = match &event {
-            Event::Key(key) => match key.code {
-                KeyCode::Char('a') if key.modifiers == KeyModifiers::CONTROL => {
-                    Some(AppEvent::Control(ControlEvent::Accept))
-                }
-                KeyCode::Char('n') => Some(AppEvent::Control(ControlEvent::Refuse)),
-                KeyCode::Char('c') | KeyCode::Char('q')
-                    if key.modifiers == KeyModifiers::CONTROL =>
-                {
-                    Some(AppEvent::Control(ControlEvent::Terminate))
-                }
-                KeyCode::Char('d') if key.modifiers == KeyModifiers::CONTROL => {
-                    Some(AppEvent::Control(ControlEvent::Delete))
-                }
-                KeyCode::Char('s') if key.modifiers == KeyModifiers::CONTROL => {
-                    Some(AppEvent::Control(ControlEvent::Subscribe))
-                }
-                KeyCode::Char('p') if key.modifiers == KeyModifiers::CONTROL => {
-                    Some(AppEvent::Control(ControlEvent::Skip))
-                }
-                KeyCode::Char('u') if key.modifiers == KeyModifiers::CONTROL => {
-                    Some(AppEvent::Control(ControlEvent::ClearInput))
-                }
-                KeyCode::Char('u') => Some(AppEvent::Control(ControlEvent::ResetSubscription(
-                    ResetLength::OneHour,
-                ))),
-                KeyCode::Char('i') => Some(AppEvent::Control(ControlEvent::ResetSubscription(
-                    ResetLength::TwentyFourHours,
-                ))),
-                KeyCode::Char('o') => Some(AppEvent::Control(ControlEvent::ResetSubscription(
-                    ResetLength::Week,
-                ))),
-                KeyCode::Tab => Some(AppEvent::Control(ControlEvent::CycleSide)),
-                KeyCode::Enter => Some(AppEvent::Control(ControlEvent::Enter)),
-                KeyCode::Char('h') | KeyCode::Left => Some(AppEvent::Control(ControlEvent::Back)),
-                KeyCode::Esc => Some(AppEvent::Control(ControlEvent::Esc)),
-                KeyCode::Backspace => Some(AppEvent::Control(ControlEvent::BackSpace)),
-                KeyCode::Char('j') | KeyCode::Down => Some(AppEvent::Control(ControlEvent::Down)),
-                KeyCode::Char('y') => Some(AppEvent::Control(ControlEvent::Yank)),
-                KeyCode::Char('k') | KeyCode::Up => Some(AppEvent::Control(ControlEvent::Up)),
-                KeyCode::Char('/') => Some(AppEvent::Control(ControlEvent::Search)),
-                _ => None,
-            },
-            _ => None,
+async fn listen_input(sender: UnboundedSender<AppEvent>) {
+    let mut events = EventStream::new();
+    while let Some(Ok(event)) = events.next().await {
+        let Event::Key(key) = event else {
+            continue;
         };
 
-        if let Some(control_event) = control_event {
-            sender.send(control_event).unwrap()
+        // The app is gone once nothing receives the events
+        if sender.send(AppEvent::Input(key.code)).is_err()
+            || sender.send(AppEvent::Key(key)).is_err()
+        {
+            break;
         }
     }
 }
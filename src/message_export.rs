@@ -0,0 +1,86 @@
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::update::SubMessage;
+
+// A captured message as written to disk, bodies that are JSON are embedded as such.
+#[derive(Serialize)]
+pub struct ExportedMessage<'a> {
+    pub topic: &'a str,
+    pub message_id: &'a str,
+    pub publish_time: Option<DateTime<Utc>>,
+    pub event_time: Option<DateTime<Utc>>,
+    pub key: Option<&'a str>,
+    pub producer_name: &'a str,
+    pub partition: Option<i32>,
+    pub chunks: Option<i32>,
+    pub batch_index: Option<i32>,
+    pub batch_size: Option<i32>,
+    pub compression: Option<&'a str>,
+    pub properties: BTreeMap<&'a str, &'a str>,
+    pub size: usize,
+    pub body: Value,
+    pub decode_error: Option<&'a str>,
+}
+
+impl<'a> ExportedMessage<'a> {
+    pub fn new(topic: &'a str, message: &'a SubMessage) -> ExportedMessage<'a> {
+        let meta = &message.meta;
+        let timestamp = |millis: u64| DateTime::from_timestamp_millis(millis as i64);
+
+        ExportedMessage {
+            topic,
+            message_id: &meta.message_id,
+            publish_time: timestamp(meta.publish_time),
+            event_time: meta.event_time.and_then(timestamp),
+            key: meta.key.as_deref(),
+            producer_name: &meta.producer_name,
+            partition: meta.partition,
+            chunks: meta.chunks,
+            batch_index: meta.batch_index,
+            batch_size: meta.batch_index.and(meta.batch_size),
+            compression: meta.compression.as_deref(),
+            properties: message.property_pairs().into_iter().collect(),
+            size: message.size,
+            body: serde_json::from_str(&message.body)
+                .unwrap_or_else(|_| Value::String(message.body.clone())),
+            decode_error: message.decode_error.as_deref(),
+        }
+    }
+}
+
+pub fn to_pretty_json(topic: &str, message: &SubMessage) -> anyhow::Result<String> {
+    serde_json::to_string_pretty(&ExportedMessage::new(topic, message))
+        .map_err(|err| anyhow!("Failed to serialize message: '{}'", err))
+}
+
+pub fn write_message(path: &str, topic: &str, message: &SubMessage) -> anyhow::Result<()> {
+    let content = to_pretty_json(topic, message)?;
+
+    std::fs::write(path, content + "\n")
+        .map_err(|err| anyhow!("Failed to export message to '{}': '{}'", path, err))
+}
+
+pub fn write_ndjson<'a>(
+    path: &str,
+    topic: &str,
+    messages: impl IntoIterator<Item = &'a SubMessage>,
+) -> anyhow::Result<usize> {
+    let mut content = String::new();
+    let mut count = 0;
+    for message in messages {
+        let line = serde_json::to_string(&ExportedMessage::new(topic, message))
+            .map_err(|err| anyhow!("Failed to serialize message: '{}'", err))?;
+        content.push_str(&line);
+        content.push('\n');
+        count += 1;
+    }
+
+    std::fs::write(path, content)
+        .map_err(|err| anyhow!("Failed to export messages to '{}': '{}'", path, err))?;
+
+    Ok(count)
+}
@@ -0,0 +1,157 @@
+use futures::TryStreamExt;
+use pulsar::consumer::InitialPosition;
+use pulsar::producer::Message;
+use pulsar::{ConsumerOptions, Pulsar, TokioExecutor};
+use pulsar_admin_sdk::apis::configuration::Configuration;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::pulsar_admin;
+use crate::tasks::ShutdownSignal;
+use crate::AppEvent;
+
+// The reader is considered caught up once no message arrived for this long.
+const REPLAY_IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+const PROGRESS_EVERY: usize = 1000;
+
+#[derive(Clone, Debug)]
+pub struct MigrationPlan {
+    pub tenant: String,
+    pub namespace: String,
+    pub source: String,
+    pub target: String,
+    pub replay: bool,
+}
+
+impl MigrationPlan {
+    fn fqn(&self, topic: &str) -> String {
+        format!("persistent://{}/{}/{}", self.tenant, self.namespace, topic)
+    }
+}
+
+pub async fn migrate(
+    plan: MigrationPlan,
+    cfg: Configuration,
+    pulsar: Arc<Mutex<Pulsar<TokioExecutor>>>,
+    event_sender: UnboundedSender<AppEvent>,
+    mut shutdown: ShutdownSignal,
+) {
+    let progress = event_sender.clone();
+    let report = move |message: String| {
+        let _ = progress.send(AppEvent::MigrationProgress(Ok(message)));
+    };
+
+    tokio::select! {
+        result = run_migration(&plan, &cfg, pulsar, &report) => {
+            let event = result.map(|replayed| match replayed {
+                Some(count) => format!(
+                    "Migrated '{}' to '{}', replayed {} messages.",
+                    plan.source, plan.target, count
+                ),
+                None => format!("Migrated '{}' to '{}'.", plan.source, plan.target),
+            });
+            let _ = event_sender.send(AppEvent::MigrationProgress(event));
+        }
+        _ = shutdown.wait() => {}
+    }
+}
+
+async fn run_migration(
+    plan: &MigrationPlan,
+    cfg: &Configuration,
+    pulsar: Arc<Mutex<Pulsar<TokioExecutor>>>,
+    report: &impl Fn(String),
+) -> anyhow::Result<Option<usize>> {
+    let (tenant, namespace) = (&plan.tenant, &plan.namespace);
+
+    let partitions = pulsar_admin::fetch_partitions(tenant, namespace, &plan.source, cfg).await?;
+    pulsar_admin::create_topic_like(
+        tenant,
+        namespace,
+        &plan.source,
+        &plan.target,
+        partitions,
+        cfg,
+    )
+    .await?;
+    report(format!("Migration: created '{}'.", plan.target));
+
+    let definition =
+        pulsar_admin::fetch_topic_definition(tenant, namespace, &plan.source, cfg).await?;
+    let copied =
+        pulsar_admin::apply_topic_definition(tenant, namespace, &plan.target, &definition, cfg)
+            .await?;
+    if !copied.is_empty() {
+        report(format!("Migration: copied {}.", copied.join(", ")));
+    }
+
+    if !plan.replay {
+        return Ok(None);
+    }
+
+    // Readers are bound to a single topic, partitioned topics are replayed partition by partition
+    let sources = if partitions > 0 {
+        (0..partitions)
+            .map(|partition| plan.fqn(&format!("{}-partition-{}", plan.source, partition)))
+            .collect()
+    } else {
+        vec![plan.fqn(&plan.source)]
+    };
+
+    let mut producer = pulsar
+        .lock()
+        .await
+        .producer()
+        .with_topic(plan.fqn(&plan.target))
+        .with_name(format!("lgm-migration-{}", Uuid::new_v4()))
+        .build()
+        .await?;
+
+    let mut replayed = 0;
+    for source in sources {
+        let mut reader = pulsar
+            .lock()
+            .await
+            .reader()
+            .with_topic(source)
+            .with_subscription(format!("lgm-migration-{}", Uuid::new_v4()))
+            .with_options(
+                ConsumerOptions::default().with_initial_position(InitialPosition::Earliest),
+            )
+            .into_reader::<Vec<u8>>()
+            .await?;
+
+        while let Ok(next) = tokio::time::timeout(REPLAY_IDLE_TIMEOUT, reader.try_next()).await {
+            let Some(message) = next? else { break };
+            let metadata = &message.payload.metadata;
+
+            producer
+                .send_non_blocking(Message {
+                    payload: message.payload.data.clone(),
+                    properties: metadata
+                        .properties
+                        .iter()
+                        .map(|property| (property.key.clone(), property.value.clone()))
+                        .collect(),
+                    partition_key: metadata.partition_key.clone(),
+                    ordering_key: metadata.ordering_key.clone(),
+                    event_time: metadata.event_time,
+                    ..Default::default()
+                })
+                .await?
+                .await?;
+
+            replayed += 1;
+            if replayed % PROGRESS_EVERY == 0 {
+                report(format!("Migration: replayed {} messages...", replayed));
+            }
+        }
+    }
+
+    producer.close().await?;
+
+    Ok(Some(replayed))
+}
@@ -0,0 +1,171 @@
+use anyhow::anyhow;
+use itertools::Itertools;
+
+use crate::keymap::View;
+
+pub const USAGE: &str =
+    "tenant/ns/topic[/sub], tenants, ns [tenant], topics [ns], subs [topic], consumers [sub], listen [topic] [start], seek <time>, ctx [name], q";
+
+// Commands typed after `:`, they are carried out with the same events as the keys.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteCommand {
+    Tenants,
+    // Shows the children of an item in the `parent` list, the named one or else the selected one.
+    Open {
+        parent: View,
+        name: Option<String>,
+    },
+    // Starts listening to the named or the selected topic, `start` is what the listen prompt takes.
+    Listen {
+        topic: Option<String>,
+        start: String,
+    },
+    // Seeks the selected subscriptions, or the ones matching in the topics view.
+    Seek(String),
+    Jump(ResourcePath),
+    Context(Option<String>),
+    Quit,
+}
+
+impl PaletteCommand {
+    pub fn parse(input: &str) -> anyhow::Result<PaletteCommand> {
+        let input = input.trim().trim_start_matches(':');
+        let (command, args) = input
+            .split_once(char::is_whitespace)
+            .map(|(command, args)| (command, args.trim()))
+            .unwrap_or((input, ""));
+        let name = (!args.is_empty()).then(|| args.to_string());
+        let open = |parent| PaletteCommand::Open { parent, name: name.clone() };
+
+        match command {
+            path if path.contains('/') && args.is_empty() => {
+                ResourcePath::parse(path).map(PaletteCommand::Jump)
+            }
+            "open" | "o" if !args.is_empty() => ResourcePath::parse(args).map(PaletteCommand::Jump),
+            "tenants" | "tenant" if args.is_empty() => Ok(PaletteCommand::Tenants),
+            "ns" | "namespaces" | "namespace" => Ok(open(View::Tenants)),
+            "topics" | "topic" => Ok(open(View::Namespaces)),
+            "subs" | "subscriptions" | "sub" => Ok(open(View::Topics)),
+            "consumers" | "consumer" => Ok(open(View::Subscriptions)),
+            "listen" => {
+                let mut words = args.split_whitespace();
+                Ok(PaletteCommand::Listen {
+                    topic: words.next().map(str::to_string),
+                    start: words.join(" "),
+                })
+            }
+            "seek" if !args.is_empty() => Ok(PaletteCommand::Seek(args.to_string())),
+            "ctx" | "context" => Ok(PaletteCommand::Context(name)),
+            "q" | "quit" if args.is_empty() => Ok(PaletteCommand::Quit),
+            _ => Err(anyhow!(
+                "Unknown command '{}', expected one of: {}",
+                input,
+                USAGE
+            )),
+        }
+    }
+}
+
+// `tenant[/namespace[/topic[/subscription]]]`, topic names may be given fully qualified.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourcePath {
+    pub tenant: String,
+    pub namespace: Option<String>,
+    pub topic: Option<String>,
+    // Selected once its topic's subscriptions are shown.
+    pub subscription: Option<String>,
+}
+
+impl ResourcePath {
+    pub fn parse(path: &str) -> anyhow::Result<ResourcePath> {
+        let path = path
+            .split_once("://")
+            .map(|(_, path)| path)
+            .unwrap_or(path);
+        // Subscription names may contain slashes, they take the rest of the path
+        let segments = path.splitn(4, '/').collect_vec();
+        if segments.iter().any(|segment| segment.is_empty()) {
+            return Err(anyhow!(
+                "Invalid path '{}', expected tenant[/namespace[/topic[/subscription]]]",
+                path
+            ));
+        }
+        let segment = |index: usize| {
+            segments
+                .get(index)
+                .map(|segment| segment.to_string())
+        };
+
+        Ok(ResourcePath {
+            tenant: segments[0].to_string(),
+            namespace: segment(1),
+            topic: segment(2),
+            subscription: segment(3),
+        })
+    }
+}
+
+// Position of a list view in the tenants > namespaces > topics > subscriptions > consumers
+// drill-down, `None` for the views opened from a list.
+pub fn depth(view: View) -> Option<usize> {
+    match view {
+        View::Tenants => Some(0),
+        View::Namespaces => Some(1),
+        View::Topics => Some(2),
+        View::Subscriptions => Some(3),
+        View::Consumers => Some(4),
+        View::Comparison | View::Overview | View::Listening => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> PaletteCommand {
+        PaletteCommand::parse(input).expect("command must parse")
+    }
+
+    #[test]
+    fn parses_commands_and_their_arguments() {
+        assert_eq!(parse(":tenants"), PaletteCommand::Tenants);
+        assert_eq!(
+            parse("subs orders"),
+            PaletteCommand::Open {
+                parent: View::Topics,
+                name: Some("orders".to_string()),
+            }
+        );
+        assert_eq!(
+            parse("listen orders -100"),
+            PaletteCommand::Listen {
+                topic: Some("orders".to_string()),
+                start: "-100".to_string(),
+            }
+        );
+        assert_eq!(parse("seek 1h"), PaletteCommand::Seek("1h".to_string()));
+        assert_eq!(parse("ctx"), PaletteCommand::Context(None));
+        assert_eq!(parse("q"), PaletteCommand::Quit);
+    }
+
+    #[test]
+    fn rejects_unknown_commands_and_missing_arguments() {
+        assert!(PaletteCommand::parse("frobnicate").is_err());
+        assert!(PaletteCommand::parse("seek").is_err());
+        assert!(PaletteCommand::parse("q now").is_err());
+    }
+
+    #[test]
+    fn parses_paths_with_slashes_in_the_subscription() {
+        assert_eq!(
+            parse("persistent://public/default/orders/team/billing"),
+            PaletteCommand::Jump(ResourcePath {
+                tenant: "public".to_string(),
+                namespace: Some("default".to_string()),
+                topic: Some("orders".to_string()),
+                subscription: Some("team/billing".to_string()),
+            })
+        );
+        assert!(ResourcePath::parse("public//orders").is_err());
+    }
+}
@@ -0,0 +1,170 @@
+use anyhow::anyhow;
+use futures::TryStreamExt;
+use itertools::Itertools;
+use pulsar::consumer::InitialPosition;
+use pulsar::{Consumer, ConsumerOptions, Pulsar, TokioExecutor};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::{oneshot, Mutex};
+use uuid::Uuid;
+
+use crate::tasks::ShutdownSignal;
+use crate::AppEvent;
+
+// Scratch topic the pings go through, created in the selected namespace when missing.
+pub const TOPIC: &str = "lgm-latency-probe";
+pub const SAMPLES: usize = 60;
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+// Pings not received back within this are counted as lost.
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct LatencyProbe {
+    pub topic_fqn: String,
+    // Round trips of the last pings, oldest first, `None` for lost pings.
+    pub samples: VecDeque<Option<Duration>>,
+    stop: oneshot::Sender<()>,
+}
+
+impl LatencyProbe {
+    // The receiver resolves once the probe is stopped, it is handed to `run`.
+    pub fn new(topic_fqn: String) -> (LatencyProbe, oneshot::Receiver<()>) {
+        let (stop, stopped) = oneshot::channel();
+        let probe = LatencyProbe {
+            topic_fqn,
+            samples: VecDeque::new(),
+            stop,
+        };
+
+        (probe, stopped)
+    }
+
+    pub fn record(&mut self, sample: Option<Duration>) {
+        if self.samples.len() >= SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn stop(self) {
+        let _ = self.stop.send(());
+    }
+
+    pub fn summary(&self) -> String {
+        let received = self
+            .samples
+            .iter()
+            .flatten()
+            .sorted()
+            .collect_vec();
+        let lost = self.samples.len() - received.len();
+        let last = match self.samples.back() {
+            Some(Some(latency)) => format!("{} ms", latency.as_millis()),
+            Some(None) => "lost".to_string(),
+            None => "waiting".to_string(),
+        };
+
+        match received.as_slice() {
+            [] => format!(" last {}, lost {} ", last, lost),
+            received => format!(
+                " last {}, p50 {} ms, max {} ms, lost {} ",
+                last,
+                received[received.len() / 2].as_millis(),
+                received[received.len() - 1].as_millis(),
+                lost
+            ),
+        }
+    }
+}
+
+pub async fn run(
+    topic_fqn: String,
+    pulsar: Arc<Mutex<Pulsar<TokioExecutor>>>,
+    event_sender: UnboundedSender<AppEvent>,
+    stopped: oneshot::Receiver<()>,
+    mut shutdown: ShutdownSignal,
+) {
+    tokio::select! {
+        result = ping(topic_fqn, pulsar, event_sender.clone()) => {
+            if let Err(err) = result {
+                let _ = event_sender.send(AppEvent::ProbeSample(Err(err)));
+            }
+        }
+        _ = stopped => {}
+        _ = shutdown.wait() => {}
+    }
+}
+
+async fn ping(
+    topic_fqn: String,
+    pulsar: Arc<Mutex<Pulsar<TokioExecutor>>>,
+    event_sender: UnboundedSender<AppEvent>,
+) -> anyhow::Result<()> {
+    // Other lgm instances may probe the same topic, only our own pings are measured
+    let probe_id = Uuid::new_v4().to_string();
+    let mut consumer: Consumer<Vec<u8>, TokioExecutor> = pulsar
+        .lock()
+        .await
+        .consumer()
+        .with_topic(topic_fqn.clone())
+        .with_subscription(format!("lgm-probe-{}", probe_id))
+        .with_options(
+            ConsumerOptions::default()
+                .durable(false)
+                .with_initial_position(InitialPosition::Latest),
+        )
+        .build()
+        .await
+        .map_err(|err| anyhow!("Failed to subscribe to '{}': '{}'", topic_fqn, err))?;
+    let mut producer = pulsar
+        .lock()
+        .await
+        .producer()
+        .with_topic(topic_fqn.clone())
+        .with_name(format!("lgm-probe-{}", probe_id))
+        .build()
+        .await
+        .map_err(|err| anyhow!("Failed to create producer: '{}'", err))?;
+
+    let mut interval = tokio::time::interval(PING_INTERVAL);
+    let mut in_flight: HashMap<u64, Instant> = HashMap::new();
+    let mut seq: u64 = 0;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                in_flight.retain(|_, sent_at| {
+                    let lost = sent_at.elapsed() > PING_TIMEOUT;
+                    if lost {
+                        let _ = event_sender.send(AppEvent::ProbeSample(Ok(None)));
+                    }
+                    !lost
+                });
+
+                in_flight.insert(seq, Instant::now());
+                producer
+                    .send_non_blocking(format!("{}:{}", probe_id, seq).into_bytes())
+                    .await
+                    .map_err(|err| anyhow!("Failed to publish ping: '{}'", err))?;
+                seq += 1;
+            }
+            message = consumer.try_next() => {
+                let message = message
+                    .map_err(|err| anyhow!("Failed to receive ping: '{}'", err))?
+                    .ok_or(anyhow!("Probe consumer closed"))?;
+                let _ = consumer.ack(&message).await;
+
+                let ping = String::from_utf8_lossy(&message.payload.data).to_string();
+                let sent_at = ping
+                    .split_once(':')
+                    .filter(|(id, _)| *id == probe_id)
+                    .and_then(|(_, seq)| seq.parse::<u64>().ok())
+                    .and_then(|seq| in_flight.remove(&seq));
+                if let Some(sent_at) = sent_at {
+                    let _ = event_sender.send(AppEvent::ProbeSample(Ok(Some(sent_at.elapsed()))));
+                }
+            }
+        }
+    }
+}
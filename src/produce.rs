@@ -0,0 +1,109 @@
+use anyhow::anyhow;
+use itertools::Itertools;
+use pulsar::producer::Message;
+use pulsar::{Pulsar, TokioExecutor};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug)]
+pub struct ProducedMessage {
+    pub body: String,
+    pub key: Option<String>,
+    pub properties: Vec<(String, String)>,
+}
+
+impl ProducedMessage {
+    // Properties are entered as `key=value` pairs separated by commas.
+    pub fn parse(body: &str, key: &str, properties: &str) -> anyhow::Result<ProducedMessage> {
+        let properties = properties
+            .split(',')
+            .map(str::trim)
+            .filter(|property| !property.is_empty())
+            .map(|property| {
+                property
+                    .split_once('=')
+                    .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+                    .ok_or(anyhow!("Expected 'key=value' property, got '{}'", property))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let key = key.trim();
+
+        Ok(ProducedMessage {
+            body: body.to_string(),
+            key: (!key.is_empty()).then(|| key.to_string()),
+            properties,
+        })
+    }
+
+    pub fn pulsar_client_command(&self, topic_fqn: &str) -> String {
+        let key = self
+            .key
+            .as_ref()
+            .map(|key| format!(" -k {}", shell_quote(key)))
+            .unwrap_or_default();
+        let properties = self
+            .properties
+            .iter()
+            .map(|(key, value)| format!(" -p {}", shell_quote(&format!("{}={}", key, value))))
+            .join("");
+
+        format!(
+            "pulsar-client produce {} -m {}{}{}",
+            topic_fqn,
+            shell_quote(&self.body),
+            key,
+            properties
+        )
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+pub async fn publish(
+    pulsar: Arc<Mutex<Pulsar<TokioExecutor>>>,
+    topic_fqn: &str,
+    message: &ProducedMessage,
+) -> anyhow::Result<String> {
+    let mut producer = pulsar
+        .lock()
+        .await
+        .producer()
+        .with_topic(topic_fqn)
+        .with_name(format!("lgm-produce-{}", Uuid::new_v4()))
+        .build()
+        .await
+        .map_err(|err| anyhow!("Failed to create producer: '{}'", err))?;
+
+    let send = async {
+        producer
+            .send_non_blocking(Message {
+                payload: message.body.clone().into_bytes(),
+                properties: message
+                    .properties
+                    .iter()
+                    .cloned()
+                    .collect::<HashMap<_, _>>(),
+                partition_key: message.key.clone(),
+                ..Default::default()
+            })
+            .await?
+            .await
+    };
+    let receipt = tokio::time::timeout(SEND_TIMEOUT, send)
+        .await
+        .map_err(|_| anyhow!("Timed out publishing to '{}'", topic_fqn))?
+        .map_err(|err| anyhow!("Failed to publish to '{}': '{}'", topic_fqn, err))?;
+    let _ = producer.close().await;
+
+    Ok(receipt
+        .message_id
+        .map(|id| format!("{}:{}", id.ledger_id, id.entry_id))
+        .unwrap_or_default())
+}
@@ -1,22 +1,68 @@
+use crate::pulsar_listener::MessageMeta;
+use crate::pulsar_listener::TopicEvent;
+use crate::recovery;
+use crate::stats_export::TopicDefinition;
+use crate::stats_export::TopicSnapshot;
+use crate::update::AckState;
 use crate::update::Consumer;
+use crate::update::DispatchLimit;
 use crate::update::Namespace;
+use crate::update::NamespaceLimits;
 use crate::update::Subscription;
+use crate::update::SubscriptionDetails;
 use crate::update::Tenant;
 use crate::update::Topic;
+use crate::update::TopicStats;
 use anyhow::anyhow;
+use chrono::DateTime;
 use chrono::TimeDelta;
 use chrono::Utc;
+use futures::FutureExt;
+use futures::StreamExt;
 use futures::TryFutureExt;
+use futures::TryStreamExt;
 use itertools::Itertools;
+use prost::Message;
+use pulsar::proto::SingleMessageMetadata;
 use pulsar_admin_sdk::apis::clusters_api::clusters_base_get_clusters;
 use pulsar_admin_sdk::apis::configuration::Configuration;
+use pulsar_admin_sdk::apis::namespaces_api::namespaces_get_policies;
 use pulsar_admin_sdk::apis::namespaces_api::namespaces_get_tenant_namespaces;
 use pulsar_admin_sdk::apis::namespaces_api::namespaces_get_topics;
+use pulsar_admin_sdk::apis::namespaces_api::namespaces_remove_max_consumers_per_subscription;
+use pulsar_admin_sdk::apis::namespaces_api::namespaces_remove_max_consumers_per_topic;
+use pulsar_admin_sdk::apis::namespaces_api::namespaces_remove_max_producers_per_topic;
+use pulsar_admin_sdk::apis::namespaces_api::namespaces_set_max_consumers_per_subscription;
+use pulsar_admin_sdk::apis::namespaces_api::namespaces_set_max_consumers_per_topic;
+use pulsar_admin_sdk::apis::namespaces_api::namespaces_set_max_producers_per_topic;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_create_non_partitioned_topic;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_create_partitioned_topic;
 use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_delete_subscription;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_get_internal_stats;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_get_max_consumers;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_get_max_producers;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_get_message_ttl;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_get_partitioned_metadata;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_get_properties;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_get_retention;
 use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_get_stats;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_get_subscriptions;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_remove_subscription_level_dispatch_rate;
 use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_reset_cursor;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_set_max_consumers;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_set_max_producers;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_set_message_ttl;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_set_retention;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_set_subscription_level_dispatch_rate;
 use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_skip_all_messages;
+use pulsar_admin_sdk::apis::persistent_topic_api::persistent_topics_update_subscription_properties;
+use pulsar_admin_sdk::apis::schemas_api::schemas_resource_get_schema;
+use pulsar_admin_sdk::apis::schemas_api::schemas_resource_post_schema;
 use pulsar_admin_sdk::apis::tenants_api::tenants_base_get_tenants;
+use pulsar_admin_sdk::models::DispatchRateImpl;
+use pulsar_admin_sdk::models::GetSchemaResponse;
+use pulsar_admin_sdk::models::PostSchemaPayload;
+use std::collections::{BTreeMap, HashMap};
 
 pub async fn fetch_clusters(cfg: &Configuration) -> anyhow::Result<Vec<String>> {
     clusters_base_get_clusters(cfg)
@@ -51,6 +97,7 @@ pub async fn fetch_consumers(
     subscription: &str,
     cfg: &Configuration,
 ) -> anyhow::Result<Vec<Consumer>> {
+    // Backlog sizes and publishers are not shown here and are expensive on busy topics
     let res = persistent_topics_get_stats(
         cfg,
         tenant,
@@ -58,9 +105,9 @@ pub async fn fetch_consumers(
         topic,
         None,
         None,
-        Some(true),
         None,
         None,
+        Some(true),
         None,
     );
     let result = res
@@ -76,12 +123,16 @@ pub async fn fetch_consumers(
         })?
         .subscriptions
         .and_then(|subs| {
-            subs.get(subscription)
-                .and_then(|sub| sub.consumers.clone())
-                .map(|consumers| {
+            subs.get(subscription).and_then(|sub| {
+                let active_consumer = sub.active_consumer_name.clone();
+                sub.consumers.clone().map(|consumers| {
                     consumers
                         .into_iter()
                         .map(|consumer_stats| Consumer {
+                            // Set for Failover and Exclusive subscriptions only
+                            active: active_consumer.is_some()
+                                && consumer_stats.consumer_name == active_consumer,
+                            key_hash_ranges: consumer_stats.key_hash_ranges.unwrap_or_default(),
                             name: consumer_stats
                                 .consumer_name
                                 .unwrap_or("Unknown name".to_string()),
@@ -89,15 +140,39 @@ pub async fn fetch_consumers(
                                 .connected_since
                                 .unwrap_or("Unknown".to_string()),
                             unacked_messages: consumer_stats.unacked_messages.unwrap_or(-1),
+                            address: consumer_stats
+                                .address
+                                .unwrap_or("Unknown".to_string()),
+                            client_version: consumer_stats
+                                .client_version
+                                .unwrap_or("Unknown".to_string()),
+                            available_permits: consumer_stats.available_permits.unwrap_or(0),
+                            msg_rate_out: consumer_stats.msg_rate_out.unwrap_or(0.0),
+                            msg_throughput_out: consumer_stats.msg_throughput_out.unwrap_or(0.0),
                         })
                         .collect()
                 })
+            })
         })
         .unwrap_or(vec![]);
 
     Ok(result)
 }
 
+pub async fn fetch_subscription_names(
+    tenant: &str,
+    namespace: &str,
+    topic: &str,
+    cfg: &Configuration,
+) -> anyhow::Result<Vec<String>> {
+    persistent_topics_get_subscriptions(cfg, tenant, namespace, topic, None)
+        .await
+        .map_err(|err| anyhow!("Failed to fetch subscriptions of '{}': '{}'", topic, err))
+}
+
+// Subscription dispatch rates are fetched this many at a time, one request per subscription.
+const DISPATCH_RATE_CONCURRENCY: usize = 16;
+
 pub async fn fetch_subs(
     tenant: &str,
     namespace: &str,
@@ -116,8 +191,15 @@ pub async fn fetch_subs(
         None,
         None,
     );
+    let internal_stats =
+        persistent_topics_get_internal_stats(cfg, tenant, namespace, topic, None, None);
+    let (res, internal_stats) = tokio::join!(res, internal_stats);
+    // Ack holes are only visible in the internal stats, a failure there only hides them
+    let cursors = internal_stats
+        .ok()
+        .and_then(|stats| stats.cursors)
+        .unwrap_or_default();
     let result = res
-        .await
         .map_err(|err| {
             anyhow!(
                 "Failed to fetch subscriptions (topic stats) {} {} {} {}",
@@ -142,14 +224,653 @@ pub async fn fetch_subs(
                         .clone()
                         .map(|consumers| consumers.len())
                         .unwrap_or(0),
+                    properties: value
+                        .subscription_properties
+                        .clone()
+                        .map(|properties| properties.into_iter().collect())
+                        .unwrap_or_default(),
+                    ack_state: cursors
+                        .get(key)
+                        .map(|cursor| {
+                            AckState::parse(
+                                cursor.mark_delete_position.clone(),
+                                cursor
+                                    .individually_deleted_messages
+                                    .as_deref()
+                                    .unwrap_or_default(),
+                            )
+                        })
+                        .unwrap_or_default(),
+                    msg_rate_out: value.msg_rate_out.unwrap_or(0.0),
+                    msg_throughput_out: value.msg_throughput_out.unwrap_or(0.0),
+                    dispatch_limit: None,
                 })
                 .collect_vec()
         })
         .unwrap_or(vec![]);
 
+    // Subscription level policies override the topic level, which overrides the namespace level.
+    // The policies only annotate the list, a failed lookup (no permission, old broker) leaves the
+    // limit unknown instead of failing the view
+    let inherited = match fetch_dispatch_limit(
+        format!(
+            "{}/persistent/{}/{}/{}/subscriptionDispatchRate",
+            cfg.base_path, tenant, namespace, topic
+        ),
+        "topic",
+        cfg,
+    )
+    .await
+    .ok()
+    .flatten()
+    {
+        Some(limit) => Some(limit),
+        None => fetch_dispatch_limit(
+            format!(
+                "{}/namespaces/{}/{}/subscriptionDispatchRate",
+                cfg.base_path, tenant, namespace
+            ),
+            "namespace",
+            cfg,
+        )
+        .await
+        .ok()
+        .flatten(),
+    };
+    let mut own_limits: HashMap<String, Option<DispatchLimit>> = futures::stream::iter(
+        result
+            .iter()
+            .map(|sub| {
+                let url = format!(
+                    "{}/persistent/{}/{}/{}/{}/dispatchRate",
+                    cfg.base_path, tenant, namespace, topic, sub.name
+                );
+                fetch_dispatch_limit(url, "subscription", cfg)
+                    .map(|limit| (sub.name.clone(), limit.ok().flatten()))
+            })
+            .collect_vec(),
+    )
+    .buffer_unordered(DISPATCH_RATE_CONCURRENCY)
+    .collect()
+    .await;
+
+    let result = result
+        .into_iter()
+        .map(|sub| Subscription {
+            dispatch_limit: own_limits
+                .remove(&sub.name)
+                .flatten()
+                .or(inherited.clone()),
+            ..sub
+        })
+        .collect();
+
     Ok(result)
 }
 
+// Reads a dispatch rate policy, `None` when it is unset or unlimited.
+async fn fetch_dispatch_limit(
+    url: String,
+    level: &'static str,
+    cfg: &Configuration,
+) -> anyhow::Result<Option<DispatchLimit>> {
+    let Some(rate) = fetch_dispatch_rate(&url, cfg).await? else {
+        return Ok(None);
+    };
+    let period = rate.rate_period_in_second.unwrap_or(1).max(1) as f64;
+    let per_sec = |limit: Option<i64>| {
+        limit
+            .filter(|limit| *limit > 0)
+            .map(|limit| limit as f64 / period)
+    };
+
+    let limit = DispatchLimit {
+        msg_per_sec: per_sec(
+            rate.dispatch_throttling_rate_in_msg
+                .map(i64::from),
+        ),
+        bytes_per_sec: per_sec(rate.dispatch_throttling_rate_in_byte),
+        level,
+    };
+
+    Ok((limit.msg_per_sec.is_some() || limit.bytes_per_sec.is_some()).then_some(limit))
+}
+
+// XXX: The generated client drops the response body of the dispatch rate endpoints, query them
+// directly
+async fn fetch_dispatch_rate(
+    url: &str,
+    cfg: &Configuration,
+) -> anyhow::Result<Option<DispatchRateImpl>> {
+    let mut request = cfg.client.get(url);
+    if let Some(token) = &cfg.bearer_access_token {
+        request = request.bearer_auth(token);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|err| anyhow!("Failed to fetch dispatch rate: '{}'", err))?;
+    // Brokers with topic level policies disabled answer 405, there is no topic level rate then
+    if response.status() == 405 {
+        return Ok(None);
+    }
+    let body = response
+        .error_for_status()
+        .map_err(|err| anyhow!("Failed to fetch dispatch rate: '{}'", err))?
+        .text()
+        .await
+        .map_err(|err| anyhow!("Failed to fetch dispatch rate: '{}'", err))?;
+
+    // Unset policies come back as 204 with an empty body
+    if body.trim().is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_str::<DispatchRateImpl>(&body)
+        .map(Some)
+        .map_err(|err| anyhow!("Failed to parse dispatch rate: '{}'", err))
+}
+
+// Details of the subscriptions of one topic, its stats are fetched once for all of them.
+pub async fn fetch_subscriptions_details(
+    tenant: &str,
+    namespace: &str,
+    topic: &str,
+    subscriptions: &[String],
+    cfg: &Configuration,
+) -> anyhow::Result<Vec<SubscriptionDetails>> {
+    let stats = persistent_topics_get_stats(
+        cfg,
+        tenant,
+        namespace,
+        topic,
+        None,
+        None,
+        Some(true),
+        None,
+        None,
+        None,
+    );
+    let internal_stats =
+        persistent_topics_get_internal_stats(cfg, tenant, namespace, topic, None, None);
+    let (stats, internal_stats) = tokio::join!(stats, internal_stats);
+    let stats = stats
+        .map_err(|err| {
+            anyhow!(
+                "Failed to fetch topic stats {} {} {} {}",
+                tenant,
+                namespace,
+                topic,
+                err
+            )
+        })?
+        .subscriptions
+        .unwrap_or_default();
+    let cursors = internal_stats
+        .map_err(|err| {
+            anyhow!(
+                "Failed to fetch internal topic stats {} {} {} {}",
+                tenant,
+                namespace,
+                topic,
+                err
+            )
+        })?
+        .cursors
+        .unwrap_or_default();
+
+    subscriptions
+        .iter()
+        .map(|subscription| {
+            let stats = stats
+                .get(subscription)
+                .cloned()
+                .ok_or(anyhow!("Subscription '{}' not found", subscription))?;
+            let cursor = cursors.get(subscription);
+
+            Ok(SubscriptionDetails {
+                name: subscription.to_string(),
+                sub_type: stats.r#type.unwrap_or("no_type".to_string()),
+                backlog_size: stats.msg_backlog.unwrap_or(0),
+                unacked_messages: stats.unacked_messages.unwrap_or(0),
+                msg_rate_out: stats.msg_rate_out.unwrap_or(0.0),
+                msg_throughput_out: stats.msg_throughput_out.unwrap_or(0.0),
+                message_ack_rate: stats.message_ack_rate.unwrap_or(0.0),
+                msg_rate_redeliver: stats.msg_rate_redeliver.unwrap_or(0.0),
+                mark_delete_position: cursor
+                    .and_then(|cursor| cursor.mark_delete_position.clone())
+                    .unwrap_or("unknown".to_string()),
+                read_position: cursor
+                    .and_then(|cursor| cursor.read_position.clone())
+                    .unwrap_or("unknown".to_string()),
+                consumers: stats
+                    .consumers
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|consumer| {
+                        consumer
+                            .consumer_name
+                            .unwrap_or("Unknown name".to_string())
+                    })
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+pub async fn fetch_schema(
+    tenant: &str,
+    namespace: &str,
+    topic: &str,
+    cfg: &Configuration,
+) -> anyhow::Result<GetSchemaResponse> {
+    schemas_resource_get_schema(cfg, tenant, namespace, topic, None)
+        .await
+        .map_err(|err| anyhow!("Failed to fetch schema: '{}'", err))
+}
+
+// Partition count of the topic, zero for non-partitioned topics.
+pub async fn fetch_partitions(
+    tenant: &str,
+    namespace: &str,
+    topic: &str,
+    cfg: &Configuration,
+) -> anyhow::Result<i32> {
+    persistent_topics_get_partitioned_metadata(cfg, tenant, namespace, topic, None, Some(false))
+        .await
+        .map(|metadata| metadata.partitions.unwrap_or(0))
+        .map_err(|err| anyhow!("Failed to fetch partitioned metadata: '{}'", err))
+}
+
+pub async fn fetch_message_by_id(
+    tenant: &str,
+    namespace: &str,
+    topic: &str,
+    message_id: &str,
+    cfg: &Configuration,
+) -> anyhow::Result<Vec<TopicEvent>> {
+    let (ledger_id, entry_id) = message_id
+        .trim()
+        .split_once(':')
+        .and_then(|(ledger, entry)| Some((ledger.parse::<u64>().ok()?, entry.parse::<u64>().ok()?)))
+        .ok_or(anyhow!("Expected 'ledgerId:entryId', got '{}'", message_id))?;
+
+    let url = format!(
+        "{}/persistent/{}/{}/{}/ledger/{}/entry/{}",
+        cfg.base_path, tenant, namespace, topic, ledger_id, entry_id
+    );
+    let mut request = cfg.client.get(url);
+    if let Some(token) = &cfg.bearer_access_token {
+        request = request.bearer_auth(token);
+    }
+    let response = request
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| anyhow!("Failed to fetch message {}: '{}'", message_id, err))?;
+
+    let headers = response.headers().clone();
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string())
+    };
+    let timestamp = |name: &str| {
+        header(name).and_then(|value| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|time| time.timestamp_millis() as u64)
+                .ok()
+                .or(value.parse().ok())
+        })
+    };
+    let properties = headers
+        .iter()
+        .filter_map(|(name, value)| {
+            let key = name.as_str().strip_prefix("x-pulsar-property-")?;
+            Some(format!("{}:{}", key, value.to_str().ok()?))
+        })
+        .collect_vec();
+    let meta = MessageMeta {
+        message_id: format!("{}:{}", ledger_id, entry_id),
+        publish_time: timestamp("x-pulsar-publish-time").unwrap_or_default(),
+        event_time: timestamp("x-pulsar-event-time").filter(|event_time| *event_time > 0),
+        key: header("x-pulsar-partition-key"),
+        producer_name: header("x-pulsar-producer-name").unwrap_or_default(),
+        partition: recovery::split_partition(topic).1,
+        compression: header("x-pulsar-compression").filter(|compression| compression != "NONE"),
+        ..MessageMeta::default()
+    };
+    let batch_size = header("x-pulsar-num-batch-message").and_then(|size| size.parse::<i32>().ok());
+
+    let data = response
+        .bytes()
+        .await
+        .map_err(|err| anyhow!("Failed to read message {}: '{}'", message_id, err))?
+        .to_vec();
+
+    let single = |data: Vec<u8>| TopicEvent {
+        size: data.len(),
+        data,
+        properties: properties.clone(),
+        meta: meta.clone(),
+        chunk: None,
+    };
+
+    let batch = batch_size
+        .filter(|batch_size| *batch_size > 1)
+        .and_then(|batch_size| Some((batch_size, split_batch(&data, batch_size)?)));
+
+    match batch {
+        Some((batch_size, messages)) => Ok(messages
+            .into_iter()
+            .enumerate()
+            .map(|(index, (metadata, payload))| TopicEvent {
+                size: payload.len(),
+                data: payload,
+                properties: metadata
+                    .properties
+                    .iter()
+                    .map(|keyvalue| format!("{}:{}", keyvalue.key, keyvalue.value))
+                    .collect(),
+                meta: MessageMeta {
+                    event_time: metadata
+                        .event_time
+                        .filter(|event_time| *event_time > 0),
+                    key: metadata.partition_key,
+                    batch_index: Some(index as i32),
+                    batch_size: Some(batch_size),
+                    ..meta.clone()
+                },
+                chunk: None,
+            })
+            .collect()),
+        // Brokers returning only the first message of a batch send it unwrapped
+        None => Ok(vec![single(data)]),
+    }
+}
+
+// Batched payloads are a sequence of size prefixed single message metadata, each followed by
+// its payload.
+fn split_batch(data: &[u8], batch_size: i32) -> Option<Vec<(SingleMessageMetadata, Vec<u8>)>> {
+    let mut rest = data;
+    let mut messages = vec![];
+    for _ in 0..batch_size {
+        let (size, tail) = rest.split_first_chunk::<4>()?;
+        let size = u32::from_be_bytes(*size) as usize;
+        let metadata = SingleMessageMetadata::decode(tail.get(..size)?).ok()?;
+        let tail = &tail[size..];
+        let payload_size = usize::try_from(metadata.payload_size).ok()?;
+        messages.push((metadata, tail.get(..payload_size)?.to_vec()));
+        rest = &tail[payload_size..];
+    }
+
+    Some(messages)
+}
+
+pub async fn create_topic_like(
+    tenant: &str,
+    namespace: &str,
+    source: &str,
+    target: &str,
+    partitions: i32,
+    cfg: &Configuration,
+) -> anyhow::Result<()> {
+    let properties = if partitions > 0 {
+        None
+    } else {
+        persistent_topics_get_properties(cfg, tenant, namespace, source, None)
+            .await
+            .ok()
+    };
+
+    create_topic(tenant, namespace, target, partitions, properties, cfg).await
+}
+
+// Creates a partitioned topic when `partitions` is positive, properties only apply to
+// non-partitioned topics.
+pub async fn create_topic(
+    tenant: &str,
+    namespace: &str,
+    topic: &str,
+    partitions: i32,
+    properties: Option<HashMap<String, String>>,
+    cfg: &Configuration,
+) -> anyhow::Result<()> {
+    if partitions > 0 {
+        persistent_topics_create_partitioned_topic(cfg, tenant, namespace, topic, partitions, None)
+            .await
+            .map_err(|err| anyhow!("Failed to create partitioned topic: '{}'", err))
+    } else {
+        persistent_topics_create_non_partitioned_topic(
+            cfg, tenant, namespace, topic, None, properties,
+        )
+        .await
+        .map_err(|err| anyhow!("Failed to create topic: '{}'", err))
+    }
+}
+
+pub async fn fetch_topic_definition(
+    tenant: &str,
+    namespace: &str,
+    topic: &str,
+    cfg: &Configuration,
+) -> anyhow::Result<TopicDefinition> {
+    let schema = match schemas_resource_get_schema(cfg, tenant, namespace, topic, None).await {
+        Ok(schema) => Some(PostSchemaPayload {
+            properties: schema.properties,
+            schema: schema.data,
+            r#type: schema
+                .r#type
+                .and_then(|schema_type| serde_json::to_value(schema_type).ok())
+                .and_then(|schema_type| schema_type.as_str().map(|s| s.to_string())),
+        }),
+        Err(pulsar_admin_sdk::apis::Error::ResponseError(response))
+            if response.status.as_u16() == 404 =>
+        {
+            None
+        }
+        Err(err) => return Err(anyhow!("Failed to fetch schema: '{}'", err)),
+    };
+
+    // XXX: Unset topic policies come back as empty bodies, which fail to deserialize
+    Ok(TopicDefinition {
+        schema,
+        retention: persistent_topics_get_retention(
+            cfg,
+            tenant,
+            namespace,
+            topic,
+            None,
+            Some(false),
+            None,
+        )
+        .await
+        .ok(),
+        message_ttl: persistent_topics_get_message_ttl(
+            cfg,
+            tenant,
+            namespace,
+            topic,
+            Some(false),
+            None,
+            None,
+        )
+        .await
+        .ok(),
+        max_producers: persistent_topics_get_max_producers(
+            cfg,
+            tenant,
+            namespace,
+            topic,
+            Some(false),
+            None,
+            None,
+        )
+        .await
+        .ok(),
+        max_consumers: persistent_topics_get_max_consumers(
+            cfg,
+            tenant,
+            namespace,
+            topic,
+            None,
+            Some(false),
+            None,
+        )
+        .await
+        .ok(),
+    })
+}
+
+pub async fn apply_topic_definition(
+    tenant: &str,
+    namespace: &str,
+    topic: &str,
+    definition: &TopicDefinition,
+    cfg: &Configuration,
+) -> anyhow::Result<Vec<&'static str>> {
+    let mut applied = vec![];
+
+    if let Some(schema) = &definition.schema {
+        schemas_resource_post_schema(cfg, tenant, namespace, topic, None, Some(schema.clone()))
+            .await
+            .map_err(|err| anyhow!("Failed to create schema: '{}'", err))?;
+        applied.push("schema");
+    }
+
+    if let Some(retention) = &definition.retention {
+        persistent_topics_set_retention(
+            cfg,
+            tenant,
+            namespace,
+            topic,
+            None,
+            None,
+            Some(retention.clone()),
+        )
+        .await
+        .map_err(|err| anyhow!("Failed to set retention: '{}'", err))?;
+        applied.push("retention");
+    }
+
+    if let Some(ttl) = definition.message_ttl {
+        persistent_topics_set_message_ttl(cfg, tenant, namespace, topic, ttl, None, None)
+            .await
+            .map_err(|err| anyhow!("Failed to set message TTL: '{}'", err))?;
+        applied.push("message TTL");
+    }
+
+    if let Some(max_producers) = definition.max_producers {
+        persistent_topics_set_max_producers(
+            cfg,
+            tenant,
+            namespace,
+            topic,
+            None,
+            None,
+            Some(max_producers),
+        )
+        .await
+        .map_err(|err| anyhow!("Failed to set max producers: '{}'", err))?;
+        applied.push("max producers");
+    }
+
+    if let Some(max_consumers) = definition.max_consumers {
+        persistent_topics_set_max_consumers(
+            cfg,
+            tenant,
+            namespace,
+            topic,
+            None,
+            None,
+            Some(max_consumers),
+        )
+        .await
+        .map_err(|err| anyhow!("Failed to set max consumers: '{}'", err))?;
+        applied.push("max consumers");
+    }
+
+    Ok(applied)
+}
+
+// Position `count` entries back on every partition of the topic, keyed by partition index, `None`
+// for non-partitioned topics. Batches are single entries, so more messages may be read.
+pub async fn fetch_positions_entries_back(
+    tenant: &str,
+    namespace: &str,
+    topic: &str,
+    count: u64,
+    cfg: &Configuration,
+    concurrency: usize,
+) -> anyhow::Result<BTreeMap<Option<i32>, (u64, u64)>> {
+    let partitions = fetch_partitions(tenant, namespace, topic, cfg).await?;
+    if partitions == 0 {
+        let position = fetch_position_entries_back(tenant, namespace, topic, count, cfg).await?;
+        return Ok(BTreeMap::from([(
+            recovery::split_partition(topic).1,
+            position,
+        )]));
+    }
+
+    futures::stream::iter(
+        (0..partitions)
+            .map(|partition| async move {
+                let name = format!("{}-partition-{}", topic, partition);
+                fetch_position_entries_back(tenant, namespace, &name, count, cfg)
+                    .await
+                    .map(|position| (Some(partition), position))
+            })
+            .collect_vec(),
+    )
+    .buffer_unordered(concurrency)
+    .try_collect()
+    .await
+}
+
+// Position `count` entries before the end of the topic. Entries are counted across the retained
+// ledgers, a batched entry holds several messages.
+async fn fetch_position_entries_back(
+    tenant: &str,
+    namespace: &str,
+    topic: &str,
+    count: u64,
+    cfg: &Configuration,
+) -> anyhow::Result<(u64, u64)> {
+    let stats = persistent_topics_get_internal_stats(cfg, tenant, namespace, topic, None, None)
+        .await
+        .map_err(|err| anyhow!("Failed to fetch internal topic stats: '{}'", err))?;
+
+    let current_ledger_entries = stats.current_ledger_entries.unwrap_or(0).max(0) as u64;
+    let mut remaining = count;
+    let mut position = (0, 0);
+
+    // The last ledger in the list is the one currently written to
+    for (index, ledger) in stats
+        .ledgers
+        .unwrap_or_default()
+        .iter()
+        .rev()
+        .enumerate()
+    {
+        let ledger_id = ledger.ledger_id.unwrap_or(0).max(0) as u64;
+        let entries = if index == 0 {
+            current_ledger_entries
+        } else {
+            ledger.entries.unwrap_or(0).max(0) as u64
+        };
+
+        position = (ledger_id, 0);
+        if remaining <= entries {
+            position = (ledger_id, entries - remaining);
+            break;
+        }
+        remaining -= entries;
+    }
+
+    Ok(position)
+}
+
 pub async fn fetch_tenants(cfg: &Configuration) -> anyhow::Result<Vec<Tenant>> {
     Ok(tenants_base_get_tenants(cfg)
         .await
@@ -171,6 +892,17 @@ pub async fn reset_subscription(
     let one_hour_before = now - time_delta;
     let timestamp = one_hour_before.timestamp_millis();
 
+    seek_subscription(tenant, namespace, topic, sub_name, cfg, timestamp).await
+}
+
+pub async fn seek_subscription(
+    tenant: &str,
+    namespace: &str,
+    topic: &str,
+    sub_name: &str,
+    cfg: &Configuration,
+    timestamp: i64,
+) -> anyhow::Result<()> {
     persistent_topics_reset_cursor(cfg, tenant, namespace, topic, sub_name, timestamp, None)
         .await
         .map_err(|err| anyhow!("Failed to seek back subscription: '{}'", err))
@@ -188,6 +920,90 @@ pub async fn delete_subscription(
         .map_err(|err| anyhow!("Failed to delete subscription: '{}'", err))
 }
 
+pub async fn update_subscription_properties(
+    tenant: &str,
+    namespace: &str,
+    topic: &str,
+    sub_name: &str,
+    properties: BTreeMap<String, String>,
+    cfg: &Configuration,
+) -> anyhow::Result<()> {
+    persistent_topics_update_subscription_properties(
+        cfg,
+        tenant,
+        namespace,
+        topic,
+        sub_name,
+        None,
+        Some(properties.into_iter().collect()),
+    )
+    .await
+    .map_err(|err| anyhow!("Failed to update subscription properties: '{}'", err))
+}
+
+// Pulsar treats a zero dispatch rate as unthrottled, so a paused subscription is throttled to a
+// single message per day instead.
+fn paused_dispatch_rate() -> DispatchRateImpl {
+    DispatchRateImpl {
+        dispatch_throttling_rate_in_msg: Some(1),
+        dispatch_throttling_rate_in_byte: Some(-1),
+        rate_period_in_second: Some(86_400),
+        relative_to_publish_rate: Some(false),
+    }
+}
+
+pub async fn set_subscription_paused(
+    tenant: &str,
+    namespace: &str,
+    topic: &str,
+    sub_name: &str,
+    paused: bool,
+    cfg: &Configuration,
+) -> anyhow::Result<()> {
+    if paused {
+        persistent_topics_set_subscription_level_dispatch_rate(
+            cfg,
+            tenant,
+            namespace,
+            topic,
+            sub_name,
+            None,
+            None,
+            Some(paused_dispatch_rate()),
+        )
+        .await
+        .map_err(|err| anyhow!("Failed to pause subscription: '{}'", err))
+    } else {
+        persistent_topics_remove_subscription_level_dispatch_rate(
+            cfg, tenant, namespace, topic, sub_name, None, None,
+        )
+        .await
+        .map_err(|err| anyhow!("Failed to resume subscription: '{}'", err))
+    }
+}
+
+pub async fn fetch_subscription_paused(
+    tenant: &str,
+    namespace: &str,
+    topic: &str,
+    sub_name: &str,
+    cfg: &Configuration,
+) -> anyhow::Result<bool> {
+    let url = format!(
+        "{}/persistent/{}/{}/{}/{}/dispatchRate",
+        cfg.base_path, tenant, namespace, topic, sub_name
+    );
+    let paused = paused_dispatch_rate();
+
+    Ok(fetch_dispatch_rate(&url, cfg)
+        .await?
+        .map(|rate| {
+            rate.dispatch_throttling_rate_in_msg == paused.dispatch_throttling_rate_in_msg
+                && rate.rate_period_in_second == paused.rate_period_in_second
+        })
+        .unwrap_or(false))
+}
+
 pub async fn fetch_namespaces(tenant: &str, cfg: &Configuration) -> anyhow::Result<Vec<Namespace>> {
     let result = namespaces_get_tenant_namespaces(cfg, tenant)
         .await
@@ -206,6 +1022,148 @@ pub async fn fetch_namespaces(tenant: &str, cfg: &Configuration) -> anyhow::Resu
     Ok(perfix_dropped)
 }
 
+pub async fn fetch_namespace_limits(
+    tenant: &str,
+    namespace: &str,
+    cfg: &Configuration,
+) -> anyhow::Result<NamespaceLimits> {
+    let policies = namespaces_get_policies(cfg, tenant, namespace)
+        .await
+        .map_err(|err| anyhow!("Failed to fetch namespace policies: '{}'", err))?;
+
+    Ok(NamespaceLimits {
+        max_consumers_per_topic: policies.max_consumers_per_topic,
+        max_producers_per_topic: policies.max_producers_per_topic,
+        max_consumers_per_subscription: policies.max_consumers_per_subscription,
+    })
+}
+
+pub async fn update_namespace_limits(
+    tenant: &str,
+    namespace: &str,
+    limits: &NamespaceLimits,
+    cfg: &Configuration,
+) -> anyhow::Result<()> {
+    match limits.max_consumers_per_topic {
+        Some(limit) => {
+            namespaces_set_max_consumers_per_topic(cfg, tenant, namespace, limit)
+                .map_err(|err| err.to_string())
+                .await
+        }
+        None => {
+            namespaces_remove_max_consumers_per_topic(cfg, tenant, namespace)
+                .map_err(|err| err.to_string())
+                .await
+        }
+    }
+    .map_err(|err| anyhow!("Failed to update max consumers per topic: '{}'", err))?;
+
+    match limits.max_producers_per_topic {
+        Some(limit) => {
+            namespaces_set_max_producers_per_topic(cfg, tenant, namespace, limit)
+                .map_err(|err| err.to_string())
+                .await
+        }
+        None => {
+            namespaces_remove_max_producers_per_topic(cfg, tenant, namespace)
+                .map_err(|err| err.to_string())
+                .await
+        }
+    }
+    .map_err(|err| anyhow!("Failed to update max producers per topic: '{}'", err))?;
+
+    match limits.max_consumers_per_subscription {
+        Some(limit) => {
+            namespaces_set_max_consumers_per_subscription(cfg, tenant, namespace, limit)
+                .map_err(|err| err.to_string())
+                .await
+        }
+        None => {
+            namespaces_remove_max_consumers_per_subscription(cfg, tenant, namespace)
+                .map_err(|err| err.to_string())
+                .await
+        }
+    }
+    .map_err(|err| anyhow!("Failed to update max consumers per subscription: '{}'", err))
+}
+
+pub async fn fetch_topic_snapshot(
+    tenant: &str,
+    namespace: &str,
+    topic: &str,
+    cfg: &Configuration,
+) -> anyhow::Result<TopicSnapshot> {
+    let stats = persistent_topics_get_stats(
+        cfg, tenant, namespace, topic, None, None, None, None, None, None,
+    )
+    .await
+    .map_err(|err| anyhow!("Failed to fetch topic stats: '{}'", err))?;
+
+    let subscriptions = stats.subscriptions.unwrap_or_default();
+
+    Ok(TopicSnapshot {
+        name: topic.to_string(),
+        msg_rate_in: stats.msg_rate_in.unwrap_or(0.0),
+        msg_rate_out: stats.msg_rate_out.unwrap_or(0.0),
+        msg_throughput_in: stats.msg_throughput_in.unwrap_or(0.0),
+        msg_throughput_out: stats.msg_throughput_out.unwrap_or(0.0),
+        storage_size: stats.storage_size.unwrap_or(0),
+        backlog: subscriptions
+            .values()
+            .filter_map(|sub| sub.msg_backlog)
+            .sum(),
+        producers: stats
+            .publishers
+            .map(|publishers| publishers.len())
+            .unwrap_or(0),
+        subscriptions: subscriptions.len(),
+        definition: fetch_topic_definition(tenant, namespace, topic, cfg)
+            .await
+            .unwrap_or_default(),
+    })
+}
+
+// Topics without persistent stats, e.g. non-persistent ones, are left out.
+pub async fn fetch_topics_stats(
+    tenant: &str,
+    namespace: &str,
+    topics: Vec<String>,
+    cfg: &Configuration,
+    concurrency: usize,
+) -> BTreeMap<String, TopicStats> {
+    futures::stream::iter(topics)
+        .map(|topic| async move {
+            let stats = persistent_topics_get_stats(
+                cfg, tenant, namespace, &topic, None, None, None, None, None, None,
+            )
+            .await
+            .ok()?;
+            let subscriptions = stats.subscriptions.unwrap_or_default();
+
+            Some((
+                topic,
+                TopicStats {
+                    backlog: subscriptions
+                        .values()
+                        .filter_map(|sub| sub.msg_backlog)
+                        .sum(),
+                    subscriptions: subscriptions.len(),
+                    producers: stats
+                        .publishers
+                        .map(|publishers| publishers.len())
+                        .unwrap_or(0),
+                    msg_rate_in: stats.msg_rate_in.unwrap_or(0.0),
+                    msg_rate_out: stats.msg_rate_out.unwrap_or(0.0),
+                    storage_size: stats.storage_size.unwrap_or(0),
+                },
+            ))
+        })
+        .buffer_unordered(concurrency)
+        .filter_map(|stats| async move { stats })
+        .collect()
+        .await
+}
+
 pub async fn fetch_topics(
     tenant: &str,
     namespace: &str,
@@ -220,7 +1178,7 @@ pub async fn fetch_topics(
         .map(|topic| Topic {
             name: topic
                 .split('/')
-                .last()
+                .next_back()
                 .map(|stripped| stripped.to_string())
                 .unwrap_or(topic.clone()),
             fqn: topic.to_string(),
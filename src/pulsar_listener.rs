@@ -1,22 +1,67 @@
+use anyhow::anyhow;
+use apache_avro::Schema as AvroSchema;
 use futures::TryStreamExt;
+use itertools::Itertools;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor};
 use pulsar::consumer::InitialPosition;
-use pulsar::{Consumer, DeserializeMessage, Payload, Pulsar, SubType, TokioExecutor};
+use pulsar::message::proto::{CompressionType, MessageIdData};
+use pulsar::reader::Reader;
+use pulsar::{DeserializeMessage, Payload, Pulsar, TokioExecutor};
+use pulsar_admin_sdk::models::get_schema_response::Type as SchemaType;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::panic;
-use std::sync::{mpsc::Sender, Arc};
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::Mutex;
 
+use crate::auth::ProtobufMapping;
+use crate::recovery;
+use crate::tasks::ShutdownSignal;
 use crate::AppEvent;
 
 #[derive(Serialize, Deserialize)]
 pub struct TopicEvent {
-    pub body: Value,
+    pub data: Vec<u8>,
     pub properties: Vec<String>,
+    pub size: usize,
+    pub meta: MessageMeta,
+    #[serde(skip)]
+    pub chunk: Option<Chunk>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Chunk {
+    pub uuid: String,
+    pub id: i32,
+    pub count: i32,
+}
+
+// Broker side message metadata, used to correlate messages with broker and producer logs.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct MessageMeta {
+    // `ledger:entry`, filled in by the listener as the id is not part of the payload.
+    pub message_id: String,
+    pub publish_time: u64,
+    pub event_time: Option<u64>,
+    pub key: Option<String>,
+    pub producer_name: String,
+    #[serde(default)]
+    pub partition: Option<i32>,
+    #[serde(default)]
+    pub chunks: Option<i32>,
+    #[serde(default)]
+    pub batch_index: Option<i32>,
+    #[serde(default)]
+    pub batch_size: Option<i32>,
+    // Codec the producer compressed the payload with, the client decompresses it on receive.
+    #[serde(default)]
+    pub compression: Option<String>,
 }
 
 impl DeserializeMessage for TopicEvent {
-    type Output = Result<TopicEvent, serde_json::Error>;
+    type Output = TopicEvent;
 
     fn deserialize_message(payload: &Payload) -> Self::Output {
         let props = payload
@@ -26,61 +71,452 @@ impl DeserializeMessage for TopicEvent {
             .map(|keyvalue| format!("{}:{}", keyvalue.key, keyvalue.value))
             .collect::<Vec<String>>();
 
-        serde_json::from_slice::<Value>(&payload.data).map(|content| TopicEvent {
-            body: content,
+        TopicEvent {
+            data: payload.data.clone(),
             properties: props,
-        })
+            size: payload.data.len(),
+            meta: MessageMeta {
+                message_id: String::new(),
+                publish_time: payload.metadata.publish_time,
+                // Zero means the producer did not set an event time
+                event_time: payload
+                    .metadata
+                    .event_time
+                    .filter(|event_time| *event_time > 0),
+                key: payload.metadata.partition_key.clone(),
+                producer_name: payload.metadata.producer_name.clone(),
+                partition: None,
+                chunks: None,
+                batch_index: None,
+                batch_size: payload.metadata.num_messages_in_batch,
+                compression: payload
+                    .metadata
+                    .compression
+                    .and_then(CompressionType::from_i32)
+                    .filter(|compression| *compression != CompressionType::None)
+                    .map(|compression| compression.as_str_name().to_string()),
+            },
+            chunk: match (
+                &payload.metadata.uuid,
+                payload.metadata.chunk_id,
+                payload.metadata.num_chunks_from_msg,
+            ) {
+                (Some(uuid), Some(id), Some(count)) if count > 1 => {
+                    Some(Chunk { uuid: uuid.clone(), id, count })
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Decoder {
+    Json,
+    Avro,
+    Protobuf,
+    String,
+    Bytes,
+}
+
+impl Decoder {
+    // Picks a decoder for the topic schema, topics without a schema are treated as JSON.
+    pub fn from_schema_type(schema_type: Option<SchemaType>) -> Decoder {
+        match schema_type {
+            None | Some(SchemaType::Json) => Decoder::Json,
+            Some(SchemaType::Avro) => Decoder::Avro,
+            Some(SchemaType::Protobuf | SchemaType::ProtobufNative) => Decoder::Protobuf,
+            Some(SchemaType::String) => Decoder::String,
+            Some(_) => Decoder::Bytes,
+        }
+    }
+
+    pub fn next(&self) -> Decoder {
+        match self {
+            Decoder::Json => Decoder::Avro,
+            Decoder::Avro => Decoder::Protobuf,
+            Decoder::Protobuf => Decoder::String,
+            Decoder::String => Decoder::Bytes,
+            Decoder::Bytes => Decoder::Json,
+        }
+    }
+
+    pub fn decode(&self, data: &[u8], definitions: &SchemaDefinitions) -> String {
+        match (self, &definitions.avro, &definitions.protobuf) {
+            (Decoder::Json, _, _) => serde_json::from_slice::<Value>(data)
+                .ok()
+                .and_then(|json| serde_json::to_string(&json).ok())
+                .unwrap_or_else(|| String::from_utf8_lossy(data).to_string()),
+            (Decoder::String, _, _) => String::from_utf8_lossy(data).to_string(),
+            (Decoder::Avro, Some(schema), _) => decode_avro(data, schema)
+                .and_then(|json| Ok(serde_json::to_string(&json)?))
+                .unwrap_or_else(|_| hex(data)),
+            (Decoder::Protobuf, _, Some(descriptor)) => decode_protobuf(data, descriptor)
+                .and_then(|json| Ok(serde_json::to_string(&json)?))
+                .unwrap_or_else(|_| hex(data)),
+            (Decoder::Avro | Decoder::Protobuf | Decoder::Bytes, _, _) => hex(data),
+        }
+    }
+
+    // Why the payload does not fit the decoder, `decode` then falls back to a lossy rendering.
+    pub fn validate(&self, data: &[u8], definitions: &SchemaDefinitions) -> Option<String> {
+        match (self, &definitions.avro, &definitions.protobuf) {
+            (Decoder::Json, _, _) => serde_json::from_slice::<Value>(data)
+                .err()
+                .map(|err| format!("Invalid JSON: {}", err)),
+            (Decoder::String, _, _) => std::str::from_utf8(data)
+                .err()
+                .map(|err| format!("Invalid UTF-8: {}", err)),
+            (Decoder::Avro, Some(schema), _) => decode_avro(data, schema)
+                .err()
+                .map(|err| format!("Invalid Avro: {}", err)),
+            (Decoder::Protobuf, _, Some(descriptor)) => decode_protobuf(data, descriptor)
+                .err()
+                .map(|err| format!("Invalid protobuf: {}", err)),
+            (Decoder::Avro | Decoder::Protobuf | Decoder::Bytes, _, _) => None,
+        }
+    }
+}
+
+fn hex(data: &[u8]) -> String {
+    data.iter()
+        .map(|byte| format!("{:02x}", byte))
+        .join(" ")
+}
+
+// Pulsar AVRO schema payloads are bare Avro datums, without the container file header.
+fn decode_avro(data: &[u8], schema: &AvroSchema) -> anyhow::Result<Value> {
+    let value = apache_avro::from_avro_datum(schema, &mut &data[..], None)?;
+    Ok(Value::try_from(value)?)
+}
+
+fn decode_protobuf(data: &[u8], descriptor: &MessageDescriptor) -> anyhow::Result<Value> {
+    let message = DynamicMessage::decode(descriptor.clone(), data)?;
+    Ok(serde_json::to_value(&message)?)
+}
+
+pub fn load_protobuf_descriptor(mapping: &ProtobufMapping) -> anyhow::Result<MessageDescriptor> {
+    let bytes = std::fs::read(&mapping.descriptor).map_err(|err| {
+        anyhow!(
+            "Failed to read protobuf descriptor '{}': '{}'",
+            mapping.descriptor.display(),
+            err
+        )
+    })?;
+    let pool = DescriptorPool::decode(bytes.as_slice())?;
+
+    pool.get_message_by_name(&mapping.message_type)
+        .ok_or(anyhow!(
+            "Message type '{}' not found in '{}'",
+            mapping.message_type,
+            mapping.descriptor.display()
+        ))
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SchemaDefinitions {
+    pub avro: Option<AvroSchema>,
+    // Comes from the config rather than the registry, see `load_protobuf_descriptor`.
+    pub protobuf: Option<MessageDescriptor>,
+}
+
+impl SchemaDefinitions {
+    // Parses the definition registered for the topic, unparseable ones are ignored and leave the
+    // payloads shown as bytes.
+    pub fn parse(schema_type: Option<&SchemaType>, definition: Option<&str>) -> SchemaDefinitions {
+        match (schema_type, definition) {
+            (Some(SchemaType::Avro), Some(definition)) => SchemaDefinitions {
+                avro: AvroSchema::parse_str(definition).ok(),
+                ..Default::default()
+            },
+            _ => SchemaDefinitions::default(),
+        }
     }
 }
 
+impl std::fmt::Display for Decoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            Decoder::Json => "json",
+            Decoder::Avro => "avro",
+            Decoder::Protobuf => "protobuf",
+            Decoder::String => "string",
+            Decoder::Bytes => "bytes",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// Chunks of at most this many messages are buffered at a time, the oldest are dropped first.
+const MAX_PENDING_CHUNKED: usize = 100;
+
+// Joins the chunks of messages split by a chunking producer. The Rust client has no chunking
+// support, without this the chunks show up as separate undecodable fragments.
+#[derive(Default)]
+struct ChunkAssembler {
+    pending: Vec<(String, TopicEvent)>,
+}
+
+impl ChunkAssembler {
+    // Returns the complete message once its last chunk arrived. Chunks arriving out of order,
+    // e.g. when reading started in the middle of a chunked message, drop the whole message.
+    fn push(&mut self, event: TopicEvent) -> Option<TopicEvent> {
+        let Some(chunk) = event.chunk.clone() else {
+            return Some(event);
+        };
+        let position = self
+            .pending
+            .iter()
+            .position(|(uuid, _)| *uuid == chunk.uuid);
+
+        match (position, chunk.id) {
+            (None, 0) => {
+                if self.pending.len() >= MAX_PENDING_CHUNKED {
+                    self.pending.remove(0);
+                }
+                self.pending.push((chunk.uuid.clone(), event));
+            }
+            (Some(position), id) if self.pending[position].1.meta.chunks == Some(id) => {
+                let assembled = &mut self.pending[position].1;
+                assembled.data.extend_from_slice(&event.data);
+                assembled.size = assembled.data.len();
+            }
+            (Some(position), _) => {
+                self.pending.remove(position);
+                return None;
+            }
+            (None, _) => return None,
+        }
+
+        let position = self
+            .pending
+            .iter()
+            .position(|(uuid, _)| *uuid == chunk.uuid)?;
+        let assembled = &mut self.pending[position].1;
+        let received = assembled.meta.chunks.unwrap_or(0) + 1;
+        assembled.meta.chunks = Some(received);
+
+        if received == chunk.count {
+            let (_, mut assembled) = self.pending.remove(position);
+            assembled.chunk = None;
+            Some(assembled)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum StartPosition {
+    Latest,
+    Earliest,
+    // `(ledger_id, entry_id)` by partition, `None` for non-partitioned topics.
+    Entries(BTreeMap<Option<i32>, (u64, u64)>),
+    Timestamp(u64),
+}
+
+// Reads the topic through non-durable readers, one per partition, so listening never leaves a
+// subscription behind on the broker.
+#[allow(clippy::too_many_arguments)]
 pub async fn listen_to_topic(
     sub_name: String,
     topic_fqn: String,
-    event_sender: Sender<AppEvent>,
+    event_sender: UnboundedSender<AppEvent>,
     pulsar: Arc<Mutex<Pulsar<TokioExecutor>>>,
     mut control_channel: tokio::sync::oneshot::Receiver<()>,
+    mut pause: tokio::sync::watch::Receiver<bool>,
+    mut shutdown: ShutdownSignal,
+    start: StartPosition,
 ) -> anyhow::Result<()> {
-    let mut consumer: Consumer<TopicEvent, TokioExecutor> = pulsar
+    let options = |partition: &str| match &start {
+        StartPosition::Earliest | StartPosition::Timestamp(_) => {
+            pulsar::ConsumerOptions::default().with_initial_position(InitialPosition::Earliest)
+        }
+        StartPosition::Entries(positions) => {
+            match positions.get(&recovery::split_partition(partition).1) {
+                Some((ledger_id, entry_id)) => pulsar::ConsumerOptions::default()
+                    .starting_on_message(MessageIdData {
+                        ledger_id: *ledger_id,
+                        entry_id: *entry_id,
+                        ..Default::default()
+                    }),
+                // Partitions added since the positions were resolved have nothing to go back to
+                None => pulsar::ConsumerOptions::default()
+                    .with_initial_position(InitialPosition::Latest),
+            }
+        }
+        StartPosition::Latest => {
+            pulsar::ConsumerOptions::default().with_initial_position(InitialPosition::Latest)
+        }
+    };
+
+    // Readers are bound to a single topic, partitioned topics get a reader per partition
+    let partitions = pulsar
         .lock()
         .await
-        .consumer()
-        .with_options(
-            pulsar::ConsumerOptions::default()
-                .durable(false)
-                .with_initial_position(InitialPosition::Latest),
-        )
-        .with_topic(topic_fqn)
-        .with_subscription_type(SubType::Exclusive)
-        .with_subscription(sub_name)
-        .build()
+        .lookup_partitioned_topic(topic_fqn)
         .await?;
 
+    let mut readers: Vec<Reader<TopicEvent, TokioExecutor>> = vec![];
+    for (partition, _) in partitions {
+        let mut reader = pulsar
+            .lock()
+            .await
+            .reader()
+            .with_options(options(&partition).durable(false))
+            .with_topic(partition)
+            .with_subscription(sub_name.clone())
+            .into_reader()
+            .await?;
+
+        if let StartPosition::Timestamp(timestamp) = start {
+            reader.seek(None, Some(timestamp)).await?;
+        }
+
+        readers.push(reader);
+    }
+
+    let mut messages = futures::stream::select_all(readers);
+    let mut chunks = ChunkAssembler::default();
+
     loop {
         tokio::select! {
-            msg = consumer.try_next() => {
+            // While paused the readers are left alone, unread messages stay buffered on the broker
+            msg = messages.try_next(), if !*pause.borrow() => {
                 match msg {
                     Ok(Some(message)) => {
-                        let topic_event = message.deserialize()?;
-                        let _ = event_sender.send(AppEvent::SubscriptionEvent(topic_event));
-
-                        consumer.ack(&message).await?;
-
+                        let mut topic_event = message.deserialize();
+                        let id = message.message_id();
+                        topic_event.meta.message_id = format!("{}:{}", id.ledger_id, id.entry_id);
+                        topic_event.meta.batch_index = id.batch_index;
+                        topic_event.meta.partition = recovery::split_partition(&message.topic).1;
+                        if let Some(topic_event) = chunks.push(topic_event) {
+                            let _ = event_sender.send(AppEvent::SubscriptionEvent(topic_event));
+                        }
                     },
                     Ok(None) => break,
                     Err(e) => {
-                        println!("Error in topic consumer, {:?}", e);
+                        println!("Error in topic reader, {:?}", e);
                         break;
                     }
                 }
             },
+            changed = pause.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+            }
             _ = &mut control_channel => {
                 // cancel!
                 break;
             }
+            _ = shutdown.wait() => {
+                break;
+            }
         }
     }
 
-    consumer.close().await?;
-
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(uuid: &str, id: i32, count: i32, data: &[u8]) -> TopicEvent {
+        TopicEvent {
+            data: data.to_vec(),
+            properties: vec![],
+            size: data.len(),
+            meta: MessageMeta::default(),
+            chunk: Some(Chunk {
+                uuid: uuid.to_string(),
+                id,
+                count,
+            }),
+        }
+    }
+
+    #[test]
+    fn passes_unchunked_messages_through() {
+        let mut chunks = ChunkAssembler::default();
+        let event = TopicEvent {
+            chunk: None,
+            ..chunk("", 0, 1, b"whole")
+        };
+
+        let event = chunks
+            .push(event)
+            .expect("unchunked messages are complete");
+        assert_eq!(event.data, b"whole");
+        assert!(chunks.pending.is_empty());
+    }
+
+    #[test]
+    fn assembles_chunks_in_order() {
+        let mut chunks = ChunkAssembler::default();
+
+        assert!(chunks.push(chunk("a", 0, 3, b"he")).is_none());
+        assert!(chunks.push(chunk("a", 1, 3, b"ll")).is_none());
+        let assembled = chunks
+            .push(chunk("a", 2, 3, b"o"))
+            .expect("last chunk completes the message");
+
+        assert_eq!(assembled.data, b"hello");
+        assert_eq!(assembled.size, 5);
+        assert_eq!(assembled.meta.chunks, Some(3));
+        assert!(assembled.chunk.is_none());
+        assert!(chunks.pending.is_empty());
+    }
+
+    #[test]
+    fn interleaved_messages_are_assembled_separately() {
+        let mut chunks = ChunkAssembler::default();
+
+        assert!(chunks.push(chunk("a", 0, 2, b"a0")).is_none());
+        assert!(chunks.push(chunk("b", 0, 2, b"b0")).is_none());
+        assert_eq!(chunks.push(chunk("b", 1, 2, b"b1")).unwrap().data, b"b0b1");
+        assert_eq!(chunks.push(chunk("a", 1, 2, b"a1")).unwrap().data, b"a0a1");
+    }
+
+    #[test]
+    fn drops_messages_with_out_of_order_chunks() {
+        let mut chunks = ChunkAssembler::default();
+
+        assert!(chunks.push(chunk("a", 0, 3, b"he")).is_none());
+        assert!(chunks.push(chunk("a", 2, 3, b"o")).is_none());
+        assert!(chunks.pending.is_empty());
+        assert!(chunks.push(chunk("a", 1, 3, b"ll")).is_none());
+        assert!(chunks.pending.is_empty());
+    }
+
+    #[test]
+    fn drops_messages_read_from_their_middle() {
+        let mut chunks = ChunkAssembler::default();
+
+        assert!(chunks.push(chunk("a", 1, 2, b"lo")).is_none());
+        assert!(chunks.pending.is_empty());
+    }
+
+    #[test]
+    fn evicts_the_oldest_pending_message() {
+        let mut chunks = ChunkAssembler::default();
+
+        for message in 0..=MAX_PENDING_CHUNKED {
+            assert!(chunks
+                .push(chunk(&message.to_string(), 0, 2, b"first"))
+                .is_none());
+        }
+        assert_eq!(chunks.pending.len(), MAX_PENDING_CHUNKED);
+
+        assert!(chunks.push(chunk("0", 1, 2, b"last")).is_none());
+        let newest = MAX_PENDING_CHUNKED.to_string();
+        assert_eq!(
+            chunks
+                .push(chunk(&newest, 1, 2, b"last"))
+                .unwrap()
+                .data,
+            b"firstlast"
+        );
+    }
+}
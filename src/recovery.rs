@@ -0,0 +1,154 @@
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use pulsar_admin_sdk::apis::configuration::Configuration;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use crate::pulsar_admin;
+use crate::stats_export::{NamespaceSnapshot, TopicDefinition};
+use crate::update::Topic;
+
+// A topic present in the newest stats export snapshot of its namespace but gone from the
+// cluster. Pulsar keeps no trace of deleted topics, the snapshot is all there is to go by.
+#[derive(Debug, Clone)]
+pub struct DeletedTopic {
+    pub name: String,
+    pub partitions: i32,
+    pub definition: TopicDefinition,
+    pub seen_at: DateTime<Utc>,
+}
+
+impl DeletedTopic {
+    pub fn describe(&self) -> String {
+        let definition = &self.definition;
+        let partitions = (self.partitions > 0).then(|| format!("{} partitions", self.partitions));
+
+        partitions
+            .into_iter()
+            .chain(
+                definition
+                    .schema
+                    .as_ref()
+                    .map(|_| "schema".to_string()),
+            )
+            .chain(
+                definition
+                    .retention
+                    .as_ref()
+                    .map(|_| "retention".to_string()),
+            )
+            .chain(
+                definition
+                    .message_ttl
+                    .map(|_| "message TTL".to_string()),
+            )
+            .chain(
+                definition
+                    .max_producers
+                    .map(|_| "max producers".to_string()),
+            )
+            .chain(
+                definition
+                    .max_consumers
+                    .map(|_| "max consumers".to_string()),
+            )
+            .join(", ")
+    }
+}
+
+// Topic listings name each partition separately, as `{topic}-partition-{index}`.
+pub fn split_partition(name: &str) -> (&str, Option<i32>) {
+    name.rsplit_once("-partition-")
+        .and_then(|(base, index)| {
+            index
+                .parse()
+                .ok()
+                .map(|index| (base, Some(index)))
+        })
+        .unwrap_or((name, None))
+}
+
+pub fn latest_snapshot(
+    dir: &Path,
+    tenant: &str,
+    namespace: &str,
+) -> anyhow::Result<Option<NamespaceSnapshot>> {
+    let prefix = format!("{}_{}_", tenant, namespace);
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(anyhow!(
+                "Failed to read stats export dir '{}': '{}'",
+                dir.display(),
+                err
+            ))
+        }
+    };
+
+    // Timestamps in the file names sort chronologically
+    let candidates = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".json"))
+        })
+        .sorted()
+        .rev();
+
+    // Underscores in names make the prefix ambiguous, the content tells for sure
+    for path in candidates {
+        let snapshot = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<NamespaceSnapshot>(&content).ok());
+        if let Some(snapshot) = snapshot {
+            if snapshot.tenant == tenant && snapshot.namespace == namespace {
+                return Ok(Some(snapshot));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+pub fn deleted_topics(snapshot: &NamespaceSnapshot, topics: &[Topic]) -> Vec<DeletedTopic> {
+    let existing: BTreeSet<&str> = topics
+        .iter()
+        .map(|topic| split_partition(&topic.name).0)
+        .collect();
+
+    snapshot
+        .topics
+        .iter()
+        .map(|topic| (split_partition(&topic.name), topic))
+        .filter(|((base, _), _)| !existing.contains(base))
+        .into_group_map_by(|((base, _), _)| *base)
+        .into_iter()
+        .map(|(base, partitions)| DeletedTopic {
+            name: base.to_string(),
+            partitions: partitions
+                .iter()
+                .filter_map(|((_, index), _)| index.map(|index| index + 1))
+                .max()
+                .unwrap_or(0),
+            definition: partitions[0].1.definition.clone(),
+            seen_at: snapshot.taken_at,
+        })
+        .sorted_by(|a, b| a.name.cmp(&b.name))
+        .collect()
+}
+
+// Messages of the deleted topic are gone for good, only its definition is recreated.
+pub async fn recreate(
+    tenant: &str,
+    namespace: &str,
+    topic: &DeletedTopic,
+    cfg: &Configuration,
+) -> anyhow::Result<Vec<&'static str>> {
+    pulsar_admin::create_topic(tenant, namespace, &topic.name, topic.partitions, None, cfg).await?;
+    pulsar_admin::apply_topic_definition(tenant, namespace, &topic.name, &topic.definition, cfg)
+        .await
+}
@@ -0,0 +1,196 @@
+use anyhow::anyhow;
+use chrono::Utc;
+use itertools::Itertools;
+use std::collections::BTreeMap;
+
+use crate::bench::{BenchLength, BenchSpec, KeyDistribution};
+use crate::migration::MigrationPlan;
+use crate::produce::ProducedMessage;
+use crate::update::{NamespaceLimits, SeekTarget, SubscriptionPath};
+
+#[derive(Default)]
+pub struct SessionLog {
+    commands: Vec<String>,
+}
+
+fn topic_fqn(tenant: &str, namespace: &str, topic: &str) -> String {
+    format!("persistent://{}/{}/{}", tenant, namespace, topic)
+}
+
+impl SessionLog {
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    pub fn unsubscribe(&mut self, tenant: &str, namespace: &str, topic: &str, sub_name: &str) {
+        self.commands.push(format!(
+            "pulsar-admin topics unsubscribe {} -s '{}'",
+            topic_fqn(tenant, namespace, topic),
+            sub_name
+        ));
+    }
+
+    pub fn clear_backlog(&mut self, tenant: &str, namespace: &str, topic: &str, sub_name: &str) {
+        self.commands.push(format!(
+            "pulsar-admin topics clear-backlog {} -s '{}'",
+            topic_fqn(tenant, namespace, topic),
+            sub_name
+        ));
+    }
+
+    pub fn reset_cursor(&mut self, path: &SubscriptionPath, target: &SeekTarget) {
+        let fqn = topic_fqn(&path.tenant, &path.namespace, &path.topic);
+        self.commands.push(match target {
+            SeekTarget::Relative(time_delta) => format!(
+                "pulsar-admin topics reset-cursor {} -s '{}' -t {}m",
+                fqn,
+                path.sub_name,
+                time_delta.num_minutes()
+            ),
+            // pulsar-admin only takes relative times, the REST API takes the timestamp as is
+            SeekTarget::Absolute(timestamp) => format!(
+                "curl -X POST \"$PULSAR_ADMIN_URL/admin/v2/persistent/{}/{}/{}/subscription/{}/resetcursor/{}\"",
+                path.tenant,
+                path.namespace,
+                path.topic,
+                path.sub_name,
+                timestamp.timestamp_millis()
+            ),
+        });
+    }
+
+    pub fn set_paused(&mut self, path: &SubscriptionPath, paused: bool) {
+        let fqn = topic_fqn(&path.tenant, &path.namespace, &path.topic);
+        self.commands.push(if paused {
+            format!(
+                "pulsar-admin topicPolicies set-subscription-dispatch-rate {} -s '{}' -md 1 -bd -1 -dt 86400",
+                fqn, path.sub_name
+            )
+        } else {
+            format!(
+                "pulsar-admin topicPolicies remove-subscription-dispatch-rate {} -s '{}'",
+                fqn, path.sub_name
+            )
+        });
+    }
+
+    pub fn update_subscription_properties(
+        &mut self,
+        path: &SubscriptionPath,
+        properties: &BTreeMap<String, String>,
+    ) {
+        self.commands.push(format!(
+            "pulsar-admin topics update-subscription-properties {} -s '{}' {}",
+            topic_fqn(&path.tenant, &path.namespace, &path.topic),
+            path.sub_name,
+            properties
+                .iter()
+                .map(|(key, value)| format!("-p '{}={}'", key, value))
+                .join(" ")
+        ));
+    }
+
+    pub fn set_namespace_limits(
+        &mut self,
+        tenant: &str,
+        namespace: &str,
+        limits: &NamespaceLimits,
+    ) {
+        let limits = [
+            (
+                "max-consumers-per-topic",
+                "-c",
+                limits.max_consumers_per_topic,
+            ),
+            (
+                "max-producers-per-topic",
+                "-p",
+                limits.max_producers_per_topic,
+            ),
+            (
+                "max-consumers-per-subscription",
+                "-c",
+                limits.max_consumers_per_subscription,
+            ),
+        ];
+
+        for (limit, flag, value) in limits {
+            self.commands.push(match value {
+                Some(value) => format!(
+                    "pulsar-admin namespaces set-{} {}/{} {} {}",
+                    limit, tenant, namespace, flag, value
+                ),
+                None => format!(
+                    "pulsar-admin namespaces remove-{} {}/{}",
+                    limit, tenant, namespace
+                ),
+            });
+        }
+    }
+
+    // Migrations combine several admin calls and a replay, they are recorded as a note.
+    pub fn migrate(&mut self, plan: &MigrationPlan) {
+        self.commands.push(format!(
+            "# lgm migration: created {} with the schema and topic policies of {}{}",
+            topic_fqn(&plan.tenant, &plan.namespace, &plan.target),
+            topic_fqn(&plan.tenant, &plan.namespace, &plan.source),
+            if plan.replay {
+                " and replayed its retained messages"
+            } else {
+                ""
+            }
+        ));
+    }
+
+    pub fn recreate_topic(&mut self, tenant: &str, namespace: &str, topic: &str, partitions: i32) {
+        let fqn = topic_fqn(tenant, namespace, topic);
+        self.commands.push(if partitions > 0 {
+            format!(
+                "pulsar-admin topics create-partitioned-topic {} -p {}",
+                fqn, partitions
+            )
+        } else {
+            format!("pulsar-admin topics create {}", fqn)
+        });
+        self.commands.push(format!(
+            "# lgm recovery: applied the schema and topic policies of {} recorded in a stats snapshot",
+            fqn
+        ));
+    }
+
+    pub fn bench(&mut self, topic_fqn: &str, spec: &BenchSpec) {
+        let length = match spec.length {
+            BenchLength::Count(count) => format!("-m {}", count),
+            BenchLength::Duration(duration) => format!("-time {}", duration.as_secs()),
+        };
+        // pulsar-perf has no payload templates nor a fixed key count, the closest is used
+        let keys = match spec.keys {
+            KeyDistribution::None => "",
+            KeyDistribution::RoundRobin(_) => " -mk autoIncrement",
+            KeyDistribution::Random => " -mk random",
+        };
+        self.commands.push(format!(
+            "pulsar-perf produce {} {} -s {} -r {}{}",
+            topic_fqn, length, spec.size, spec.rate, keys
+        ));
+    }
+
+    pub fn produce(&mut self, topic_fqn: &str, message: &ProducedMessage) {
+        self.commands
+            .push(message.pulsar_client_command(topic_fqn));
+    }
+
+    pub fn export(&self, path: &str, cluster_name: &str) -> anyhow::Result<()> {
+        let script = std::iter::once("#!/bin/sh".to_string())
+            .chain(std::iter::once(format!(
+                "# Mutating actions performed by lgm on '{}', exported at {}",
+                cluster_name,
+                Utc::now().to_rfc3339()
+            )))
+            .chain(self.commands.iter().cloned())
+            .join("\n");
+
+        std::fs::write(path, script + "\n")
+            .map_err(|err| anyhow!("Failed to export session to '{}': '{}'", path, err))
+    }
+}
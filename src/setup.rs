@@ -0,0 +1,187 @@
+use anyhow::anyhow;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+
+use crate::auth::{self, Auth, Context};
+use crate::diagnostics;
+
+// Asks for the cluster settings on the command line when there is no config yet, checks that they
+// connect and writes them to `path`. Does nothing when stdin is not a terminal or the user
+// declines, reading the missing config then fails as before.
+pub async fn offer(path: &Path) -> anyhow::Result<()> {
+    if path.exists() || !io::stdin().is_terminal() {
+        return Ok(());
+    }
+
+    println!("No config found at '{}'.", path.display());
+    if !confirm("Create one now?", true)? {
+        return Ok(());
+    }
+
+    loop {
+        let context = ask_context()?;
+
+        println!("\nChecking the connection...\n");
+        let checked = match &context.auth {
+            Auth::Token { token } => Context {
+                auth: Auth::Token {
+                    token: auth::resolve_value(token)?,
+                },
+                ..context.clone()
+            },
+            Auth::OAuth { .. } | Auth::TokenCmd { .. } => context.clone(),
+        };
+        let connected = diagnostics::print(&diagnostics::diagnose(checked).await);
+        if connected || confirm("The checks failed, write the config anyway?", false)? {
+            write(path, &context)?;
+            println!("Wrote '{}'.\n", path.display());
+            return Ok(());
+        }
+        if !confirm("Enter the settings again?", true)? {
+            return Ok(());
+        }
+    }
+}
+
+fn ask_context() -> anyhow::Result<Context> {
+    let pulsar_url = ask("Broker url", "pulsar://localhost:6650")?;
+    let pulsar_admin_url = ask("Admin API url", "http://localhost:8080")?;
+    let auth = loop {
+        match ask("Auth method (none, token, token_cmd, oauth)", "none")?.as_str() {
+            "none" => break Auth::Token { token: String::new() },
+            "token" => {
+                break Auth::Token {
+                    token: ask("Token, also ${VAR} or keyring:NAME", "")?,
+                }
+            }
+            "token_cmd" => {
+                break Auth::TokenCmd {
+                    command: ask("Command printing the token", "")?,
+                }
+            }
+            "oauth" => {
+                break Auth::OAuth {
+                    issuer_url: ask("Issuer url", "")?,
+                    audience: ask("Audience", "")?,
+                    credentials_file_url: ask("Credentials file url", "file:///")?,
+                }
+            }
+            other => println!("Unknown auth method '{}'.", other),
+        }
+    };
+    let default_tenant = ask("Default tenant", "public")?;
+    let default_namespace = Some(ask("Default namespace, empty for none", "")?)
+        .filter(|namespace| !namespace.is_empty());
+
+    Ok(Context {
+        pulsar_url,
+        pulsar_admin_url,
+        default_tenant,
+        default_namespace,
+        production: false,
+        auth,
+    })
+}
+
+fn write(path: &Path, context: &Context) -> anyhow::Result<()> {
+    let mut config = toml::Table::new();
+    config.insert("pulsar_url".into(), context.pulsar_url.clone().into());
+    config.insert(
+        "pulsar_admin_url".into(),
+        context.pulsar_admin_url.clone().into(),
+    );
+    config.insert(
+        "default_tenant".into(),
+        context.default_tenant.clone().into(),
+    );
+    if let Some(namespace) = &context.default_namespace {
+        config.insert("default_namespace".into(), namespace.clone().into());
+    }
+
+    let mut args = toml::Table::new();
+    let auth_type = match &context.auth {
+        Auth::Token { token } => {
+            args.insert("token".into(), token.clone().into());
+            "Token"
+        }
+        Auth::OAuth {
+            issuer_url,
+            audience,
+            credentials_file_url,
+        } => {
+            args.insert("issuer_url".into(), issuer_url.clone().into());
+            args.insert("audience".into(), audience.clone().into());
+            args.insert(
+                "credentials_file_url".into(),
+                credentials_file_url.clone().into(),
+            );
+            "OAuth"
+        }
+        Auth::TokenCmd { command } => {
+            args.insert("command".into(), command.clone().into());
+            "token_cmd"
+        }
+    };
+    let mut auth = toml::Table::new();
+    auth.insert("type".into(), auth_type.into());
+    auth.insert("args".into(), args.into());
+    config.insert("auth".into(), auth.into());
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|err| anyhow!("Failed to create '{}': '{}'", dir.display(), err))?;
+    }
+    let content =
+        toml::to_string(&config).map_err(|err| anyhow!("Failed to serialize config: '{}'", err))?;
+    std::fs::write(path, content)
+        .map_err(|err| anyhow!("Failed to write config to '{}': '{}'", path.display(), err))
+}
+
+// Reads the secret from stdin, a whole OAuth credentials file may be piped in.
+pub fn store_secret(name: &str) -> anyhow::Result<()> {
+    if io::stdin().is_terminal() {
+        println!("Secret for '{}', end with an empty line:", name);
+    }
+    let mut secret = String::new();
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if line.is_empty() && io::stdin().is_terminal() {
+            break;
+        }
+        secret.push_str(&line);
+        secret.push('\n');
+    }
+    let secret = secret.trim();
+    if secret.is_empty() {
+        return Err(anyhow!("No secret given for '{}'", name));
+    }
+
+    auth::store_secret(name, secret)
+}
+
+fn ask(question: &str, default: &str) -> anyhow::Result<String> {
+    if default.is_empty() {
+        print!("{}: ", question);
+    } else {
+        print!("{} [{}]: ", question, default);
+    }
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer)? == 0 {
+        return Err(anyhow!("Setup aborted"));
+    }
+    let answer = answer.trim();
+
+    Ok(if answer.is_empty() { default } else { answer }.to_string())
+}
+
+fn confirm(question: &str, default: bool) -> anyhow::Result<bool> {
+    let hint = if default { "Y/n" } else { "y/N" };
+    let answer = ask(&format!("{} [{}]", question, hint), "")?;
+
+    Ok(match answer.to_lowercase().as_str() {
+        "" => default,
+        answer => answer.starts_with('y'),
+    })
+}
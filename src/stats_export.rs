@@ -0,0 +1,141 @@
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use itertools::Itertools;
+use pulsar_admin_sdk::apis::configuration::Configuration;
+use pulsar_admin_sdk::models::{PostSchemaPayload, RetentionPolicies};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::auth::{self, Auth, Token};
+use crate::pulsar_admin;
+use crate::tasks::ShutdownSignal;
+use crate::token_refresh;
+use crate::update::TOPIC_STATS_CONCURRENCY;
+use crate::AppEvent;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NamespaceSnapshot {
+    pub taken_at: DateTime<Utc>,
+    pub tenant: String,
+    pub namespace: String,
+    pub topics: Vec<TopicSnapshot>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TopicSnapshot {
+    pub name: String,
+    pub msg_rate_in: f64,
+    pub msg_rate_out: f64,
+    pub msg_throughput_in: f64,
+    pub msg_throughput_out: f64,
+    pub storage_size: i64,
+    pub backlog: i64,
+    pub producers: usize,
+    pub subscriptions: usize,
+    // Kept so a deleted topic can be recreated as it was, absent in older snapshots.
+    #[serde(default)]
+    pub definition: TopicDefinition,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TopicDefinition {
+    pub schema: Option<PostSchemaPayload>,
+    pub retention: Option<RetentionPolicies>,
+    pub message_ttl: Option<i32>,
+    pub max_producers: Option<i32>,
+    pub max_consumers: Option<i32>,
+}
+
+// Periodically exports the stats of the given `tenant/namespace` pairs into `dir` until the app
+// shuts down. Failures are reported to the app, the next tick tries again. The export stays on the
+// cluster it started with and refreshes its token on its own.
+pub async fn run(
+    namespaces: Vec<(String, String)>,
+    dir: PathBuf,
+    interval: Duration,
+    (mut cfg, auth, mut token): (Configuration, Auth, Token),
+    event_sender: UnboundedSender<AppEvent>,
+    mut shutdown: ShutdownSignal,
+) {
+    let mut ticker = tokio::time::interval(interval);
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                if auth.refreshable() && token.expires_within(token_refresh::MARGIN) {
+                    match auth::auth(&auth).await {
+                        Ok(fresh) => {
+                            cfg.bearer_access_token = Some(fresh.access_token.clone());
+                            token = fresh;
+                        }
+                        Err(err) => {
+                            let _ = event_sender.send(AppEvent::StatsExportFailed(anyhow!(
+                                "Failed to refresh the stats export token: '{}'",
+                                err
+                            )));
+                        }
+                    }
+                }
+                for (tenant, namespace) in &namespaces {
+                    if let Err(err) = export(tenant, namespace, &dir, &cfg).await {
+                        let _ = event_sender.send(AppEvent::StatsExportFailed(err));
+                    }
+                }
+            }
+            _ = shutdown.wait() => break,
+        }
+    }
+}
+
+async fn export(
+    tenant: &str,
+    namespace: &str,
+    dir: &Path,
+    cfg: &Configuration,
+) -> anyhow::Result<()> {
+    let topics = pulsar_admin::fetch_topics(tenant, namespace, cfg).await?;
+
+    // Topics without persistent stats (e.g. non-persistent ones) are left out of the snapshot
+    let mut topics: Vec<TopicSnapshot> = futures::stream::iter(
+        topics
+            .iter()
+            .map(|topic| pulsar_admin::fetch_topic_snapshot(tenant, namespace, &topic.name, cfg))
+            .collect_vec(),
+    )
+    .buffer_unordered(TOPIC_STATS_CONCURRENCY)
+    .filter_map(|snapshot| async move { snapshot.ok() })
+    .collect()
+    .await;
+    topics.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let snapshot = NamespaceSnapshot {
+        taken_at: Utc::now(),
+        tenant: tenant.to_string(),
+        namespace: namespace.to_string(),
+        topics,
+    };
+
+    let path = dir.join(format!(
+        "{}_{}_{}.json",
+        tenant,
+        namespace,
+        snapshot.taken_at.format("%Y%m%dT%H%M%SZ")
+    ));
+    let content = serde_json::to_string_pretty(&snapshot)?;
+
+    tokio::fs::create_dir_all(dir)
+        .await
+        .map_err(|err| {
+            anyhow!(
+                "Failed to create stats export dir '{}': '{}'",
+                dir.display(),
+                err
+            )
+        })?;
+    tokio::fs::write(&path, content)
+        .await
+        .map_err(|err| anyhow!("Failed to export stats to '{}': '{}'", path.display(), err))
+}
@@ -0,0 +1,42 @@
+use anyhow::anyhow;
+use std::collections::BTreeSet;
+
+pub struct TableRows {
+    pub columns: &'static [&'static str],
+    pub hidden: BTreeSet<usize>,
+    pub rows: Vec<Vec<String>>,
+}
+
+pub fn write_csv(path: &str, table: &TableRows) -> anyhow::Result<usize> {
+    let TableRows { columns, hidden, rows } = table;
+    let line = |fields: Vec<&str>| {
+        fields
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| !hidden.contains(index))
+            .map(|(_, field)| escape(field))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+
+    let mut content = line(columns.to_vec());
+    content.push('\n');
+    for row in rows {
+        content.push_str(&line(row.iter().map(String::as_str).collect()));
+        content.push('\n');
+    }
+
+    std::fs::write(path, content)
+        .map_err(|err| anyhow!("Failed to export table to '{}': '{}'", path, err))?;
+
+    Ok(rows.len())
+}
+
+// Quotes fields containing separators, quotes or line breaks, as spreadsheets expect.
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,64 @@
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+// Background tasks spawned by the app (listeners, benchmarks, ...), torn down together on exit so
+// none of them outlive the terminal restore.
+pub struct TaskRegistry {
+    tasks: Vec<(&'static str, JoinHandle<()>)>,
+    shutdown: watch::Sender<bool>,
+}
+
+#[derive(Clone)]
+pub struct ShutdownSignal(watch::Receiver<bool>);
+
+impl ShutdownSignal {
+    pub async fn wait(&mut self) {
+        let _ = self.0.wait_for(|shutdown| *shutdown).await;
+    }
+}
+
+impl Default for TaskRegistry {
+    fn default() -> Self {
+        TaskRegistry {
+            tasks: vec![],
+            shutdown: watch::channel(false).0,
+        }
+    }
+}
+
+impl TaskRegistry {
+    pub fn spawn<T, F>(&mut self, name: &'static str, task: T)
+    where
+        T: FnOnce(ShutdownSignal) -> F,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.tasks
+            .retain(|(_, handle)| !handle.is_finished());
+
+        let signal = ShutdownSignal(self.shutdown.subscribe());
+        self.tasks
+            .push((name, tokio::spawn(task(signal))));
+    }
+
+    // Signals all tasks to stop and waits up to `grace` for them, stragglers are aborted.
+    // Returns the names of aborted tasks.
+    pub async fn shutdown(&mut self, grace: Duration) -> Vec<&'static str> {
+        let _ = self.shutdown.send(true);
+        let deadline = tokio::time::Instant::now() + grace;
+        let mut aborted = vec![];
+
+        for (name, mut handle) in std::mem::take(&mut self.tasks) {
+            if tokio::time::timeout_at(deadline, &mut handle)
+                .await
+                .is_err()
+            {
+                handle.abort();
+                aborted.push(name);
+            }
+        }
+
+        aborted
+    }
+}
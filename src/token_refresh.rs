@@ -0,0 +1,63 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::oneshot;
+
+use crate::auth::{self, Auth};
+use crate::tasks::ShutdownSignal;
+use crate::AppEvent;
+
+pub const MARGIN: Duration = Duration::from_secs(60);
+const RETRY: Duration = Duration::from_secs(30);
+
+pub async fn run(
+    context: String,
+    auth: Auth,
+    expires_at: DateTime<Utc>,
+    event_sender: UnboundedSender<AppEvent>,
+    stopped: oneshot::Receiver<()>,
+    mut shutdown: ShutdownSignal,
+) {
+    tokio::select! {
+        _ = refresh(context, auth, expires_at, event_sender) => {}
+        _ = stopped => {}
+        _ = shutdown.wait() => {}
+    }
+}
+
+async fn refresh(
+    context: String,
+    auth: Auth,
+    mut expires_at: DateTime<Utc>,
+    event_sender: UnboundedSender<AppEvent>,
+) {
+    loop {
+        let refresh_at = expires_at - TimeDelta::from_std(MARGIN).unwrap_or_default();
+        let wait = (refresh_at - Utc::now())
+            .to_std()
+            .unwrap_or_default();
+        tokio::time::sleep(wait).await;
+
+        match auth::auth(&auth).await {
+            Ok(token) => {
+                let next = token.expires_at;
+                let _ = event_sender.send(AppEvent::TokenRefreshed {
+                    context: context.clone(),
+                    token: Ok(token),
+                });
+                match next {
+                    Some(next) => expires_at = next,
+                    // Tokens without an expiry don't need refreshing
+                    None => return,
+                }
+            }
+            Err(err) => {
+                let _ = event_sender.send(AppEvent::TokenRefreshed {
+                    context: context.clone(),
+                    token: Err(err),
+                });
+                tokio::time::sleep(RETRY).await;
+            }
+        }
+    }
+}
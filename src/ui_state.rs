@@ -0,0 +1,47 @@
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+// Layout choices made in the app, kept next to the config so they survive restarts.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UiState {
+    #[serde(default = "default_listening_split")]
+    pub listening_split: u16,
+    #[serde(default)]
+    pub bookmarks: Vec<String>,
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        UiState {
+            listening_split: default_listening_split(),
+            bookmarks: vec![],
+        }
+    }
+}
+
+fn default_listening_split() -> u16 {
+    50
+}
+
+impl UiState {
+    pub fn path(config_path: &Path) -> PathBuf {
+        config_path.with_file_name("state.toml")
+    }
+
+    // Falls back to the defaults when the file is missing or unreadable.
+    pub fn load(path: &Path) -> UiState {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let content = toml::to_string(self)
+            .map_err(|err| anyhow!("Failed to serialize ui state: '{}'", err))?;
+
+        std::fs::write(path, content)
+            .map_err(|err| anyhow!("Failed to save ui state to '{}': '{}'", path.display(), err))
+    }
+}
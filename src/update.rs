@@ -1,28 +1,52 @@
 use crate::pulsar_admin;
 use anyhow::anyhow;
-use chrono::TimeDelta;
+use chrono::{DateTime, Local, NaiveDateTime, TimeDelta, TimeZone, Utc};
 use clipboard::{ClipboardContext, ClipboardProvider};
 use core::fmt;
 use crossterm::event::KeyCode;
+use futures::future::join_all;
+use futures::StreamExt;
 use itertools::Itertools;
+use prost_reflect::MessageDescriptor;
 use pulsar::{Pulsar, TokioExecutor};
 use pulsar_admin_sdk::apis::configuration::Configuration;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::io::Stdout;
-use std::usize;
+use std::ops::Range;
+use std::path::PathBuf;
 use std::{
-    sync::{
-        mpsc::{Receiver, Sender},
-        Arc,
-    },
-    time::Duration,
+    sync::Arc,
+    time::{Duration, Instant},
 };
-use tokio::sync::{oneshot, Mutex};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::sync::{oneshot, watch, Mutex};
+use tokio::time::MissedTickBehavior;
 use uuid::Uuid;
 
 use ratatui::{backend::CrosstermBackend, Terminal};
 
-use crate::auth::Token;
-use crate::{draw, pulsar_listener, AppEvent, ControlEvent};
+use crate::admin_cache::AdminCache;
+use crate::auth::{self, Auth, Context, DeadLetterPolicy, Token};
+use crate::bench::{self, BenchLength, BenchSpec, ConsumeReport};
+use crate::connections::Connections;
+use crate::filter::{Expression, KeyFilter, PartitionFilter, PropertyFilter, SearchPattern};
+use crate::journal::{self, Checkpoint, Journal};
+use crate::keymap::{Binding, Keymap, Layer, View};
+use crate::message_export;
+use crate::migration::{self, MigrationPlan};
+use crate::palette::{self, PaletteCommand, ResourcePath};
+use crate::probe::{self, LatencyProbe};
+use crate::produce::{self, ProducedMessage};
+use crate::pulsar_listener::{Decoder, MessageMeta, SchemaDefinitions, StartPosition, TopicEvent};
+use crate::recovery::{self, DeletedTopic};
+use crate::session::SessionLog;
+use crate::table_export::{self, TableRows};
+use crate::tasks::TaskRegistry;
+use crate::token_refresh;
+use crate::ui_state::UiState;
+use crate::{draw, pulsar_listener, AppEvent, CompanionTopic, ControlEvent, YankPart};
 
 #[derive(Clone)]
 pub struct Tenants {
@@ -34,55 +58,733 @@ pub struct Tenants {
 pub struct Namespaces {
     pub namespaces: Vec<Namespace>,
     pub cursor: Option<usize>,
+    pub limits: NamespaceLimits,
+    pub stats: BTreeMap<String, NamespaceStats>,
+}
+
+#[derive(Clone, Debug)]
+pub struct NamespaceStats {
+    pub topics: usize,
+    pub backlog: i64,
+}
+
+// Client quotas of the namespace under the cursor, `None` means the broker default applies.
+#[derive(Clone, Debug, Default)]
+pub struct NamespaceLimits {
+    pub max_consumers_per_topic: Option<i32>,
+    pub max_producers_per_topic: Option<i32>,
+    pub max_consumers_per_subscription: Option<i32>,
+}
+
+impl NamespaceLimits {
+    // Parses `<consumers/topic> <producers/topic> <consumers/subscription>`, `-` unsets a limit.
+    pub fn parse(input: &str) -> anyhow::Result<NamespaceLimits> {
+        let parse_limit = |limit: &str| match limit {
+            "-" => Ok(None),
+            limit => limit
+                .parse::<i32>()
+                .map(Some)
+                .map_err(|_| anyhow!("Invalid limit '{}', expected a number or '-'", limit)),
+        };
+
+        let (consumers_per_topic, producers_per_topic, consumers_per_subscription) = input
+            .split_whitespace()
+            .collect_tuple()
+            .ok_or(anyhow!(
+                "Expected '<consumers/topic> <producers/topic> <consumers/sub>', got '{}'",
+                input
+            ))?;
+
+        Ok(NamespaceLimits {
+            max_consumers_per_topic: parse_limit(consumers_per_topic)?,
+            max_producers_per_topic: parse_limit(producers_per_topic)?,
+            max_consumers_per_subscription: parse_limit(consumers_per_subscription)?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max_consumers_per_topic.is_none()
+            && self.max_producers_per_topic.is_none()
+            && self.max_consumers_per_subscription.is_none()
+    }
+
+    pub fn to_input(&self) -> String {
+        [
+            self.max_consumers_per_topic,
+            self.max_producers_per_topic,
+            self.max_consumers_per_subscription,
+        ]
+        .iter()
+        .map(|limit| limit.map_or("-".to_string(), |limit| limit.to_string()))
+        .join(" ")
+    }
+}
+
+impl fmt::Display for NamespaceLimits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let limits = [
+            (self.max_consumers_per_topic, "consumers/topic"),
+            (self.max_producers_per_topic, "producers/topic"),
+            (self.max_consumers_per_subscription, "consumers/sub"),
+        ];
+
+        write!(
+            f,
+            "{}",
+            limits
+                .iter()
+                .filter_map(|(limit, label)| limit.map(|limit| format!("max {limit} {label}")))
+                .join(", ")
+        )
+    }
 }
 
 #[derive(Clone)]
 pub struct Topics {
     pub topics: Vec<Topic>,
+    pub bookmarks: Vec<String>,
     pub cursor: Option<usize>,
+    pub stats: BTreeMap<String, TopicStats>,
+    pub sort: TableSort,
+}
+
+impl Topics {
+    pub const COLUMNS: &'static [&'static str] = &[
+        "name",
+        "producers",
+        "subs",
+        "in msg/s",
+        "out msg/s",
+        "storage",
+        "backlog",
+    ];
+
+    // Bookmarks are `tenant/namespace/topic` paths, subscriptions add their name as a fourth part.
+    pub fn is_bookmarked(&self, topic: &Topic) -> bool {
+        self.bookmarks
+            .iter()
+            .any(|bookmark| topic.fqn.ends_with(&format!("://{bookmark}")))
+    }
+
+    // Sorts topics by the sort column, keeping bookmarked topics on top. Topics still waiting for
+    // their stats come first in ascending order.
+    pub fn sort(&mut self) {
+        let mut topics = std::mem::take(&mut self.topics);
+        let bookmarked: BTreeSet<String> = topics
+            .iter()
+            .filter(|topic| self.is_bookmarked(topic))
+            .map(|topic| topic.name.clone())
+            .collect();
+        let stat: Option<fn(&TopicStats) -> f64> = match self.sort.column {
+            1 => Some(|stats| stats.producers as f64),
+            2 => Some(|stats| stats.subscriptions as f64),
+            3 => Some(|stats| stats.msg_rate_in),
+            4 => Some(|stats| stats.msg_rate_out),
+            5 => Some(|stats| stats.storage_size as f64),
+            6 => Some(|stats| stats.backlog as f64),
+            _ => None,
+        };
+
+        topics.sort_by(|a, b| {
+            let by_column = match stat {
+                Some(stat) => compare_values(
+                    self.stats.get(&a.name).map(stat),
+                    self.stats.get(&b.name).map(stat),
+                ),
+                None => a.name.cmp(&b.name),
+            };
+
+            bookmarked
+                .contains(&b.name)
+                .cmp(&bookmarked.contains(&a.name))
+                .then(self.sort.apply(by_column))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        self.topics = topics;
+    }
+
+    pub fn resort(&mut self) {
+        let selected = self
+            .cursor
+            .map(|cursor| self.topics[cursor].name.clone());
+        self.sort();
+        self.cursor = selected.and_then(|name| {
+            self.topics
+                .iter()
+                .position(|topic| topic.name == name)
+        });
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TableSort {
+    pub column: usize,
+    pub descending: bool,
+}
+
+impl TableSort {
+    fn apply(&self, ordering: Ordering) -> Ordering {
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+
+    fn next_column(&mut self, columns: usize) {
+        self.column = (self.column + 1) % columns;
+    }
+
+    fn reverse(&mut self) {
+        self.descending = !self.descending;
+    }
+}
+
+pub fn hidden_columns(columns: &[&str], shown: &[String]) -> anyhow::Result<BTreeSet<usize>> {
+    if let Some(unknown) = shown
+        .iter()
+        .find(|column| !columns.contains(&column.as_str()))
+    {
+        return Err(anyhow!(
+            "Unknown column '{}', expected some of: {}",
+            unknown,
+            columns.join(", ")
+        ));
+    }
+
+    Ok((1..columns.len())
+        .filter(|index| {
+            !shown
+                .iter()
+                .any(|column| column == columns[*index])
+        })
+        .collect())
+}
+
+// Orders floats with the missing ones first.
+fn compare_values(a: Option<f64>, b: Option<f64>) -> Ordering {
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
 }
 
 #[derive(Clone)]
 pub struct Subscriptions {
     pub subscriptions: Vec<Subscription>,
     pub cursor: Option<usize>,
+    pub compare_with: Option<String>,
+    pub marked: BTreeSet<SubscriptionPath>,
+    pub sort: TableSort,
+    // Indices into `COLUMNS`, picked in the config or with `=`.
+    pub hidden_columns: BTreeSet<usize>,
+    pub dead_letter_policies: Vec<DeadLetterPolicy>,
+}
+
+impl Subscriptions {
+    pub const COLUMNS: &'static [&'static str] =
+        &["name", "type", "consumers", "backlog", "dispatch"];
+
+    pub fn sort(&mut self) {
+        let sort = self.sort;
+        // Throttled first when descending, then limited ones
+        let dispatch = |sub: &Subscription| match (sub.throttling(), &sub.dispatch_limit) {
+            (Some(_), _) => 2,
+            (None, Some(_)) => 1,
+            (None, None) => 0,
+        };
+
+        self.subscriptions.sort_by(|a, b| {
+            let by_column = match sort.column {
+                1 => a.sub_type.cmp(&b.sub_type),
+                2 => a.consumer_count.cmp(&b.consumer_count),
+                3 => a.backlog_size.cmp(&b.backlog_size),
+                4 => dispatch(a).cmp(&dispatch(b)),
+                _ => a.name.cmp(&b.name),
+            };
+
+            sort.apply(by_column)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+    }
+
+    pub fn resort(&mut self) {
+        let selected = self
+            .cursor
+            .map(|cursor| self.subscriptions[cursor].name.clone());
+        self.sort();
+        self.cursor = selected.and_then(|name| {
+            self.subscriptions
+                .iter()
+                .position(|subscription| subscription.name == name)
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct Comparison {
+    pub subscriptions: Vec<SubscriptionDetails>,
+}
+
+#[derive(Clone, Default)]
+pub struct Overview {
+    pub tenant: String,
+    // Every namespace of the tenant, the loaded ones also have an entry in `namespace_stats`.
+    pub namespaces: Vec<String>,
+    pub namespace_stats: BTreeMap<String, NamespaceStats>,
+    // Keyed by `namespace/topic`.
+    pub topic_stats: BTreeMap<String, TopicStats>,
+}
+
+impl Overview {
+    pub const TOP_TOPICS: usize = 10;
+
+    pub fn topic_count(&self) -> usize {
+        self.namespace_stats
+            .values()
+            .map(|stats| stats.topics)
+            .sum()
+    }
+
+    pub fn backlog(&self) -> i64 {
+        self.namespace_stats
+            .values()
+            .map(|stats| stats.backlog)
+            .sum()
+    }
+
+    pub fn msg_rates(&self) -> (f64, f64) {
+        self.topic_stats
+            .values()
+            .fold((0.0, 0.0), |(rate_in, rate_out), stats| {
+                (rate_in + stats.msg_rate_in, rate_out + stats.msg_rate_out)
+            })
+    }
+
+    pub fn top_by_backlog(&self) -> Vec<(&String, &TopicStats)> {
+        self.topic_stats
+            .iter()
+            .filter(|(_, stats)| stats.backlog > 0)
+            .sorted_by_key(|(_, stats)| std::cmp::Reverse(stats.backlog))
+            .take(Self::TOP_TOPICS)
+            .collect()
+    }
+
+    // Published and dispatched messages together.
+    pub fn top_by_throughput(&self) -> Vec<(&String, &TopicStats)> {
+        let throughput = |stats: &TopicStats| stats.msg_rate_in + stats.msg_rate_out;
+
+        self.topic_stats
+            .iter()
+            .filter(|(_, stats)| throughput(stats) > 0.0)
+            .sorted_by(|(_, a), (_, b)| throughput(b).total_cmp(&throughput(a)))
+            .take(Self::TOP_TOPICS)
+            .collect()
+    }
 }
 
 #[derive(Clone)]
 pub struct Consumers {
     pub consumers: Vec<Consumer>,
     pub cursor: Option<usize>,
+    pub sort: TableSort,
+    pub hidden_columns: BTreeSet<usize>,
+}
+
+impl Consumers {
+    pub const COLUMNS: &'static [&'static str] = &[
+        "name",
+        "address",
+        "client version",
+        "connected since",
+        "unacked messages",
+        "permits",
+        "msg rate out",
+        "throughput out",
+        "assignment",
+    ];
+
+    // Shared subscriptions of big fleets can have thousands of consumers, only one page of them
+    // is rendered at a time.
+    pub const PAGE_SIZE: usize = 100;
+
+    pub fn page_range(&self) -> Range<usize> {
+        let start = self.cursor.unwrap_or(0) / Self::PAGE_SIZE * Self::PAGE_SIZE;
+        start..(start + Self::PAGE_SIZE).min(self.consumers.len())
+    }
+
+    pub fn next_page(&mut self) {
+        let next = self.page_range().start + Self::PAGE_SIZE;
+        if next < self.consumers.len() {
+            self.cursor = Some(next);
+        }
+    }
+
+    pub fn sort(&mut self) {
+        let sort = self.sort;
+        self.consumers.sort_by(|a, b| {
+            let by_column = match sort.column {
+                1 => a.address.cmp(&b.address),
+                2 => a.client_version.cmp(&b.client_version),
+                3 => a.connected_since.cmp(&b.connected_since),
+                4 => a.unacked_messages.cmp(&b.unacked_messages),
+                5 => a.available_permits.cmp(&b.available_permits),
+                6 => compare_values(Some(a.msg_rate_out), Some(b.msg_rate_out)),
+                7 => compare_values(Some(a.msg_throughput_out), Some(b.msg_throughput_out)),
+                8 => (a.active, &a.key_hash_ranges).cmp(&(b.active, &b.key_hash_ranges)),
+                _ => a.name.cmp(&b.name),
+            };
+
+            sort.apply(by_column)
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.address.cmp(&b.address))
+        });
+    }
+
+    // Sorts again keeping the cursor on the same consumer, names repeat across connections.
+    pub fn resort(&mut self) {
+        let selected = self.cursor.map(|cursor| {
+            let consumer = &self.consumers[cursor];
+            (consumer.name.clone(), consumer.address.clone())
+        });
+        self.sort();
+        self.cursor = selected.and_then(|(name, address)| {
+            self.consumers
+                .iter()
+                .position(|consumer| consumer.name == name && consumer.address == address)
+        });
+    }
+
+    pub fn previous_page(&mut self) {
+        if self.cursor.is_some() {
+            self.cursor = Some(
+                self.page_range()
+                    .start
+                    .saturating_sub(Self::PAGE_SIZE),
+            );
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct Listening {
-    pub messages: Vec<SubMessage>,
+    pub messages: VecDeque<SubMessage>,
+    // Kept at the top of the list regardless of the search and of evictions.
+    pub pinned: Vec<SubMessage>,
     pub filtered_messages: Vec<SubMessage>,
     pub panel: SelectedPanel,
     pub cursor: Option<usize>,
     pub search: Option<String>,
+    // Search the filtered messages were last narrowed down with, it lags behind while typing.
+    pub filtered_search: Option<String>,
+    // `filtered_search` parsed once, rather than for every message it is matched against.
+    pub compiled_search: Option<MessageSearch>,
+    pub decoder: Decoder,
+    pub decoder_overridden: bool,
+    pub schema_definitions: SchemaDefinitions,
+    pub paused: bool,
+    pub follow: bool,
+    pub preview_wrap: bool,
+    pub max_messages: usize,
+    pub dropped: usize,
+    pub timestamp_format: String,
+    pub quarantine_only: bool,
+    pub preview_mode: PreviewMode,
+    pub histogram: HistogramWindow,
+    pub preview_search: Option<PreviewSearch>,
+    // Recovered or loaded captures have no subscription behind them.
+    pub browse_only: bool,
+    pub protobuf_descriptors: BTreeMap<String, MessageDescriptor>,
+    // Decode failures per topic name, kept across listening sessions.
+    pub decode_errors: BTreeMap<String, usize>,
+    pub receipts: VecDeque<(Instant, usize)>,
+    // Payload sizes of `messages` with their counts, kept along so the median is not sorted out
+    // of the whole buffer every frame.
+    pub message_sizes: BTreeMap<usize, usize>,
+}
+
+#[derive(Clone)]
+pub enum MessageSearch {
+    Expression(Expression),
+    Property(PropertyFilter),
+    Partition(PartitionFilter),
+    Key(KeyFilter),
+    Regex(SearchPattern),
+    Plain(String),
+    // Incomplete filters, e.g. while typing, keep everything.
+    Invalid,
 }
 
+impl MessageSearch {
+    pub fn parse(search: &str) -> MessageSearch {
+        if Expression::is_expression(search) {
+            Expression::parse(search).map_or(MessageSearch::Invalid, MessageSearch::Expression)
+        } else if PropertyFilter::is_property_filter(search) {
+            PropertyFilter::parse(search).map_or(MessageSearch::Invalid, MessageSearch::Property)
+        } else if PartitionFilter::is_partition_filter(search) {
+            PartitionFilter::parse(search).map_or(MessageSearch::Invalid, MessageSearch::Partition)
+        } else if KeyFilter::is_key_filter(search) {
+            KeyFilter::parse(search).map_or(MessageSearch::Invalid, MessageSearch::Key)
+        } else if SearchPattern::is_regex(search) {
+            SearchPattern::parse(search).map_or(MessageSearch::Invalid, MessageSearch::Regex)
+        } else {
+            MessageSearch::Plain(search.replace(' ', ""))
+        }
+    }
+
+    pub fn matches(&self, message: &SubMessage) -> bool {
+        match self {
+            MessageSearch::Expression(expression) => {
+                serde_json::from_str(&message.body).is_ok_and(|json| expression.matches(&json))
+            }
+            MessageSearch::Property(filter) => filter.matches(&message.properties),
+            MessageSearch::Partition(filter) => filter.matches(message.meta.partition),
+            MessageSearch::Key(filter) => filter.matches(message.meta.key.as_deref()),
+            MessageSearch::Regex(pattern) => {
+                pattern.is_match(&message.body)
+                    || message
+                        .properties
+                        .iter()
+                        .any(|prop| pattern.is_match(prop))
+            }
+            MessageSearch::Plain(search) => {
+                message.body.contains(search.as_str())
+                    || message
+                        .properties
+                        .iter()
+                        .any(|prop| prop.contains(search.as_str()))
+            }
+            MessageSearch::Invalid => true,
+        }
+    }
+}
+
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+// Typed filters are applied once the keys stop for this long, large lists lag otherwise.
+const FILTER_DEBOUNCE: Duration = Duration::from_millis(150);
+const PREVIEW_MATCH_CONTEXT: usize = 2;
+const MIN_LISTENING_SPLIT: u16 = 20;
+const MAX_LISTENING_SPLIT: u16 = 80;
+
 impl Listening {
+    // Applies the typed search. A plain search that only got longer can only match fewer
+    // messages, so the filtered ones are narrowed down instead of scanning every message again.
+    pub fn apply_search(&mut self) {
+        let narrows = match (&self.filtered_search, &self.search) {
+            (Some(previous), Some(search)) => {
+                is_plain_search(previous)
+                    && is_plain_search(search)
+                    && search
+                        .replace(' ', "")
+                        .starts_with(&previous.replace(' ', ""))
+            }
+            _ => false,
+        };
+
+        if narrows {
+            self.set_filtered_search();
+            let filtered = std::mem::take(&mut self.filtered_messages);
+            self.filtered_messages = filtered
+                .into_iter()
+                .filter(|message| self.is_pinned(message) || self.matches(message))
+                .collect();
+        } else {
+            self.filter_messages();
+        }
+    }
+
     pub fn filter_messages(&mut self) {
-        let messages = self.messages.clone();
-        self.filtered_messages = match &self.search {
-            Some(search) => {
-                let search = search.replace(' ', "");
+        self.set_filtered_search();
+        self.filtered_messages = self
+            .pinned
+            .iter()
+            .chain(
+                self.messages
+                    .iter()
+                    .filter(|message| self.matches(message) && !self.is_pinned(message)),
+            )
+            .cloned()
+            .collect_vec();
+    }
 
-                messages
-                    .into_iter()
-                    .filter(|message| {
-                        message.body.contains(&search)
-                            || message
-                                .properties
-                                .iter()
-                                .any(|prop| prop.contains(&search))
-                    })
-                    .collect_vec()
-            }
-            None => messages,
+    fn set_filtered_search(&mut self) {
+        if self.filtered_search != self.search {
+            self.compiled_search = self.search.as_deref().map(MessageSearch::parse);
+        }
+        self.filtered_search = self.search.clone();
+    }
+
+    pub fn is_pinned(&self, message: &SubMessage) -> bool {
+        self.pinned
+            .iter()
+            .any(|pinned| pinned.meta.message_id == message.meta.message_id)
+    }
+
+    // Pins or unpins the selected message, the cursor follows it to its new position.
+    pub fn toggle_pin(&mut self) {
+        let Some(message) = self
+            .cursor
+            .and_then(|cursor| self.filtered_messages.get(cursor))
+            .cloned()
+        else {
+            return;
         };
+
+        if self.is_pinned(&message) {
+            self.pinned
+                .retain(|pinned| pinned.meta.message_id != message.meta.message_id);
+        } else {
+            self.pinned.push(message.clone());
+        }
+        self.filter_messages();
+        self.cursor = self
+            .filtered_messages
+            .iter()
+            .position(|filtered| filtered.meta.message_id == message.meta.message_id)
+            .or(get_new_cursor(&self.filtered_messages, self.cursor));
+    }
+
+    fn matches(&self, message: &SubMessage) -> bool {
+        if self.quarantine_only && message.decode_error.is_none() {
+            return false;
+        }
+
+        self.compiled_search
+            .as_ref()
+            .is_none_or(|search| search.matches(message))
+    }
+
+    // Appends a received message, evicting the oldest one once the buffer is full. The cursor
+    // is shifted along so it stays on the same message.
+    pub fn capture(&mut self, message: SubMessage) {
+        let now = Instant::now();
+        self.receipts.push_back((now, message.size));
+        while self
+            .receipts
+            .front()
+            .is_some_and(|(received_at, _)| now.duration_since(*received_at) > RATE_WINDOW)
+        {
+            self.receipts.pop_front();
+        }
+
+        *self
+            .message_sizes
+            .entry(message.size)
+            .or_default() += 1;
+        self.messages.push_back(message);
+
+        if self.messages.len() > self.max_messages {
+            if let Some(evicted) = self.messages.pop_front() {
+                if let Some(count) = self.message_sizes.get_mut(&evicted.size) {
+                    *count -= 1;
+                    if *count == 0 {
+                        self.message_sizes.remove(&evicted.size);
+                    }
+                }
+                self.dropped += 1;
+                if self.matches(&evicted) && !self.is_pinned(&evicted) {
+                    self.cursor = self.cursor.map(|cursor| cursor.saturating_sub(1));
+                }
+            }
+        }
+
+        self.filter_messages();
+    }
+
+    pub fn to_sub_message(&self, event: TopicEvent) -> SubMessage {
+        SubMessage {
+            body: self
+                .decoder
+                .decode(&event.data, &self.schema_definitions),
+            decode_error: self
+                .decoder
+                .validate(&event.data, &self.schema_definitions),
+            raw: event.data,
+            properties: event.properties,
+            size: event.size,
+            meta: event.meta,
+        }
+    }
+
+    pub fn set_decoder(&mut self, decoder: Decoder) {
+        self.decoder = decoder;
+        self.decoder_overridden = true;
+        for message in self.messages.iter_mut() {
+            message.body = decoder.decode(&message.raw, &self.schema_definitions);
+            message.decode_error = decoder.validate(&message.raw, &self.schema_definitions);
+        }
+        self.filter_messages();
+    }
+
+    pub fn receive_rate(&self) -> (f64, f64) {
+        let (count, bytes) = self
+            .receipts
+            .iter()
+            .filter(|(received_at, _)| received_at.elapsed() <= RATE_WINDOW)
+            .fold((0, 0), |(count, bytes), (_, size)| {
+                (count + 1, bytes + size)
+            });
+        let seconds = RATE_WINDOW.as_secs_f64();
+
+        (count as f64 / seconds, bytes as f64 / seconds)
+    }
+
+    pub fn publish_histogram(&self, window: Duration, buckets: usize) -> Vec<u64> {
+        let mut counts = vec![0; buckets];
+        let now = Utc::now().timestamp_millis();
+        let window = window.as_millis() as i64;
+        let bucket_span = (window / buckets.max(1) as i64).max(1);
+
+        for message in &self.filtered_messages {
+            let age = now - message.meta.publish_time as i64;
+            if (0..window).contains(&age) {
+                let bucket = (age / bucket_span) as usize;
+                if let Some(count) = counts.get_mut(buckets - 1 - bucket.min(buckets - 1)) {
+                    *count += 1;
+                }
+            }
+        }
+
+        counts
+    }
+
+    pub fn preview_match_lines(&self) -> Vec<usize> {
+        match (&self.preview_search, draw::preview_content(self)) {
+            (Some(search), Some(content)) => content
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !search.pattern.find_all(line).is_empty())
+                .map(|(index, _)| index)
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    pub fn jump_to_preview_match(&mut self, step: isize) {
+        let lines = self.preview_match_lines();
+        if let Some(search) = self.preview_search.as_mut() {
+            if lines.is_empty() {
+                return;
+            }
+            search.current = (search.current.min(lines.len() - 1) as isize + step)
+                .rem_euclid(lines.len() as isize) as usize;
+            self.panel = SelectedPanel::Right {
+                scroll_offset: lines[search.current].saturating_sub(PREVIEW_MATCH_CONTEXT) as u16,
+            };
+        }
+    }
+
+    pub fn follow_newest(&mut self) {
+        self.cursor = self.filtered_messages.len().checked_sub(1);
+    }
+
+    pub fn median_message_size(&self) -> Option<usize> {
+        let mut remaining = self.messages.len() / 2;
+        for (size, count) in &self.message_sizes {
+            if remaining < *count {
+                return Some(*size);
+            }
+            remaining -= count;
+        }
+
+        None
     }
 }
 
@@ -93,6 +795,8 @@ pub enum Resource {
     Topics,
     Subscriptions,
     Consumers,
+    Comparison,
+    Overview,
     Listening { sub_name: String },
 }
 
@@ -102,6 +806,78 @@ impl std::fmt::Display for Resource {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PreviewMode {
+    // Decoded with the active decoder, JSON is pretty printed.
+    Decoded,
+    Utf8,
+    Hex,
+}
+
+impl PreviewMode {
+    pub fn next(&self) -> PreviewMode {
+        match self {
+            PreviewMode::Decoded => PreviewMode::Utf8,
+            PreviewMode::Utf8 => PreviewMode::Hex,
+            PreviewMode::Hex => PreviewMode::Decoded,
+        }
+    }
+}
+
+impl std::fmt::Display for PreviewMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            PreviewMode::Decoded => "decoded",
+            PreviewMode::Utf8 => "raw utf-8",
+            PreviewMode::Hex => "hex",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Clone)]
+pub struct PreviewSearch {
+    pub query: String,
+    pub pattern: SearchPattern,
+    pub current: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HistogramWindow {
+    Hidden,
+    Minute,
+    TenMinutes,
+}
+
+impl HistogramWindow {
+    pub fn next(&self) -> HistogramWindow {
+        match self {
+            HistogramWindow::Hidden => HistogramWindow::Minute,
+            HistogramWindow::Minute => HistogramWindow::TenMinutes,
+            HistogramWindow::TenMinutes => HistogramWindow::Hidden,
+        }
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        match self {
+            HistogramWindow::Hidden => None,
+            HistogramWindow::Minute => Some(Duration::from_secs(60)),
+            HistogramWindow::TenMinutes => Some(Duration::from_secs(600)),
+        }
+    }
+}
+
+impl std::fmt::Display for HistogramWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            HistogramWindow::Hidden => "hidden",
+            HistogramWindow::Minute => "last 60s",
+            HistogramWindow::TenMinutes => "last 10m",
+        };
+        write!(f, "{name}")
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum SelectedPanel {
     Left,
@@ -140,139 +916,745 @@ pub struct Topic {
     pub fqn: String,
 }
 
+#[derive(Clone, Debug)]
+pub struct TopicStats {
+    pub backlog: i64,
+    pub subscriptions: usize,
+    pub producers: usize,
+    pub msg_rate_in: f64,
+    pub msg_rate_out: f64,
+    pub storage_size: i64,
+}
+
 #[derive(Clone, Debug)]
 pub struct Subscription {
     pub name: String,
     pub sub_type: String,
     pub backlog_size: i64,
     pub consumer_count: usize,
+    pub properties: BTreeMap<String, String>,
+    pub ack_state: AckState,
+    pub msg_rate_out: f64,
+    pub msg_throughput_out: f64,
+    pub dispatch_limit: Option<DispatchLimit>,
 }
 
 #[derive(Clone, Debug)]
-pub struct Consumer {
-    pub name: String,
-    pub unacked_messages: i32,
-    pub connected_since: String,
+pub struct DispatchLimit {
+    pub msg_per_sec: Option<f64>,
+    pub bytes_per_sec: Option<f64>,
+    pub level: &'static str,
 }
 
-#[derive(Clone, Debug)]
-pub struct SubMessage {
-    pub body: String,
-    pub properties: Vec<String>,
-}
+// Observed dispatch rate this close to the limit counts as throttled.
+const THROTTLED_RATIO: f64 = 0.9;
 
-pub struct ConfirmationModal {
-    pub message: String,
-    pub command: ConfirmedCommand,
+impl Subscription {
+    pub fn throttling(&self) -> Option<String> {
+        let limit = self.dispatch_limit.as_ref()?;
+        if self.backlog_size == 0 {
+            return None;
+        }
+
+        let at_limit = |observed: f64, limit: Option<f64>| {
+            limit.is_some_and(|limit| observed >= limit * THROTTLED_RATIO)
+        };
+
+        if at_limit(self.msg_rate_out, limit.msg_per_sec) {
+            Some(format!(
+                "throttled by {} policy ({:.1} msg/s)",
+                limit.level,
+                limit.msg_per_sec.unwrap_or_default()
+            ))
+        } else if at_limit(self.msg_throughput_out, limit.bytes_per_sec) {
+            Some(format!(
+                "throttled by {} policy ({:.0} B/s)",
+                limit.level,
+                limit.bytes_per_sec.unwrap_or_default()
+            ))
+        } else {
+            None
+        }
+    }
 }
 
-pub enum ConfirmedCommand {
-    CloseInfoMessage,
-    DeleteSubscription {
-        tenant: String,
-        namespace: String,
-        topic: String,
-        sub_name: String,
-        cfg: Configuration,
-    },
-    SkipAllMessages {
-        tenant: String,
-        namespace: String,
-        topic: String,
-        sub_name: String,
-        cfg: Configuration,
-    },
-    SeekSubscription {
-        tenant: String,
-        namespace: String,
-        topic: String,
-        sub_name: String,
-        time_delta: TimeDelta,
-        cfg: Configuration,
-    },
+// Acknowledgements of a subscription cursor past its mark-delete position, taken from the
+// individually deleted message ranges of the topic internal stats.
+#[derive(Clone, Debug, Default)]
+pub struct AckState {
+    pub mark_delete_position: Option<String>,
+    // Alternating unacked and acked runs of entries, starting right after the mark-delete
+    // position.
+    pub segments: Vec<AckSegment>,
 }
 
-#[derive(Clone)]
-pub struct Resources {
-    pub tenants: Tenants,
-    pub namespaces: Namespaces,
-    pub topics: Topics,
-    pub subscriptions: Subscriptions,
-    pub consumers: Consumers,
-    pub listening: Listening,
+#[derive(Clone, Debug)]
+pub struct AckSegment {
+    pub acked: bool,
+    pub entries: u64,
 }
 
-impl Resources {
-    fn cursor_up(&mut self, active_resource: &Resource) {
-        match active_resource {
-            Resource::Tenants => {
-                self.tenants.cursor = cursor_up(self.tenants.cursor, self.tenants.tenants.len())
+impl AckState {
+    // Parses ranges like `[(12:3..12:5], (12:7..12:9]]`, each `(from..to]` range is acked.
+    pub fn parse(mark_delete_position: Option<String>, individually_deleted: &str) -> AckState {
+        let parse_position = |position: &str| {
+            position
+                .trim()
+                .split_once(':')
+                .and_then(|(ledger, entry)| {
+                    Some((ledger.parse::<i64>().ok()?, entry.parse::<i64>().ok()?))
+                })
+        };
+        // Entries between positions of different ledgers are unknown, count them as one
+        let entries_between = |from: (i64, i64), to: (i64, i64)| {
+            if from.0 == to.0 {
+                (to.1 - from.1).max(0) as u64
+            } else {
+                1
             }
+        };
 
-            Resource::Namespaces => {
-                self.namespaces.cursor =
-                    cursor_up(self.namespaces.cursor, self.namespaces.namespaces.len())
+        let ranges = individually_deleted
+            .trim_matches(|c| c == '[' || c == ']')
+            .split(", ")
+            .filter_map(|range| {
+                let (from, to) = range
+                    .trim_matches(|c| c == '(' || c == ']')
+                    .split_once("..")?;
+                Some((parse_position(from)?, parse_position(to)?))
+            })
+            .collect_vec();
+
+        let mut previous = mark_delete_position
+            .as_deref()
+            .and_then(parse_position);
+        let mut segments = vec![];
+        for (from, to) in ranges {
+            if let Some(previous) = previous {
+                segments.push(AckSegment {
+                    acked: false,
+                    entries: entries_between(previous, from),
+                });
             }
+            segments.push(AckSegment {
+                acked: true,
+                entries: entries_between(from, to),
+            });
+            previous = Some(to);
+        }
+
+        AckState { mark_delete_position, segments }
+    }
+
+    pub fn holes(&self) -> usize {
+        self.segments
+            .iter()
+            .filter(|segment| !segment.acked)
+            .count()
+    }
+
+    pub fn unacked_entries(&self) -> u64 {
+        self.segments
+            .iter()
+            .filter(|segment| !segment.acked)
+            .map(|segment| segment.entries)
+            .sum()
+    }
+}
+
+// Fully qualified subscription, marks can span several topics.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SubscriptionPath {
+    pub tenant: String,
+    pub namespace: String,
+    pub topic: String,
+    pub sub_name: String,
+}
+
+impl fmt::Display for SubscriptionPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.topic, self.sub_name)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum SeekTarget {
+    Relative(TimeDelta),
+    Absolute(DateTime<Utc>),
+}
+
+impl SeekTarget {
+    pub fn timestamp_millis(&self) -> i64 {
+        match self {
+            SeekTarget::Relative(time_delta) => (Utc::now() - *time_delta).timestamp_millis(),
+            SeekTarget::Absolute(timestamp) => timestamp.timestamp_millis(),
+        }
+    }
+
+    // Parses `<n>m`, `<n>h` and `<n>d` relative offsets or a local `YYYY-MM-DD HH:MM[:SS]` /
+    // RFC 3339 timestamp.
+    pub fn parse(input: &str) -> anyhow::Result<SeekTarget> {
+        let input = input.trim();
+        let relative = |unit: char| {
+            input
+                .strip_suffix(unit)
+                .and_then(|amount| amount.trim().parse::<i64>().ok())
+        };
+
+        if let Some(minutes) = relative('m').and_then(TimeDelta::try_minutes) {
+            return Ok(SeekTarget::Relative(minutes));
+        }
+        if let Some(hours) = relative('h').and_then(TimeDelta::try_hours) {
+            return Ok(SeekTarget::Relative(hours));
+        }
+        if let Some(days) = relative('d').and_then(TimeDelta::try_days) {
+            return Ok(SeekTarget::Relative(days));
+        }
+        if let Ok(timestamp) = DateTime::parse_from_rfc3339(input) {
+            return Ok(SeekTarget::Absolute(timestamp.with_timezone(&Utc)));
+        }
+
+        ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+            .and_then(|naive| Local.from_local_datetime(&naive).single())
+            .map(|timestamp| SeekTarget::Absolute(timestamp.with_timezone(&Utc)))
+            .ok_or(anyhow!(
+                "Can't parse '{}', expected e.g. '3h', '2d' or '2024-05-01 14:30'",
+                input
+            ))
+    }
+}
+
+impl fmt::Display for SeekTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SeekTarget::Relative(time_delta) => write!(f, "{}h back", time_delta.num_hours()),
+            SeekTarget::Absolute(timestamp) => write!(
+                f,
+                "{}",
+                timestamp
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SubscriptionDetails {
+    pub name: String,
+    pub sub_type: String,
+    pub backlog_size: i64,
+    pub unacked_messages: i64,
+    pub msg_rate_out: f64,
+    pub msg_throughput_out: f64,
+    pub message_ack_rate: f64,
+    pub msg_rate_redeliver: f64,
+    pub mark_delete_position: String,
+    pub read_position: String,
+    pub consumers: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Consumer {
+    pub name: String,
+    pub unacked_messages: i32,
+    pub connected_since: String,
+    pub address: String,
+    pub client_version: String,
+    pub available_permits: i32,
+    pub msg_rate_out: f64,
+    pub msg_throughput_out: f64,
+    // The consumer messages are dispatched to, on Failover subscriptions.
+    pub active: bool,
+    // Sticky key hash ranges routed to the consumer, on Key_Shared subscriptions.
+    pub key_hash_ranges: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SubMessage {
+    pub body: String,
+    pub raw: Vec<u8>,
+    pub properties: Vec<String>,
+    pub size: usize,
+    #[serde(default)]
+    pub decode_error: Option<String>,
+    #[serde(default)]
+    pub meta: MessageMeta,
+}
+
+impl SubMessage {
+    // Properties are kept as `key:value` strings.
+    pub fn property_pairs(&self) -> Vec<(&str, &str)> {
+        self.properties
+            .iter()
+            .map(|property| property.split_once(':').unwrap_or((property, "")))
+            .collect()
+    }
+}
+
+pub struct ConfirmationModal {
+    pub message: String,
+    pub command: ConfirmedCommand,
+}
+
+#[derive(Clone)]
+pub struct Action {
+    pub key: String,
+    pub label: String,
+    pub event: ControlEvent,
+}
+
+pub struct ActionMenu {
+    pub title: &'static str,
+    pub actions: Vec<Action>,
+    pub query: String,
+    pub cursor: usize,
+    // Typing an action's key runs it right away instead of searching.
+    pub shortcuts: bool,
+}
+
+impl ActionMenu {
+    pub fn matching(&self) -> Vec<&Action> {
+        let query = self.query.to_lowercase();
+        self.actions
+            .iter()
+            .filter(|action| {
+                action.label.to_lowercase().contains(&query)
+                    || action.key.to_lowercase().contains(&query)
+            })
+            .collect()
+    }
+}
+
+pub struct ProduceModal {
+    pub topic: Topic,
+    pub fields: [String; 3],
+    pub focused: usize,
+}
+
+impl ProduceModal {
+    pub const LABELS: [&'static str; 3] = [
+        "body",
+        "key (optional)",
+        "properties (key=value, comma separated)",
+    ];
+}
+
+pub struct ListFilter {
+    pub view: View,
+    pub query: String,
+    // Invalid regexes, e.g. while typing, keep everything.
+    pub pattern: Result<SearchPattern, String>,
+}
+
+impl ListFilter {
+    pub fn new(view: View, query: &str) -> ListFilter {
+        ListFilter {
+            view,
+            query: query.to_string(),
+            pattern: SearchPattern::parse(query).map_err(|err| err.to_string()),
+        }
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        self.pattern
+            .as_ref()
+            .map_or(true, |pattern| pattern.is_match(name))
+    }
+}
+
+pub struct InputModal {
+    pub title: String,
+    pub input: String,
+    pub command: InputCommand,
+}
+
+#[allow(clippy::large_enum_variant)]
+pub enum InputCommand {
+    StartListening {
+        topic: Topic,
+    },
+    MigrateTopic {
+        tenant: String,
+        namespace: String,
+        source: String,
+    },
+    RecoverTopic {
+        tenant: String,
+        namespace: String,
+        deleted: Vec<DeletedTopic>,
+    },
+    ExportSession,
+    ExportTable,
+    SetNamespaceLimits {
+        tenant: String,
+        namespace: String,
+        cfg: Configuration,
+    },
+    TypedConfirmation {
+        expected: String,
+        command: ConfirmedCommand,
+    },
+    Bench {
+        topic_fqn: String,
+    },
+    ConsumeBench {
+        topic_fqn: String,
+    },
+    SeekSubscriptions {
+        subscriptions: Vec<SubscriptionPath>,
+    },
+    FilterList {
+        view: View,
+    },
+    SearchPreview,
+    Palette,
+    YankProperty,
+    ExportMessage,
+    ExportCapture,
+    SaveCapture,
+    OpenCapture,
+    FetchMessage {
+        topic: Topic,
+    },
+    SeekNamespace {
+        tenant: String,
+        namespace: String,
+        topics: Vec<String>,
+    },
+    SetSubscriptionProperty {
+        tenant: String,
+        namespace: String,
+        topic: String,
+        sub_name: String,
+        properties: BTreeMap<String, String>,
+        cfg: Configuration,
+    },
+}
+
+pub enum ConfirmedCommand {
+    CloseInfoMessage,
+    RecoverJournal {
+        checkpoint: Checkpoint,
+    },
+    DeleteSubscription {
+        tenant: String,
+        namespace: String,
+        topic: String,
+        sub_name: String,
+        cfg: Configuration,
+    },
+    SkipAllMessages {
+        tenant: String,
+        namespace: String,
+        topic: String,
+        sub_name: String,
+        cfg: Configuration,
+    },
+    SeekSubscription {
+        tenant: String,
+        namespace: String,
+        topic: String,
+        sub_name: String,
+        time_delta: TimeDelta,
+        cfg: Configuration,
+    },
+    DeleteSubscriptions {
+        subscriptions: Vec<SubscriptionPath>,
+        cfg: Configuration,
+    },
+    SeekSubscriptions {
+        subscriptions: Vec<SubscriptionPath>,
+        target: SeekTarget,
+        cfg: Configuration,
+    },
+    Bench {
+        topic_fqn: String,
+        spec: BenchSpec,
+    },
+    SetSubscriptionPaused {
+        subscription: SubscriptionPath,
+        paused: bool,
+        cfg: Configuration,
+    },
+    MigrateTopic {
+        plan: MigrationPlan,
+        cfg: Configuration,
+    },
+    RecoverTopic {
+        tenant: String,
+        namespace: String,
+        topic: DeletedTopic,
+        cfg: Configuration,
+    },
+}
+
+#[derive(Clone)]
+pub struct Resources {
+    pub tenants: Tenants,
+    pub namespaces: Namespaces,
+    pub topics: Topics,
+    pub subscriptions: Subscriptions,
+    pub consumers: Consumers,
+    pub comparison: Comparison,
+    pub overview: Overview,
+    pub listening: Listening,
+}
+
+impl Resources {
+    fn columns_mut(
+        &mut self,
+        active_resource: &Resource,
+    ) -> Option<(&'static [&'static str], &mut BTreeSet<usize>)> {
+        match active_resource {
+            Resource::Subscriptions => Some((
+                Subscriptions::COLUMNS,
+                &mut self.subscriptions.hidden_columns,
+            )),
+            Resource::Consumers => Some((Consumers::COLUMNS, &mut self.consumers.hidden_columns)),
+            _ => None,
+        }
+    }
 
+    fn change_sort(
+        &mut self,
+        active_resource: &Resource,
+        change: impl FnOnce(&mut TableSort, usize),
+    ) {
+        match active_resource {
             Resource::Topics => {
-                self.topics.cursor = cursor_up(self.topics.cursor, self.topics.topics.len())
+                change(&mut self.topics.sort, Topics::COLUMNS.len());
+                self.topics.resort();
             }
-
             Resource::Subscriptions => {
-                self.subscriptions.cursor = cursor_up(
-                    self.subscriptions.cursor,
-                    self.subscriptions.subscriptions.len(),
-                )
+                change(&mut self.subscriptions.sort, Subscriptions::COLUMNS.len());
+                self.subscriptions.resort();
             }
-
             Resource::Consumers => {
-                self.consumers.cursor =
-                    cursor_up(self.consumers.cursor, self.consumers.consumers.len())
+                change(&mut self.consumers.sort, Consumers::COLUMNS.len());
+                self.consumers.resort();
             }
+            _ => {}
+        }
+    }
+
+    fn cursor_up(&mut self, active_resource: &Resource, filter: Option<&ListFilter>) {
+        self.step_cursor(active_resource, filter, cursor_up)
+    }
+
+    fn cursor_down(&mut self, active_resource: &Resource, filter: Option<&ListFilter>) {
+        self.step_cursor(active_resource, filter, cursor_down)
+    }
+
+    // Moves the cursor of the active list, `step` gets the current position and the item count.
+    fn step_cursor(
+        &mut self,
+        active_resource: &Resource,
+        filter: Option<&ListFilter>,
+        step: impl Fn(Option<usize>, usize) -> Option<usize>,
+    ) {
+        match active_resource {
+            Resource::Comparison | Resource::Overview => {}
 
             Resource::Listening { .. } => {
-                self.listening.cursor = cursor_up(
+                self.listening.cursor = step(
                     self.listening.cursor,
                     self.listening.filtered_messages.len(),
                 )
             }
+
+            _ => self.move_cursor(active_resource, filter, step),
+        }
+    }
+
+    fn move_cursor(
+        &mut self,
+        active_resource: &Resource,
+        filter: Option<&ListFilter>,
+        step: impl Fn(Option<usize>, usize) -> Option<usize>,
+    ) {
+        let visible = self.visible_items(active_resource, filter);
+        let cursor = match active_resource {
+            Resource::Tenants => &mut self.tenants.cursor,
+            Resource::Namespaces => &mut self.namespaces.cursor,
+            Resource::Topics => &mut self.topics.cursor,
+            Resource::Subscriptions => &mut self.subscriptions.cursor,
+            Resource::Consumers => &mut self.consumers.cursor,
+            Resource::Comparison | Resource::Overview | Resource::Listening { .. } => return,
+        };
+        let position = cursor.and_then(|cursor| visible.iter().position(|index| *index == cursor));
+
+        if let Some(index) =
+            step(position, visible.len()).and_then(|position| visible.get(position))
+        {
+            *cursor = Some(*index)
+        }
+    }
+
+    // Keeps the cursor on the filtered list, moving it to the first match if it got hidden.
+    fn snap_cursor(&mut self, active_resource: &Resource, filter: Option<&ListFilter>) {
+        let visible = self.visible_items(active_resource, filter);
+        let cursor = match active_resource {
+            Resource::Tenants => self.tenants.cursor,
+            Resource::Namespaces => self.namespaces.cursor,
+            Resource::Topics => self.topics.cursor,
+            Resource::Subscriptions => self.subscriptions.cursor,
+            _ => return,
+        };
+
+        if !cursor.is_some_and(|cursor| visible.contains(&cursor)) {
+            self.move_cursor(active_resource, filter, |_, _| Some(0));
         }
     }
 
-    fn cursor_down(&mut self, active_resource: &Resource) {
+    pub fn table_rows(
+        &self,
+        active_resource: &Resource,
+        filter: Option<&ListFilter>,
+    ) -> Option<TableRows> {
+        let visible = self.visible_items(active_resource, filter);
+
         match active_resource {
-            Resource::Tenants => {
-                self.tenants.cursor = cursor_down(self.tenants.cursor, self.tenants.tenants.len())
-            }
-            Resource::Namespaces => {
-                self.namespaces.cursor =
-                    cursor_down(self.namespaces.cursor, self.namespaces.namespaces.len())
-            }
             Resource::Topics => {
-                self.topics.cursor = cursor_down(self.topics.cursor, self.topics.topics.len())
+                let rows = visible
+                    .into_iter()
+                    .map(|index| {
+                        let topic = &self.topics.topics[index];
+                        let stats = match self.topics.stats.get(&topic.name) {
+                            Some(stats) => vec![
+                                stats.producers.to_string(),
+                                stats.subscriptions.to_string(),
+                                format!("{:.2}", stats.msg_rate_in),
+                                format!("{:.2}", stats.msg_rate_out),
+                                stats.storage_size.to_string(),
+                                stats.backlog.to_string(),
+                            ],
+                            None => vec![String::new(); Topics::COLUMNS.len() - 1],
+                        };
+
+                        std::iter::once(topic.name.clone())
+                            .chain(stats)
+                            .collect()
+                    })
+                    .collect();
+
+                Some(TableRows {
+                    columns: Topics::COLUMNS,
+                    hidden: BTreeSet::new(),
+                    rows,
+                })
             }
 
             Resource::Subscriptions => {
-                self.subscriptions.cursor = cursor_down(
-                    self.subscriptions.cursor,
-                    self.subscriptions.subscriptions.len(),
-                )
+                let rows = visible
+                    .into_iter()
+                    .map(|index| {
+                        let sub = &self.subscriptions.subscriptions[index];
+                        vec![
+                            sub.name.clone(),
+                            sub.sub_type.clone(),
+                            sub.consumer_count.to_string(),
+                            sub.backlog_size.to_string(),
+                            match (sub.throttling(), &sub.dispatch_limit) {
+                                (Some(_), _) => "throttled".to_string(),
+                                (None, Some(_)) => "limited".to_string(),
+                                (None, None) => String::new(),
+                            },
+                        ]
+                    })
+                    .collect();
+
+                Some(TableRows {
+                    columns: Subscriptions::COLUMNS,
+                    hidden: self.subscriptions.hidden_columns.clone(),
+                    rows,
+                })
             }
 
             Resource::Consumers => {
-                self.consumers.cursor =
-                    cursor_down(self.consumers.cursor, self.consumers.consumers.len())
-            }
+                let sub_type = self
+                    .selected_subscription()
+                    .map(|sub| sub.sub_type.as_str())
+                    .unwrap_or_default();
+                let rows = visible
+                    .into_iter()
+                    .map(|index| {
+                        let consumer = &self.consumers.consumers[index];
+                        vec![
+                            consumer.name.clone(),
+                            consumer.address.clone(),
+                            consumer.client_version.clone(),
+                            consumer.connected_since.clone(),
+                            consumer.unacked_messages.to_string(),
+                            consumer.available_permits.to_string(),
+                            format!("{:.2}", consumer.msg_rate_out),
+                            format!("{:.2}", consumer.msg_throughput_out),
+                            match sub_type {
+                                "Failover" if consumer.active => "active".to_string(),
+                                "Failover" => "standby".to_string(),
+                                "Key_Shared" => consumer.key_hash_ranges.join(" "),
+                                _ => String::new(),
+                            },
+                        ]
+                    })
+                    .collect();
 
-            Resource::Listening { .. } => {
-                self.listening.cursor = cursor_down(
-                    self.listening.cursor,
-                    self.listening.filtered_messages.len(),
-                )
+                Some(TableRows {
+                    columns: Consumers::COLUMNS,
+                    hidden: self.consumers.hidden_columns.clone(),
+                    rows,
+                })
             }
+
+            _ => None,
         }
     }
 
+    // Indices of the list items kept by the filter, consumers are paged instead of filtered.
+    pub fn visible_items(
+        &self,
+        active_resource: &Resource,
+        filter: Option<&ListFilter>,
+    ) -> Vec<usize> {
+        let names: Vec<&String> = match active_resource {
+            Resource::Tenants => self
+                .tenants
+                .tenants
+                .iter()
+                .map(|tenant| &tenant.name)
+                .collect(),
+            Resource::Namespaces => self
+                .namespaces
+                .namespaces
+                .iter()
+                .map(|namespace| &namespace.name)
+                .collect(),
+            Resource::Topics => self
+                .topics
+                .topics
+                .iter()
+                .map(|topic| &topic.name)
+                .collect(),
+            Resource::Subscriptions => self
+                .subscriptions
+                .subscriptions
+                .iter()
+                .map(|subscription| &subscription.name)
+                .collect(),
+            Resource::Consumers => return (0..self.consumers.consumers.len()).collect(),
+            Resource::Comparison | Resource::Overview | Resource::Listening { .. } => vec![],
+        };
+
+        names
+            .into_iter()
+            .enumerate()
+            .filter(|(_, name)| filter.is_none_or(|filter| filter.matches(name)))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
     pub fn selected_tenant(&self) -> Option<&Tenant> {
         self.tenants
             .cursor
@@ -311,6 +1693,123 @@ impl Resources {
             .cursor
             .and_then(|cursor| self.subscriptions.subscriptions.get(cursor))
     }
+
+    // Position of the selected subscription's dead letter or retry topic in the topics list,
+    // only topics of the same namespace are found.
+    pub fn companion_topic_position(&self, companion: CompanionTopic) -> Option<usize> {
+        let namespace_path = format!(
+            "{}/{}",
+            self.selected_tenant_name()?,
+            self.selected_namespace_name()?
+        );
+        let topic = self.selected_topic()?;
+        let subscription = self.selected_subscription()?;
+        let base_name = topic
+            .name
+            .split_once("-partition-")
+            .map(|(base_name, _)| base_name)
+            .unwrap_or(&topic.name);
+
+        let configured = self
+            .subscriptions
+            .dead_letter_policies
+            .iter()
+            .find(|policy| {
+                policy.topic == format!("{}/{}", namespace_path, base_name)
+                    && policy.subscription == subscription.name
+            })
+            .and_then(|policy| match companion {
+                CompanionTopic::DeadLetter => policy.dead_letter_topic.as_ref(),
+                CompanionTopic::Retry => policy.retry_letter_topic.as_ref(),
+            });
+        let companion_name = match configured {
+            Some(configured) => match configured.rsplit_once('/') {
+                Some((path, name)) if path == namespace_path => name.to_string(),
+                _ => return None,
+            },
+            None => format!("{}-{}-{}", base_name, subscription.name, companion.suffix()),
+        };
+        let partition_prefix = format!("{companion_name}-partition-");
+
+        self.topics.topics.iter().position(|topic| {
+            topic.name == companion_name || topic.name.starts_with(&partition_prefix)
+        })
+    }
+
+    pub fn subscription_path(&self, subscription: &Subscription) -> Option<SubscriptionPath> {
+        Some(SubscriptionPath {
+            tenant: self.selected_tenant_name()?.to_string(),
+            namespace: self.selected_namespace_name()?.to_string(),
+            topic: self.selected_topic_name()?.to_string(),
+            sub_name: subscription.name.clone(),
+        })
+    }
+
+    pub fn consumer_limit_warning(&self) -> Option<String> {
+        let limits = &self.namespaces.limits;
+        let topic_consumers: usize = self
+            .subscriptions
+            .subscriptions
+            .iter()
+            .map(|subscription| subscription.consumer_count)
+            .sum();
+        let reached = |limit: Option<i32>, count: usize| {
+            limit.filter(|limit| count >= (*limit).max(0) as usize)
+        };
+
+        if let Some(limit) = reached(
+            limits.max_consumers_per_subscription,
+            self.consumers.consumers.len(),
+        ) {
+            Some(format!(
+                "maxConsumersPerSubscription ({limit}) reached, new consumers will be rejected"
+            ))
+        } else {
+            reached(limits.max_consumers_per_topic, topic_consumers).map(|limit| {
+                format!("maxConsumersPerTopic ({limit}) reached, new consumers will be rejected")
+            })
+        }
+    }
+
+    pub fn select_named(&mut self, view: View, name: &str) -> bool {
+        let (position, cursor) = match view {
+            View::Tenants => (
+                self.tenants
+                    .tenants
+                    .iter()
+                    .position(|tenant| tenant.name == name),
+                &mut self.tenants.cursor,
+            ),
+            View::Namespaces => (
+                self.namespaces
+                    .namespaces
+                    .iter()
+                    .position(|namespace| namespace.name == name),
+                &mut self.namespaces.cursor,
+            ),
+            View::Topics => (
+                self.topics
+                    .topics
+                    .iter()
+                    .position(|topic| topic.name == name),
+                &mut self.topics.cursor,
+            ),
+            View::Subscriptions => (
+                self.subscriptions
+                    .subscriptions
+                    .iter()
+                    .position(|subscription| subscription.name == name),
+                &mut self.subscriptions.cursor,
+            ),
+            View::Consumers | View::Comparison | View::Overview | View::Listening => return false,
+        };
+
+        if position.is_some() {
+            *cursor = position;
+        }
+        position.is_some()
+    }
+
     pub fn selected_message(&self) -> Option<&SubMessage> {
         self.listening
             .cursor
@@ -318,6 +1817,15 @@ impl Resources {
     }
 }
 
+// Plain searches match substrings of the body and properties, unlike the prefixed filters.
+fn is_plain_search(search: &str) -> bool {
+    !Expression::is_expression(search)
+        && !PropertyFilter::is_property_filter(search)
+        && !PartitionFilter::is_partition_filter(search)
+        && !KeyFilter::is_key_filter(search)
+        && !SearchPattern::is_regex(search)
+}
+
 pub fn selected_topic(resources: &Resources) -> Option<Topic> {
     resources
         .topics
@@ -338,6 +1846,34 @@ fn cursor_up(current: Option<usize>, col_size: usize) -> Option<usize> {
     }
 }
 
+// Without events the screen is still redrawn this often, for clocks, rates and toasts to move.
+const REDRAW_INTERVAL: Duration = Duration::from_millis(250);
+
+// Terminal rows not showing list items: the header, borders, padding and table headers.
+const PAGE_MARGIN: u16 = 12;
+
+// List items or preview lines fitting on the screen.
+fn page_size(terminal: &Terminal<CrosstermBackend<Stdout>>) -> usize {
+    terminal
+        .size()
+        .map(|size| size.height.saturating_sub(PAGE_MARGIN) as usize)
+        .unwrap_or(10)
+        .max(1)
+}
+
+// Unlike single steps, pages stop at the ends of the list instead of wrapping around.
+fn page_up(page: usize) -> impl Fn(Option<usize>, usize) -> Option<usize> {
+    move |current, _| Some(current.unwrap_or(0).saturating_sub(page))
+}
+
+fn page_down(page: usize) -> impl Fn(Option<usize>, usize) -> Option<usize> {
+    move |current, col_size| {
+        col_size
+            .checked_sub(1)
+            .map(|last| current.map_or(0, |cursor| (cursor + page).min(last)))
+    }
+}
+
 fn cursor_down(current: Option<usize>, col_size: usize) -> Option<usize> {
     match current {
         Some(cursor) => {
@@ -355,55 +1891,614 @@ pub struct App {
     pub pulsar: PulsarApp,
     pub info_to_show: Option<InfoToShow>,
     pub confirmation_modal: Option<ConfirmationModal>,
+    pub input_modal: Option<InputModal>,
+    pub action_menu: Option<ActionMenu>,
+    pub list_filter: Option<ListFilter>,
+    pub produce_modal: Option<ProduceModal>,
+    pub consume_report: Option<ConsumeReport>,
+    // Set while typed filter input waits for the keys to stop before it is applied.
+    pub filter_due: Option<Instant>,
+    // Dropping it stops the background stats fetch of the listed namespaces or topics.
+    pub stats_fetch: Option<oneshot::Sender<()>>,
+    // Dropping it stops the background refetch of the active view.
+    pub view_refresh: Option<oneshot::Sender<()>>,
+    // Dropping it stops refreshing the token of the active context.
+    pub token_refresh: Option<oneshot::Sender<()>>,
+    pub help_scroll: Option<u16>,
+    pub probe: Option<LatencyProbe>,
+    pub keymap: Keymap,
+    // Key pressed before, for two key sequences like `gg`.
+    pub previous_key: Option<KeyCode>,
     pub active_resource: Resource,
     pub resources: Resources,
     pub pulsar_admin_cfg: Configuration,
+    pub cache: AdminCache,
+    pub connections: Connections,
+    // Configured clusters, the default one first.
+    pub contexts: Vec<(String, Context)>,
+    pub context: String,
     pub cluster_name: String,
+    pub production: bool,
+    pub typed_confirmation: bool,
+    pub fetched_at: Instant,
+    pub freshness_threshold: Duration,
+    pub auto_refresh: bool,
+    pub auto_refresh_interval: Duration,
+    // Last auto-refresh attempt, failed ones are retried only after another interval.
+    pub auto_refreshed_at: Instant,
+    pub tasks: TaskRegistry,
+    pub journal: Journal,
+    pub session: SessionLog,
+    // Stats export dir, its snapshots are used to recover deleted topics.
+    pub snapshot_dir: Option<PathBuf>,
+    pub ui_state: UiState,
+    pub ui_state_path: PathBuf,
 }
 
-pub struct PulsarApp {
-    pub receiver: Receiver<AppEvent>,
-    pub sender: Sender<AppEvent>,
-    pub client: Arc<Mutex<Pulsar<TokioExecutor>>>,
+impl App {
+    fn show_fetched(&mut self, resource: Resource) {
+        self.active_resource = resource;
+        self.fetched_at = Instant::now();
+    }
+
+    fn key_layer(&self) -> Layer {
+        let searching = matches!(self.active_resource, Resource::Listening { .. })
+            && matches!(self.resources.listening.panel, SelectedPanel::Search);
+        if self.confirmation_modal.is_some() {
+            Layer::Confirmation
+        } else if self.input_modal.is_some() || self.produce_modal.is_some() || searching {
+            Layer::Input
+        } else {
+            Layer::Base
+        }
+    }
+
+    // Bindings listed for the active view, backing both the help panel and the `a` menu.
+    pub fn available_bindings(&self) -> Vec<&Binding> {
+        self.keymap
+            .listed(View::of(&self.active_resource))
+            .filter(|binding| {
+                binding.companion().is_none_or(|companion| {
+                    self.resources
+                        .companion_topic_position(companion)
+                        .is_some()
+                })
+            })
+            .collect()
+    }
+
+    pub fn available_actions(&self) -> Vec<Action> {
+        self.available_bindings()
+            .into_iter()
+            .filter(|binding| binding.in_menu)
+            .map(|binding| Action {
+                key: binding.keys_label(),
+                label: binding.label.unwrap_or_default().to_string(),
+                event: binding.event.clone(),
+            })
+            .collect()
+    }
+
+    // Age of the data in the active view, live views are never stale.
+    pub fn data_age(&self) -> Option<Duration> {
+        match self.active_resource {
+            Resource::Listening { .. } => None,
+            _ => Some(self.fetched_at.elapsed()),
+        }
+    }
+}
+
+pub struct PulsarApp {
+    pub receiver: UnboundedReceiver<AppEvent>,
+    pub sender: UnboundedSender<AppEvent>,
+    pub client: Arc<Mutex<Pulsar<TokioExecutor>>>,
     pub token: Token,
     pub active_sub_handle: Option<tokio::sync::oneshot::Sender<()>>,
+    pub active_pause_handle: Option<watch::Sender<bool>>,
 }
 
-pub async fn update<'a>(
+pub async fn update(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     app: &mut App,
 ) -> anyhow::Result<()> {
+    let mut redraw = tokio::time::interval(REDRAW_INTERVAL);
+    redraw.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
     loop {
         terminal.draw(|f| draw::draw_new(f, app))?;
-        if let Ok(event) = app
-            .pulsar
-            .receiver
-            .recv_timeout(Duration::from_millis(100))
-        {
+        let filter_due = app.filter_due;
+        let event = tokio::select! {
+            event = app.pulsar.receiver.recv() => event,
+            _ = redraw.tick() => None,
+            _ = tokio::time::sleep_until(filter_due.unwrap_or_else(Instant::now).into()),
+                if filter_due.is_some() => None,
+        };
+        let event = match event {
+            Some(AppEvent::Key(key)) => {
+                let (view, layer) = (View::of(&app.active_resource), app.key_layer());
+                app.keymap
+                    .resolve(&key, &mut app.previous_key, view, layer)
+                    .map(AppEvent::Control)
+            }
+            event => event,
+        };
+        if let Some(event) = event {
             match event {
-                // XXX: Allow only a subset of events if search is enabled
+                // Resolved into a control event above
+                AppEvent::Key(_) => {}
+
+                // XXX: Input modal captures everything except its own controls
+                AppEvent::Control(control_event)
+                    if app.input_modal.is_some()
+                        && !matches!(
+                            control_event,
+                            ControlEvent::Enter
+                                | ControlEvent::Esc
+                                | ControlEvent::BackSpace
+                                | ControlEvent::ClearInput
+                                | ControlEvent::Terminate
+                        ) => {}
+
+                // XXX: Produce modal captures everything except its own controls
+                AppEvent::Control(control_event)
+                    if app.produce_modal.is_some()
+                        && !matches!(
+                            control_event,
+                            ControlEvent::Enter
+                                | ControlEvent::Esc
+                                | ControlEvent::BackSpace
+                                | ControlEvent::ClearInput
+                                | ControlEvent::CycleSide
+                                | ControlEvent::Terminate
+                        ) => {}
+
+                AppEvent::Input(KeyCode::Char(char)) if app.produce_modal.is_some() => {
+                    if let Some(modal) = app.produce_modal.as_mut() {
+                        modal.fields[modal.focused].push(char);
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::ClearInput) if app.produce_modal.is_some() => {
+                    if let Some(modal) = app.produce_modal.as_mut() {
+                        modal.fields[modal.focused].clear();
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::BackSpace) if app.produce_modal.is_some() => {
+                    if let Some(modal) = app.produce_modal.as_mut() {
+                        modal.fields[modal.focused].pop();
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::CycleSide) if app.produce_modal.is_some() => {
+                    if let Some(modal) = app.produce_modal.as_mut() {
+                        modal.focused = (modal.focused + 1) % modal.fields.len();
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::Esc) if app.produce_modal.is_some() => {
+                    app.produce_modal = None;
+                }
+
+                AppEvent::Control(ControlEvent::Enter) if app.produce_modal.is_some() => {
+                    if let Some(modal) = app.produce_modal.take() {
+                        submit_produce(app, modal).await;
+                    }
+                }
+
+                // XXX: Consume bench results capture everything until dismissed
+                AppEvent::Control(control_event)
+                    if app.consume_report.is_some()
+                        && !matches!(
+                            control_event,
+                            ControlEvent::Enter | ControlEvent::Esc | ControlEvent::Terminate
+                        ) => {}
+
+                AppEvent::Control(ControlEvent::Enter | ControlEvent::Esc)
+                    if app.consume_report.is_some() =>
+                {
+                    app.consume_report = None;
+                }
+
+                // XXX: Help overlay captures everything except scrolling until dismissed
                 AppEvent::Control(control_event)
-                    if matches!(app.resources.listening.panel, SelectedPanel::Search)
-                        && matches!(
+                    if app.help_scroll.is_some()
+                        && !matches!(
                             control_event,
-                            ControlEvent::Yank
-                                | ControlEvent::Back
-                                | ControlEvent::Up
+                            ControlEvent::Up
                                 | ControlEvent::Down
-                                | ControlEvent::Delete
-                                | ControlEvent::ResetSubscription(..)
+                                | ControlEvent::PageUp
+                                | ControlEvent::PageDown
+                                | ControlEvent::Top
+                                | ControlEvent::Bottom
+                                | ControlEvent::Enter
+                                | ControlEvent::Esc
+                                | ControlEvent::Help
+                                | ControlEvent::Terminate
+                        ) => {}
+
+                AppEvent::Control(ControlEvent::Enter | ControlEvent::Esc | ControlEvent::Help)
+                    if app.help_scroll.is_some() =>
+                {
+                    app.help_scroll = None;
+                }
+
+                AppEvent::Control(event) if app.help_scroll.is_some() => {
+                    let page = page_size(terminal) as u16;
+                    let last = draw::help_lines(&app.keymap)
+                        .len()
+                        .saturating_sub(page as usize) as u16;
+                    if let Some(scroll) = app.help_scroll.as_mut() {
+                        *scroll = match event {
+                            ControlEvent::Up => scroll.saturating_sub(1),
+                            ControlEvent::Down => scroll.saturating_add(1),
+                            ControlEvent::PageUp => scroll.saturating_sub(page),
+                            ControlEvent::PageDown => scroll.saturating_add(page),
+                            ControlEvent::Top => 0,
+                            _ => last,
+                        }
+                        .min(last);
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::Help) => {
+                    if app.confirmation_modal.is_none() {
+                        app.help_scroll = Some(0);
+                    }
+                }
+
+                // XXX: Action menu captures everything except its own controls, typed keys go to
+                // its search query so they are handled as raw input
+                AppEvent::Control(control_event)
+                    if app.action_menu.is_some()
+                        && !matches!(
+                            control_event,
+                            ControlEvent::Enter | ControlEvent::Esc | ControlEvent::Terminate
                         ) => {}
 
+                AppEvent::Input(KeyCode::Char(char))
+                    if app.action_menu.as_ref().is_some_and(|menu| {
+                        menu.shortcuts
+                            && menu
+                                .actions
+                                .iter()
+                                .any(|action| action.key == char.to_string())
+                    }) =>
+                {
+                    if let Some(menu) = app.action_menu.take() {
+                        if let Some(action) = menu
+                            .actions
+                            .into_iter()
+                            .find(|action| action.key == char.to_string())
+                        {
+                            let _ = app
+                                .pulsar
+                                .sender
+                                .send(AppEvent::Control(action.event));
+                        }
+                    }
+                }
+
+                AppEvent::Input(input) if app.action_menu.is_some() => {
+                    if let Some(menu) = app.action_menu.as_mut() {
+                        match input {
+                            KeyCode::Up => menu.cursor = menu.cursor.saturating_sub(1),
+                            KeyCode::Down => {
+                                menu.cursor =
+                                    (menu.cursor + 1).min(menu.matching().len().saturating_sub(1))
+                            }
+                            KeyCode::Char(char) => {
+                                menu.query.push(char);
+                                menu.cursor = 0;
+                            }
+                            KeyCode::Backspace => {
+                                menu.query.pop();
+                                menu.cursor = 0;
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::Esc) if app.action_menu.is_some() => {
+                    app.action_menu = None;
+                }
+
+                AppEvent::Control(ControlEvent::Enter) if app.action_menu.is_some() => {
+                    if let Some(menu) = app.action_menu.take() {
+                        if let Some(action) = menu.matching().get(menu.cursor) {
+                            let _ = app
+                                .pulsar
+                                .sender
+                                .send(AppEvent::Control(action.event.clone()));
+                        }
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::Actions) => {
+                    if app.confirmation_modal.is_none() {
+                        app.action_menu = Some(ActionMenu {
+                            title: "Actions",
+                            actions: app.available_actions(),
+                            query: String::new(),
+                            cursor: 0,
+                            shortcuts: false,
+                        });
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::ToggleBookmark) => {
+                    let bookmark = match &app.active_resource {
+                        Resource::Topics => app.resources.selected_topic().map(|topic| {
+                            topic
+                                .fqn
+                                .split_once("://")
+                                .map(|(_, path)| path.to_string())
+                                .unwrap_or(topic.fqn.clone())
+                        }),
+                        Resource::Subscriptions => app
+                            .resources
+                            .selected_subscription()
+                            .and_then(|subscription| app.resources.subscription_path(subscription))
+                            .map(|path| {
+                                format!(
+                                    "{}/{}/{}/{}",
+                                    path.tenant, path.namespace, path.topic, path.sub_name
+                                )
+                            }),
+                        _ => None,
+                    };
+
+                    if let Some(bookmark) = bookmark {
+                        toggle_bookmark(app, bookmark);
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::Bookmarks) => {
+                    if app.resources.topics.bookmarks.is_empty() {
+                        show_info_msg(app, "No bookmarks yet, add them with 'M'.");
+                    } else if app.confirmation_modal.is_none() {
+                        app.action_menu = Some(ActionMenu {
+                            title: "Bookmarks",
+                            actions: app
+                                .resources
+                                .topics
+                                .bookmarks
+                                .iter()
+                                .enumerate()
+                                .map(|(index, bookmark)| Action {
+                                    key: if index < 9 {
+                                        (index + 1).to_string()
+                                    } else {
+                                        String::new()
+                                    },
+                                    label: bookmark.clone(),
+                                    event: ControlEvent::OpenBookmark(bookmark.clone()),
+                                })
+                                .collect(),
+                            query: String::new(),
+                            cursor: 0,
+                            shortcuts: true,
+                        });
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::OpenBookmark(bookmark)) => {
+                    if let Err(err) = match ResourcePath::parse(&bookmark) {
+                        Ok(path) => open_path(app, &path).await,
+                        Err(err) => Err(err),
+                    } {
+                        show_error_msg(app, err.to_string());
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::Contexts) => {
+                    if app.contexts.len() < 2 {
+                        show_info_msg(
+                            app,
+                            "Only one cluster configured, add more under [contexts].",
+                        );
+                    } else if app.confirmation_modal.is_none() {
+                        app.action_menu = Some(ActionMenu {
+                            title: "Contexts",
+                            actions: app
+                                .contexts
+                                .iter()
+                                .enumerate()
+                                .map(|(index, (name, context))| Action {
+                                    key: if index < 9 {
+                                        (index + 1).to_string()
+                                    } else {
+                                        String::new()
+                                    },
+                                    label: format!(
+                                        "{}{} ({})",
+                                        if *name == app.context { "* " } else { "" },
+                                        name,
+                                        context.pulsar_url
+                                    ),
+                                    event: ControlEvent::SwitchContext(name.clone()),
+                                })
+                                .collect(),
+                            query: String::new(),
+                            cursor: app
+                                .contexts
+                                .iter()
+                                .position(|(name, _)| *name == app.context)
+                                .unwrap_or(0),
+                            shortcuts: true,
+                        });
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::SwitchContext(name)) => {
+                    match switch_context(app, &name).await {
+                        Ok(()) => show_info_msg(app, &format!("Switched to '{}'.", name)),
+                        Err(err) => show_error_msg(app, err.to_string()),
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::Palette) => {
+                    if app.confirmation_modal.is_none() {
+                        app.input_modal = Some(InputModal {
+                            title: format!("Command ({})", palette::USAGE),
+                            input: String::new(),
+                            command: InputCommand::Palette,
+                        });
+                    }
+                }
+
+                AppEvent::Palette { input, resumed } => run_palette(app, &input, resumed).await,
+
+                AppEvent::NamespaceStats { tenant, namespace, stats } => {
+                    if app.resources.selected_tenant_name() == Some(tenant.as_str()) {
+                        app.resources
+                            .namespaces
+                            .stats
+                            .insert(namespace, stats);
+                    }
+                }
+
+                AppEvent::OverviewStats {
+                    tenant,
+                    namespace,
+                    topics,
+                    stats,
+                } => {
+                    let overview = &mut app.resources.overview;
+                    if overview.tenant == tenant {
+                        // Drops the topics deleted since a refresh
+                        let prefix = format!("{}/", namespace);
+                        overview
+                            .topic_stats
+                            .retain(|topic, _| !topic.starts_with(&prefix));
+                        overview.namespace_stats.insert(
+                            namespace.clone(),
+                            NamespaceStats {
+                                topics,
+                                backlog: stats.values().map(|stats| stats.backlog).sum(),
+                            },
+                        );
+                        overview.topic_stats.extend(
+                            stats
+                                .into_iter()
+                                .map(|(topic, stats)| (format!("{}/{}", namespace, topic), stats)),
+                        );
+                    }
+                }
+
+                // Refreshes of a view left in the meantime are dropped
+                AppEvent::ViewRefreshed { path, view } if path == view_path(app) => match view {
+                    Ok(view) => show_refreshed_view(app, view),
+                    Err(err) => show_error_msg(app, format!("Failed to refresh :[ {:?}", err)),
+                },
+                AppEvent::ViewRefreshed { .. } => {}
+
+                AppEvent::TopicStats { namespace, stats } => {
+                    // Stats of a namespace left in the meantime are dropped
+                    let selected = app
+                        .resources
+                        .selected_tenant_name()
+                        .zip(app.resources.selected_namespace_name())
+                        .map(|(tenant, selected)| format!("{}/{}", tenant, selected));
+                    if selected.as_ref() == Some(&namespace) {
+                        app.resources.topics.stats = stats;
+                        app.resources.topics.resort();
+                    }
+                }
+
+                AppEvent::Input(KeyCode::Char(char)) if app.input_modal.is_some() => {
+                    if let Some(modal) = app.input_modal.as_mut() {
+                        modal.input.push(char);
+                    }
+                    schedule_filter(app);
+                }
+
+                AppEvent::Control(ControlEvent::ClearInput) if app.input_modal.is_some() => {
+                    if let Some(modal) = app.input_modal.as_mut() {
+                        modal.input.clear();
+                    }
+                    schedule_filter(app);
+                }
+
+                AppEvent::Control(ControlEvent::BackSpace) if app.input_modal.is_some() => {
+                    if let Some(modal) = app.input_modal.as_mut() {
+                        modal.input.pop();
+                    }
+                    schedule_filter(app);
+                }
+
+                AppEvent::Control(ControlEvent::Esc) if app.input_modal.is_some() => {
+                    if let Some(InputCommand::FilterList { .. }) =
+                        app.input_modal.take().map(|modal| modal.command)
+                    {
+                        app.list_filter = None;
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::Enter) if app.input_modal.is_some() => {
+                    if let Some(modal) = app.input_modal.take() {
+                        submit_input(app, modal).await;
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::SetProperty) => {
+                    if let (Resource::Subscriptions, Some(subscription)) =
+                        (&app.active_resource, app.resources.selected_subscription())
+                    {
+                        app.input_modal = Some(InputModal {
+                            title: format!("Set '{}' property (key=value)", subscription.name),
+                            input: String::new(),
+                            command: InputCommand::SetSubscriptionProperty {
+                                tenant: app
+                                    .resources
+                                    .selected_tenant_name()
+                                    .expect("tenant must be set")
+                                    .to_string(),
+                                namespace: app
+                                    .resources
+                                    .selected_namespace_name()
+                                    .expect("namespace must be set")
+                                    .to_string(),
+                                topic: app
+                                    .resources
+                                    .selected_topic_name()
+                                    .expect("namespace must be set")
+                                    .to_string(),
+                                sub_name: subscription.name.clone(),
+                                properties: subscription.properties.clone(),
+                                cfg: app.pulsar_admin_cfg.clone(),
+                            },
+                        })
+                    }
+                }
+
+                // <c-d>/<c-u> scroll the focused preview by half a page, vim style
+                AppEvent::Control(
+                    event @ (ControlEvent::HalfPageDown | ControlEvent::HalfPageUp),
+                ) => {
+                    let half_page = (page_size(terminal) / 2).max(1) as u16;
+                    if let (Resource::Listening { .. }, SelectedPanel::Right { scroll_offset }) =
+                        (&app.active_resource, &mut app.resources.listening.panel)
+                    {
+                        *scroll_offset = match event {
+                            ControlEvent::HalfPageDown => scroll_offset.saturating_add(half_page),
+                            _ => scroll_offset.saturating_sub(half_page),
+                        }
+                    }
+                }
+
                 AppEvent::Control(ControlEvent::ClearInput) => {
                     if matches!(app.resources.listening.panel, SelectedPanel::Search) {
                         app.resources.listening.search = Some(String::new());
+                        schedule_filter(app);
                     }
                 }
                 AppEvent::Input(input) => {
                     if matches!(app.resources.listening.panel, SelectedPanel::Search) {
                         if let Resource::Listening { .. } = &app.active_resource {
                             let char = match input {
-                                KeyCode::Char(char) if char != '/' => Some(char),
+                                KeyCode::Char(char) => Some(char),
                                 _ => None,
                             };
 
@@ -416,7 +2511,7 @@ pub async fn update<'a>(
                                     .map(|current_search| format!("{}{}", current_search, char));
                             }
 
-                            app.resources.listening.filter_messages();
+                            schedule_filter(app);
                         }
                     }
                 }
@@ -426,7 +2521,8 @@ pub async fn update<'a>(
                         &mut app.active_resource,
                         app.resources.selected_subscription(),
                     ) {
-                        app.confirmation_modal = Some(ConfirmationModal {
+                        let name = subscription.name.clone();
+                        let modal = ConfirmationModal {
                             message: format!("Skip all '{}' messages?", subscription.name),
                             command: ConfirmedCommand::SkipAllMessages {
                                 tenant: app
@@ -447,11 +2543,16 @@ pub async fn update<'a>(
                                 sub_name: subscription.name.clone(),
                                 cfg: app.pulsar_admin_cfg.clone(),
                             },
-                        })
+                        };
+                        request_confirmation(app, modal, name);
                     }
                 }
 
                 AppEvent::Command(ConfirmedCommand::CloseInfoMessage) => app.info_to_show = None,
+                AppEvent::Command(ConfirmedCommand::RecoverJournal { checkpoint }) => {
+                    app.confirmation_modal = None;
+                    browse_capture(app, checkpoint, "recovered");
+                }
                 AppEvent::Command(ConfirmedCommand::SkipAllMessages {
                     tenant,
                     namespace,
@@ -466,8 +2567,11 @@ pub async fn update<'a>(
 
                     app.confirmation_modal = None;
 
-                    if let Err(err) = result {
-                        app.info_to_show = Some(InfoToShow::error(err.to_string()))
+                    match result {
+                        Ok(_) => app
+                            .session
+                            .clear_backlog(&tenant, &namespace, &topic, &sub_name),
+                        Err(err) => app.info_to_show = Some(InfoToShow::error(err.to_string())),
                     }
 
                     refresh_subscriptions(app).await;
@@ -486,6 +2590,8 @@ pub async fn update<'a>(
                     .await
                     {
                         Ok(_) => {
+                            app.session
+                                .unsubscribe(&tenant, &namespace, &topic, &sub_name);
                             refresh_subscriptions(app).await;
                             show_info_msg(app, "Subscription deleted.");
                         }
@@ -499,6 +2605,195 @@ pub async fn update<'a>(
 
                     app.confirmation_modal = None;
                 }
+                AppEvent::Command(ConfirmedCommand::DeleteSubscriptions { subscriptions, cfg }) => {
+                    let mut results = vec![];
+                    for path in subscriptions {
+                        let result = pulsar_admin::delete_subscription(
+                            &path.tenant,
+                            &path.namespace,
+                            &path.topic,
+                            &path.sub_name,
+                            &cfg,
+                        )
+                        .await;
+                        if result.is_ok() {
+                            app.session.unsubscribe(
+                                &path.tenant,
+                                &path.namespace,
+                                &path.topic,
+                                &path.sub_name,
+                            );
+                        }
+                        results.push((path, result));
+                    }
+
+                    app.confirmation_modal = None;
+                    app.resources.subscriptions.marked.clear();
+                    refresh_subscriptions(app).await;
+                    show_bulk_summary(app, "Deleted", results);
+                }
+                AppEvent::Command(ConfirmedCommand::SeekSubscriptions {
+                    subscriptions,
+                    target,
+                    cfg,
+                }) => {
+                    let mut results = vec![];
+                    for path in subscriptions {
+                        let result = pulsar_admin::seek_subscription(
+                            &path.tenant,
+                            &path.namespace,
+                            &path.topic,
+                            &path.sub_name,
+                            &cfg,
+                            target.timestamp_millis(),
+                        )
+                        .await;
+                        if result.is_ok() {
+                            app.session.reset_cursor(&path, &target);
+                        }
+                        results.push((path, result));
+                    }
+
+                    app.confirmation_modal = None;
+                    app.resources.subscriptions.marked.clear();
+                    // Namespace wide seeks are started from the Topics view, which stays put
+                    if let Resource::Subscriptions = &app.active_resource {
+                        refresh_subscriptions(app).await;
+                    }
+                    show_bulk_summary(app, "Seeked", results);
+                }
+                AppEvent::Command(ConfirmedCommand::SetSubscriptionPaused {
+                    subscription,
+                    paused,
+                    cfg,
+                }) => {
+                    let result = pulsar_admin::set_subscription_paused(
+                        &subscription.tenant,
+                        &subscription.namespace,
+                        &subscription.topic,
+                        &subscription.sub_name,
+                        paused,
+                        &cfg,
+                    )
+                    .await;
+                    app.confirmation_modal = None;
+
+                    if result.is_ok() {
+                        app.session.set_paused(&subscription, paused);
+                    }
+
+                    match result {
+                        Ok(_) if paused => show_info_msg(
+                            app,
+                            &format!("Paused delivery on '{}'.", subscription.sub_name),
+                        ),
+                        Ok(_) => show_info_msg(
+                            app,
+                            &format!("Resumed delivery on '{}'.", subscription.sub_name),
+                        ),
+                        Err(err) => show_error_msg(app, err.to_string()),
+                    }
+                }
+                AppEvent::Command(ConfirmedCommand::MigrateTopic { plan, cfg }) => {
+                    app.confirmation_modal = None;
+                    app.session.migrate(&plan);
+                    show_info_msg(app, &format!("Migrating '{}'...", plan.source));
+
+                    let pulsar = app.pulsar.client.clone();
+                    let sender = app.pulsar.sender.clone();
+                    app.tasks.spawn("migration", |shutdown| {
+                        migration::migrate(plan, cfg, pulsar, sender, shutdown)
+                    });
+                }
+                AppEvent::Command(ConfirmedCommand::RecoverTopic {
+                    tenant,
+                    namespace,
+                    topic,
+                    cfg,
+                }) => {
+                    app.confirmation_modal = None;
+                    match recovery::recreate(&tenant, &namespace, &topic, &cfg).await {
+                        Ok(applied) => {
+                            app.session.recreate_topic(
+                                &tenant,
+                                &namespace,
+                                &topic.name,
+                                topic.partitions,
+                            );
+                            app.cache.clear();
+                            if let Ok(topics) = app.cache.topics(&tenant, &namespace, &cfg).await {
+                                app.resources.topics.topics = topics;
+                                app.resources.topics.sort();
+                            }
+                            show_info_msg(
+                                app,
+                                &if applied.is_empty() {
+                                    format!("Recreated '{}'.", topic.name)
+                                } else {
+                                    format!(
+                                        "Recreated '{}' with {}.",
+                                        topic.name,
+                                        applied.join(", ")
+                                    )
+                                },
+                            )
+                        }
+                        Err(err) => show_error_msg(app, err.to_string()),
+                    }
+                }
+                AppEvent::MigrationProgress(progress) => match progress {
+                    Ok(message) => {
+                        // The target topic is created along the way
+                        app.cache.clear();
+                        show_info_msg(app, &message)
+                    }
+                    Err(err) => show_error_msg(app, format!("Migration failed: {}", err)),
+                },
+                AppEvent::Command(ConfirmedCommand::Bench { topic_fqn, spec }) => {
+                    app.confirmation_modal = None;
+                    app.session.bench(&topic_fqn, &spec);
+                    show_info_msg(app, &format!("Publishing {} to '{}'...", spec, topic_fqn));
+
+                    let pulsar = app.pulsar.client.clone();
+                    let sender = app.pulsar.sender.clone();
+                    app.tasks.spawn("bench", |shutdown| {
+                        bench::publish(topic_fqn, spec, pulsar, sender, shutdown)
+                    });
+                }
+                AppEvent::StatsExportFailed(err) | AppEvent::JournalFailed(err) => {
+                    show_error_msg(app, err.to_string())
+                }
+
+                AppEvent::TokenRefreshed { context, token } => match token {
+                    Ok(token) if context == app.context => {
+                        app.pulsar_admin_cfg.bearer_access_token = Some(token.access_token.clone());
+                        if let Err(err) = rebuild_token_cmd_client(app, &token).await {
+                            show_error_msg(app, err.to_string());
+                        }
+                        app.pulsar.token = token;
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        show_error_msg(app, format!("Failed to refresh the token: {}", err))
+                    }
+                },
+                AppEvent::BenchProgress(progress) => show_info_msg(app, &progress),
+                AppEvent::ProbeSample(sample) => match (sample, app.probe.as_mut()) {
+                    (Ok(sample), Some(probe)) => probe.record(sample),
+                    (Ok(_), None) => {}
+                    (Err(err), _) => {
+                        app.probe = None;
+                        show_error_msg(app, format!("Latency probe failed: {}", err))
+                    }
+                },
+                AppEvent::ConsumeBenchFinished(report) => match report {
+                    Ok(report) => app.consume_report = Some(report),
+                    Err(err) => show_error_msg(app, format!("Consume bench failed: {}", err)),
+                },
+                AppEvent::BenchFinished(report) => match report {
+                    Ok(report) => show_info_msg(app, &report.to_string()),
+                    Err(err) => show_error_msg(app, format!("Bench failed: {}", err)),
+                },
                 AppEvent::Command(ConfirmedCommand::SeekSubscription {
                     tenant,
                     namespace,
@@ -513,8 +2808,17 @@ pub async fn update<'a>(
                     .await;
                     app.confirmation_modal = None;
 
-                    if let Err(err) = result {
-                        app.info_to_show = Some(InfoToShow::error(err.to_string()))
+                    match result {
+                        Ok(_) => app.session.reset_cursor(
+                            &SubscriptionPath {
+                                tenant,
+                                namespace,
+                                topic,
+                                sub_name,
+                            },
+                            &SeekTarget::Relative(time_delta),
+                        ),
+                        Err(err) => app.info_to_show = Some(InfoToShow::error(err.to_string())),
                     }
 
                     refresh_subscriptions(app).await;
@@ -530,6 +2834,39 @@ pub async fn update<'a>(
                 AppEvent::Control(ControlEvent::Refuse) => {
                     app.confirmation_modal = None;
                 }
+                AppEvent::Control(ControlEvent::NextMatch) => {
+                    if let Resource::Listening { .. } = &app.active_resource {
+                        app.resources.listening.jump_to_preview_match(1);
+                    }
+                }
+                AppEvent::Control(ControlEvent::PreviousMatch) => {
+                    if let Resource::Listening { .. } = &app.active_resource {
+                        app.resources.listening.jump_to_preview_match(-1);
+                    }
+                }
+                AppEvent::Control(ControlEvent::Search)
+                    if !matches!(
+                        app.active_resource,
+                        Resource::Listening { .. }
+                            | Resource::Consumers
+                            | Resource::Comparison
+                            | Resource::Overview
+                    ) =>
+                {
+                    let view = View::of(&app.active_resource);
+                    app.input_modal = Some(InputModal {
+                        title: format!(
+                            "Filter {} (prefix with ~ for regex)",
+                            format!("{:?}", view).to_lowercase()
+                        ),
+                        input: app
+                            .list_filter
+                            .as_ref()
+                            .map(|filter| filter.query.clone())
+                            .unwrap_or_default(),
+                        command: InputCommand::FilterList { view },
+                    });
+                }
                 AppEvent::Control(ControlEvent::Search) => {
                     if let Resource::Listening { .. } = &mut app.active_resource {
                         match &app.resources.listening.panel {
@@ -540,13 +2877,19 @@ pub async fn update<'a>(
                                     app.resources.listening.search = Some(String::new());
                                 }
                             },
-                            SelectedPanel::Right { .. } => match &app.resources.listening.search {
-                                Some(_) => app.resources.listening.panel = SelectedPanel::Search,
-                                None => {
-                                    app.resources.listening.panel = SelectedPanel::Search;
-                                    app.resources.listening.search = Some(String::new());
-                                }
-                            },
+                            SelectedPanel::Right { .. } => {
+                                app.input_modal = Some(InputModal {
+                                    title: "Search preview (prefix with ~ for regex)".to_string(),
+                                    input: app
+                                        .resources
+                                        .listening
+                                        .preview_search
+                                        .as_ref()
+                                        .map(|search| search.query.clone())
+                                        .unwrap_or_default(),
+                                    command: InputCommand::SearchPreview,
+                                });
+                            }
                             SelectedPanel::Search => match &app.resources.listening.search {
                                 Some(_) => {
                                     app.resources.listening.panel = SelectedPanel::Left;
@@ -560,33 +2903,91 @@ pub async fn update<'a>(
                     }
                 }
                 AppEvent::Control(ControlEvent::Delete) => {
-                    if let Resource::Subscriptions = &mut app.active_resource {
-                        if let Some(subscription) = app.resources.selected_subscription() {
-                            app.confirmation_modal = Some(ConfirmationModal {
-                                message: format!("Delete '{}' subscription?", subscription.name),
+                    if let (Resource::Subscriptions, false) = (
+                        &app.active_resource,
+                        app.resources.subscriptions.marked.is_empty(),
+                    ) {
+                        let subscriptions = app
+                            .resources
+                            .subscriptions
+                            .marked
+                            .iter()
+                            .cloned()
+                            .collect_vec();
+                        let name = format!("{} subscriptions", subscriptions.len());
+                        let modal = ConfirmationModal {
+                            message: format!(
+                                "Delete {} marked subscriptions?",
+                                subscriptions.len()
+                            ),
+                            command: ConfirmedCommand::DeleteSubscriptions {
+                                subscriptions,
+                                cfg: app.pulsar_admin_cfg.clone(),
+                            },
+                        };
+                        request_confirmation(app, modal, name);
+                    } else if let Resource::Subscriptions = &mut app.active_resource {
+                        if let Some(subscription) = app.resources.selected_subscription().cloned() {
+                            let tenant = app
+                                .resources
+                                .selected_tenant_name()
+                                .expect("tenant must be set")
+                                .to_string();
+                            let namespace = app
+                                .resources
+                                .selected_namespace_name()
+                                .expect("namespace must be set")
+                                .to_string();
+                            let topic = app
+                                .resources
+                                .selected_topic_name()
+                                .expect("namespace must be set")
+                                .to_string();
+
+                            // The listed count may be stale, consumers get force disconnected
+                            let consumers = pulsar_admin::fetch_consumers(
+                                &tenant,
+                                &namespace,
+                                &topic,
+                                &subscription.name,
+                                &app.pulsar_admin_cfg,
+                            )
+                            .await
+                            .map(|consumers| consumers.len())
+                            .unwrap_or(subscription.consumer_count);
+                            let warning = match consumers {
+                                0 => String::new(),
+                                1 => " 1 consumer is currently connected and will be disconnected."
+                                    .to_string(),
+                                count => format!(
+                                    " {} consumers are currently connected and will be disconnected.",
+                                    count
+                                ),
+                            };
+
+                            let modal = ConfirmationModal {
+                                message: format!(
+                                    "Delete '{}' subscription?{}",
+                                    subscription.name, warning
+                                ),
                                 command: ConfirmedCommand::DeleteSubscription {
-                                    tenant: app
-                                        .resources
-                                        .selected_tenant_name()
-                                        .expect("tenant must be set")
-                                        .to_string(),
-                                    namespace: app
-                                        .resources
-                                        .selected_namespace_name()
-                                        .expect("namespace must be set")
-                                        .to_string(),
-                                    topic: app
-                                        .resources
-                                        .selected_topic_name()
-                                        .expect("namespace must be set")
-                                        .to_string(),
+                                    tenant,
+                                    namespace,
+                                    topic,
                                     sub_name: subscription.name.clone(),
                                     cfg: app.pulsar_admin_cfg.clone(),
                                 },
-                            })
+                            };
+                            request_confirmation(app, modal, subscription.name);
                         }
                     }
                 }
+                AppEvent::Control(ControlEvent::ResetSubscription(_))
+                    if matches!(app.active_resource, Resource::Listening { .. })
+                        && app.resources.listening.browse_only =>
+                {
+                    show_info_msg(app, "This capture is browse-only.");
+                }
                 AppEvent::Control(ControlEvent::ResetSubscription(length)) => {
                     if let Resource::Listening { sub_name } = &app.active_resource {
                         let length = match length {
@@ -623,7 +3024,43 @@ pub async fn update<'a>(
                             show_info_msg(app, "Seeked successfully.");
                         };
                     }
-                    if let Resource::Subscriptions = &app.active_resource {
+                    if let (Resource::Subscriptions, false) = (
+                        &app.active_resource,
+                        app.resources.subscriptions.marked.is_empty(),
+                    ) {
+                        let time_delta = match length {
+                            crate::ResetLength::OneHour => {
+                                TimeDelta::try_hours(1).expect("Expecting hours")
+                            }
+                            crate::ResetLength::TwentyFourHours => {
+                                TimeDelta::try_hours(24).expect("Expecting hours")
+                            }
+                            crate::ResetLength::Week => {
+                                TimeDelta::try_days(7).expect("Expecting days")
+                            }
+                        };
+                        let subscriptions = app
+                            .resources
+                            .subscriptions
+                            .marked
+                            .iter()
+                            .cloned()
+                            .collect_vec();
+                        let target = SeekTarget::Relative(time_delta);
+
+                        app.confirmation_modal = Some(ConfirmationModal {
+                            message: format!(
+                                "Seek {} marked subscriptions {}?",
+                                subscriptions.len(),
+                                target
+                            ),
+                            command: ConfirmedCommand::SeekSubscriptions {
+                                subscriptions,
+                                target,
+                                cfg: app.pulsar_admin_cfg.clone(),
+                            },
+                        })
+                    } else if let Resource::Subscriptions = &app.active_resource {
                         if let Some(subscription) = app.resources.selected_subscription() {
                             let (time_delta, time_str) = match length {
                                 crate::ResetLength::OneHour => {
@@ -682,373 +3119,2406 @@ pub async fn update<'a>(
                     }
                 }
                 AppEvent::Control(ControlEvent::Yank) => {
+                    if let (Resource::Listening { .. }, Some(_)) =
+                        (&app.active_resource, app.resources.selected_message())
+                    {
+                        let action = |key: &str, label: &str, event| Action {
+                            key: key.to_string(),
+                            label: label.to_string(),
+                            event,
+                        };
+                        app.action_menu = Some(ActionMenu {
+                            title: "Copy",
+                            actions: vec![
+                                action("b", "body", ControlEvent::YankPart(YankPart::Body)),
+                                action(
+                                    "p",
+                                    "properties",
+                                    ControlEvent::YankPart(YankPart::Properties),
+                                ),
+                                action("v", "property value", ControlEvent::YankProperty),
+                                action(
+                                    "i",
+                                    "message id",
+                                    ControlEvent::YankPart(YankPart::MessageId),
+                                ),
+                                action(
+                                    "a",
+                                    "everything (JSON with metadata)",
+                                    ControlEvent::YankPart(YankPart::Message),
+                                ),
+                                action("c", "pulsar-client command", ControlEvent::YankCommand),
+                            ],
+                            query: String::new(),
+                            cursor: 0,
+                            shortcuts: true,
+                        });
+                    }
+                }
+                AppEvent::Control(ControlEvent::YankPart(part)) => {
+                    if let Resource::Listening { .. } = &app.active_resource {
+                        if let Some(message) = app.resources.selected_message() {
+                            let content = match part {
+                                YankPart::Body => Ok(message.body.clone()),
+                                YankPart::Properties => Ok(message
+                                    .property_pairs()
+                                    .iter()
+                                    .map(|(key, value)| format!("{}={}", key, value))
+                                    .join("\n")),
+                                YankPart::MessageId => Ok(message.meta.message_id.clone()),
+                                YankPart::Message => message_export::to_pretty_json(
+                                    app.resources
+                                        .selected_topic_name()
+                                        .unwrap_or_default(),
+                                    message,
+                                ),
+                            };
+
+                            match content.and_then(copy_to_clipboard) {
+                                Ok(_) => show_info_msg(
+                                    app,
+                                    match part {
+                                        YankPart::Body => "Message body copied to clipboard.",
+                                        YankPart::Properties => "Properties copied to clipboard.",
+                                        YankPart::MessageId => "Message id copied to clipboard.",
+                                        YankPart::Message => "Message copied to clipboard.",
+                                    },
+                                ),
+                                Err(err) => show_error_msg(app, err.to_string()),
+                            }
+                        }
+                    }
+                }
+                AppEvent::Control(ControlEvent::YankCommand) => {
                     if let Resource::Listening { .. } = &app.active_resource {
-                        if let Some(sub_message) = app.resources.selected_message() {
-                            let content = &sub_message.body;
-                            let res = ClipboardContext::new()
-                                .map_err(|_| anyhow!("Failed to get the clipboard."))
-                                .and_then(|mut ctx| {
-                                    ctx.set_contents(content.to_owned())
-                                        .map_err(|_| anyhow!("Failed to copy to clipboard."))
+                        if let Some(message) = app.resources.selected_message() {
+                            let topic_fqn = app
+                                .resources
+                                .selected_topic()
+                                .map(|topic| topic.fqn.clone())
+                                .unwrap_or_default();
+                            let command = String::from_utf8(message.raw.clone())
+                                .map_err(|_| {
+                                    anyhow!("Binary payloads can't be passed to pulsar-client")
+                                })
+                                .map(|body| {
+                                    ProducedMessage {
+                                        body,
+                                        key: message.meta.key.clone(),
+                                        properties: message
+                                            .property_pairs()
+                                            .into_iter()
+                                            .map(|(key, value)| {
+                                                (key.to_string(), value.to_string())
+                                            })
+                                            .collect(),
+                                    }
+                                    .pulsar_client_command(&topic_fqn)
                                 });
 
-                            match res {
-                                Ok(_) => show_info_msg(app, "Message copied to clipboard."),
+                            match command.and_then(copy_to_clipboard) {
+                                Ok(_) => {
+                                    show_info_msg(app, "pulsar-client command copied to clipboard.")
+                                }
                                 Err(err) => show_error_msg(app, err.to_string()),
                             }
                         }
                     }
                 }
+                AppEvent::Control(ControlEvent::YankProperty) => {
+                    if let Resource::Listening { .. } = &app.active_resource {
+                        match app.resources.selected_message() {
+                            Some(message) if !message.properties.is_empty() => {
+                                app.input_modal = Some(InputModal {
+                                    title: format!(
+                                        "Copy property value ({})",
+                                        message
+                                            .property_pairs()
+                                            .iter()
+                                            .map(|(key, _)| key)
+                                            .join(", ")
+                                    ),
+                                    input: String::new(),
+                                    command: InputCommand::YankProperty,
+                                })
+                            }
+                            Some(_) => show_info_msg(app, "Message has no properties."),
+                            None => {}
+                        }
+                    }
+                }
                 AppEvent::Control(ControlEvent::Subscribe) => {
                     if let Resource::Topics = &app.active_resource {
                         if let Some(topic) = app.resources.selected_topic().cloned() {
-                            let sub_name = format!("lgm_subscription_{}", Uuid::new_v4());
-                            app.active_resource =
-                                Resource::Listening { sub_name: sub_name.clone() };
-                            app.resources.listening.cursor = None;
-                            app.resources.listening.messages = vec![];
-                            app.resources.listening.filtered_messages = vec![];
-                            app.resources.listening.search = None;
-                            let new_pulsar = app.pulsar.client.clone();
-                            let new_sender = app.pulsar.sender.clone();
-                            let (tx, rx) = oneshot::channel::<()>();
-                            app.pulsar.active_sub_handle = Some(tx);
-                            let _sub_handle = tokio::task::spawn(async move {
-                                pulsar_listener::listen_to_topic(
-                                    sub_name,
-                                    topic.fqn.clone(),
-                                    new_sender,
-                                    new_pulsar,
-                                    rx,
-                                )
-                                .await
-                            });
+                            ask_start_position(app, topic);
                         }
                     }
                 }
 
-                AppEvent::Control(ControlEvent::Up) => {
-                    app.confirmation_modal = None;
-                    if let SelectedPanel::Right { scroll_offset } =
-                        &mut app.resources.listening.panel
-                    {
-                        *scroll_offset = scroll_offset.saturating_sub(1)
-                    } else {
-                        app.resources.cursor_up(&app.active_resource)
+                AppEvent::Control(ControlEvent::SeekTo)
+                    if matches!(app.active_resource, Resource::Topics) =>
+                {
+                    let namespace = app
+                        .resources
+                        .selected_namespace_name()
+                        .expect("namespace must be set")
+                        .to_string();
+                    app.input_modal = Some(InputModal {
+                        title: format!(
+                            "Seek subscriptions of '{}' matching (e.g. svc-* 3h, *-audit 2024-05-01 14:30)",
+                            namespace
+                        ),
+                        input: String::new(),
+                        command: InputCommand::SeekNamespace {
+                            tenant: app
+                                .resources
+                                .selected_tenant_name()
+                                .expect("tenant must be set")
+                                .to_string(),
+                            namespace,
+                            topics: app
+                                .resources
+                                .topics
+                                .topics
+                                .iter()
+                                .map(|topic| topic.name.clone())
+                                .collect(),
+                        },
+                    })
+                }
+
+                AppEvent::Control(ControlEvent::SeekTo) => {
+                    if let Resource::Subscriptions = &app.active_resource {
+                        let subscriptions = if app.resources.subscriptions.marked.is_empty() {
+                            app.resources
+                                .selected_subscription()
+                                .and_then(|subscription| {
+                                    app.resources.subscription_path(subscription)
+                                })
+                                .into_iter()
+                                .collect_vec()
+                        } else {
+                            app.resources
+                                .subscriptions
+                                .marked
+                                .iter()
+                                .cloned()
+                                .collect_vec()
+                        };
+
+                        if !subscriptions.is_empty() {
+                            app.input_modal = Some(InputModal {
+                                title: format!(
+                                    "Seek {} subscriptions to (e.g. 3h, 2d, 2024-05-01 14:30)",
+                                    subscriptions.len()
+                                ),
+                                input: String::new(),
+                                command: InputCommand::SeekSubscriptions { subscriptions },
+                            })
+                        }
                     }
                 }
 
-                AppEvent::Control(ControlEvent::Down) => {
-                    app.confirmation_modal = None;
-                    if let SelectedPanel::Right { scroll_offset } =
-                        &mut app.resources.listening.panel
+                AppEvent::Control(ControlEvent::Produce) => {
+                    if let (Resource::Topics, Some(topic)) =
+                        (&app.active_resource, app.resources.selected_topic())
                     {
-                        *scroll_offset = scroll_offset.saturating_add(1)
-                    } else {
-                        app.resources.cursor_down(&app.active_resource)
+                        app.produce_modal = Some(ProduceModal {
+                            topic: topic.clone(),
+                            fields: Default::default(),
+                            focused: 0,
+                        });
                     }
                 }
 
-                AppEvent::Control(ControlEvent::BackSpace) => {
-                    if let Resource::Listening { .. } = &app.active_resource {
-                        if matches!(app.resources.listening.panel, SelectedPanel::Search) {
-                            app.resources.listening.search = match &app.resources.listening.search {
-                                Some(current_search) => {
-                                    let len = current_search.len();
-                                    if len > 0 {
-                                        Some(current_search[0..len - 1].to_owned())
-                                    } else {
-                                        Some("".to_string())
-                                    }
-                                }
-                                None => None,
-                            };
-                            app.resources.listening.filter_messages();
-                        }
+                AppEvent::Control(ControlEvent::ToggleProbe) => match app.probe.take() {
+                    Some(probe) => {
+                        probe.stop();
+                        show_info_msg(app, "Stopped the latency probe.");
                     }
-                }
+                    None => {
+                        if let (Resource::Namespaces | Resource::Topics, Some(namespace)) = (
+                            &app.active_resource,
+                            app.resources.selected_namespace_name(),
+                        ) {
+                            let tenant = app
+                                .resources
+                                .selected_tenant_name()
+                                .expect("tenant must be set");
+                            match ensure_probe_topic(tenant, namespace, &app.pulsar_admin_cfg).await
+                            {
+                                Ok(topic_fqn) => {
+                                    let (probe, stopped) = LatencyProbe::new(topic_fqn.clone());
+                                    app.probe = Some(probe);
 
-                AppEvent::Control(ControlEvent::Back | ControlEvent::Esc) => {
-                    if app.confirmation_modal.is_some() {
-                        app.confirmation_modal = None;
-                    } else {
-                        match &app.active_resource {
-                            Resource::Tenants { .. } => {}
-                            Resource::Namespaces { .. } => {
-                                let tenants =
-                                    pulsar_admin::fetch_tenants(&app.pulsar_admin_cfg).await;
-                                match tenants {
-                                    Ok(tenants) => {
-                                        app.resources.tenants.tenants = tenants;
-                                        app.resources
-                                            .tenants
-                                            .tenants
-                                            .sort_by(|a, b| a.name.cmp(&b.name));
-                                        app.active_resource = Resource::Tenants;
-                                    }
-                                    Err(err) => {
-                                        show_error_msg(
-                                            app,
-                                            format!("Failed to fetch tenants :[ {:?}", err),
-                                        );
-                                    }
+                                    let pulsar = app.pulsar.client.clone();
+                                    let sender = app.pulsar.sender.clone();
+                                    app.tasks.spawn("latency probe", move |shutdown| {
+                                        probe::run(topic_fqn, pulsar, sender, stopped, shutdown)
+                                    });
                                 }
+                                Err(err) => show_error_msg(app, err.to_string()),
                             }
-                            Resource::Topics => {
-                                let namespaces = pulsar_admin::fetch_namespaces(
-                                    &app.resources.selected_tenant().unwrap().name,
-                                    &app.pulsar_admin_cfg,
-                                )
-                                .await;
+                        }
+                    }
+                },
 
-                                match namespaces {
-                                    Ok(namespaces) => {
-                                        app.resources.namespaces.namespaces = namespaces;
-                                        app.resources
-                                            .namespaces
-                                            .namespaces
-                                            .sort_by(|a, b| a.name.cmp(&b.name));
-                                        app.active_resource = Resource::Namespaces;
-                                    }
-                                    Err(err) => {
-                                        show_error_msg(
-                                            app,
-                                            format!("Failed to fetch namespaces :[ {:?}", err),
-                                        );
-                                    }
-                                }
-                            }
+                AppEvent::Control(ControlEvent::ConsumeBench) => {
+                    if let (Resource::Topics, Some(topic)) =
+                        (&app.active_resource, app.resources.selected_topic())
+                    {
+                        app.input_modal = Some(InputModal {
+                            title: format!("Consume bench '{}' for (e.g. 30s, 5m)", topic.name),
+                            input: "30s".to_string(),
+                            command: InputCommand::ConsumeBench { topic_fqn: topic.fqn.clone() },
+                        })
+                    }
+                }
 
-                            Resource::Subscriptions => {
-                                let topics = pulsar_admin::fetch_topics(
-                                    &app.resources.selected_tenant().unwrap().name,
-                                    &app.resources.selected_namespace().unwrap().name,
-                                    &app.pulsar_admin_cfg,
-                                )
-                                .await;
+                AppEvent::Control(ControlEvent::Bench) => {
+                    if let (Resource::Topics, Some(topic)) =
+                        (&app.active_resource, app.resources.selected_topic())
+                    {
+                        if app.production {
+                            show_error_msg(
+                                app,
+                                "Bench is disabled, cluster is marked as production".to_string(),
+                            );
+                        } else {
+                            app.input_modal = Some(InputModal {
+                                title: format!("Bench '{}' ({})", topic.name, BenchSpec::USAGE),
+                                input: BenchSpec::DEFAULT_INPUT.to_string(),
+                                command: InputCommand::Bench { topic_fqn: topic.fqn.clone() },
+                            })
+                        }
+                    }
+                }
 
-                                match topics {
-                                    Ok(topics) => {
-                                        app.resources.topics.topics = topics;
-                                        app.active_resource = Resource::Topics;
-                                    }
-                                    Err(err) => {
-                                        show_error_msg(
-                                            app,
-                                            format!("Failed to fetch topics :[ {:?}", err),
-                                        );
-                                    }
-                                }
-                            }
+                AppEvent::Control(ControlEvent::EditLimits) => {
+                    if let Resource::Topics = &app.active_resource {
+                        let namespace = app
+                            .resources
+                            .selected_namespace_name()
+                            .expect("namespace must be set")
+                            .to_string();
+                        app.input_modal = Some(InputModal {
+                            title: format!(
+                                "Limits of '{}' (<consumers/topic> <producers/topic> <consumers/sub>, - to unset)",
+                                namespace
+                            ),
+                            input: app.resources.namespaces.limits.to_input(),
+                            command: InputCommand::SetNamespaceLimits {
+                                tenant: app
+                                    .resources
+                                    .selected_tenant_name()
+                                    .expect("tenant must be set")
+                                    .to_string(),
+                                namespace,
+                                cfg: app.pulsar_admin_cfg.clone(),
+                            },
+                        })
+                    }
+                }
 
-                            Resource::Consumers => {
-                                let subscriptions = pulsar_admin::fetch_subs(
-                                    &app.resources.selected_tenant().unwrap().name,
-                                    &app.resources.selected_namespace().unwrap().name,
-                                    &app.resources.selected_topic().unwrap().name,
-                                    &app.pulsar_admin_cfg,
-                                )
-                                .await;
+                AppEvent::Control(ControlEvent::TogglePause) => {
+                    if let (Resource::Listening { .. }, Some(pause)) =
+                        (&app.active_resource, &app.pulsar.active_pause_handle)
+                    {
+                        app.resources.listening.paused = !app.resources.listening.paused;
+                        let _ = pause.send(app.resources.listening.paused);
+                    }
 
-                                match subscriptions {
-                                    Ok(subscriptions) => {
-                                        app.resources.subscriptions.subscriptions = subscriptions;
-                                        app.resources
-                                            .subscriptions
-                                            .subscriptions
-                                            .sort_by(|a, b| a.name.cmp(&b.name));
-                                        app.active_resource = Resource::Subscriptions;
-                                    }
-                                    Err(err) => {
-                                        show_error_msg(
-                                            app,
-                                            format!("Failed to fetch subscriptions :[ {:?}", err),
-                                        );
-                                    }
-                                }
-                            }
+                    if let (Resource::Subscriptions, Some(subscription)) =
+                        (&app.active_resource, app.resources.selected_subscription())
+                    {
+                        let subscription = app
+                            .resources
+                            .subscription_path(subscription)
+                            .expect("topic must be set");
+                        let paused = pulsar_admin::fetch_subscription_paused(
+                            &subscription.tenant,
+                            &subscription.namespace,
+                            &subscription.topic,
+                            &subscription.sub_name,
+                            &app.pulsar_admin_cfg,
+                        )
+                        .await;
 
-                            Resource::Listening { .. } => {
-                                match &app.resources.listening.panel {
-                                    SelectedPanel::Search => {
-                                        app.resources.listening.panel = SelectedPanel::Left;
-                                        app.resources.listening.search = None;
-                                        app.resources.listening.filtered_messages =
-                                            app.resources.listening.messages.clone();
-                                    }
-                                    _ => {
-                                        let topics = pulsar_admin::fetch_topics(
-                                            &app.resources.selected_tenant().unwrap().name,
-                                            &app.resources.selected_namespace().unwrap().name,
-                                            &app.pulsar_admin_cfg,
+                        match paused {
+                            Ok(paused) => {
+                                app.confirmation_modal = Some(ConfirmationModal {
+                                    message: if paused {
+                                        format!("Resume delivery on '{}'?", subscription.sub_name)
+                                    } else {
+                                        format!(
+                                            "Pause delivery on '{}'? Consumers stay connected but receive no messages.",
+                                            subscription.sub_name
                                         )
-                                        .await;
+                                    },
+                                    command: ConfirmedCommand::SetSubscriptionPaused {
+                                        subscription,
+                                        paused: !paused,
+                                        cfg: app.pulsar_admin_cfg.clone(),
+                                    },
+                                })
+                            }
+                            Err(err) => show_error_msg(app, err.to_string()),
+                        }
+                    }
+                }
 
-                                        match topics {
-                                            Ok(topics) => {
-                                                app.resources.topics.topics = topics;
-                                                app.resources
-                                                    .topics
-                                                    .topics
-                                                    .sort_by(|a, b| a.name.cmp(&b.name));
-                                                app.resources.listening.search = None;
-                                                app.resources.listening.panel = SelectedPanel::Left;
-                                                app.active_resource = Resource::Topics;
+                AppEvent::Control(ControlEvent::ExportMessage) => {
+                    if let Resource::Listening { .. } = &app.active_resource {
+                        if let Some(message) = app.resources.selected_message() {
+                            let id = message.meta.message_id.replace(':', "-");
+                            app.input_modal = Some(InputModal {
+                                title: "Export the selected message as JSON to".to_string(),
+                                input: format!("lgm-message-{}.json", id),
+                                command: InputCommand::ExportMessage,
+                            });
+                        }
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::ExportCapture) => {
+                    if let Some(table) = app
+                        .resources
+                        .table_rows(&app.active_resource, app.list_filter.as_ref())
+                    {
+                        app.input_modal = Some(InputModal {
+                            title: format!(
+                                "Export {} {} rows as CSV to",
+                                table.rows.len(),
+                                if app.list_filter.is_some() {
+                                    "filtered"
+                                } else {
+                                    "listed"
+                                }
+                            ),
+                            input: format!(
+                                "lgm-{}.csv",
+                                format!("{:?}", View::of(&app.active_resource)).to_lowercase()
+                            ),
+                            command: InputCommand::ExportTable,
+                        });
+                    } else if let Resource::Listening { .. } = &app.active_resource {
+                        let listening = &app.resources.listening;
+                        if listening.filtered_messages.is_empty() {
+                            show_info_msg(app, "No messages to export.");
+                        } else {
+                            let filtered = listening.search.is_some() || listening.quarantine_only;
+                            app.input_modal = Some(InputModal {
+                                title: format!(
+                                    "Export {} {} messages as NDJSON to",
+                                    listening.filtered_messages.len(),
+                                    if filtered { "filtered" } else { "captured" }
+                                ),
+                                input: "lgm-capture.ndjson".to_string(),
+                                command: InputCommand::ExportCapture,
+                            });
+                        }
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::SaveCapture) => {
+                    if let Resource::Listening { .. } = &app.active_resource {
+                        if app.resources.listening.messages.is_empty() {
+                            show_info_msg(app, "No messages to save.");
+                        } else {
+                            app.input_modal = Some(InputModal {
+                                title: format!(
+                                    "Save {} captured messages to",
+                                    app.resources.listening.messages.len()
+                                ),
+                                input: "lgm-capture.json".to_string(),
+                                command: InputCommand::SaveCapture,
+                            });
+                        }
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::OpenCapture) => {
+                    app.input_modal = Some(InputModal {
+                        title: "Open a saved capture from".to_string(),
+                        input: "lgm-capture.json".to_string(),
+                        command: InputCommand::OpenCapture,
+                    });
+                }
+
+                AppEvent::Control(ControlEvent::FetchMessage) => {
+                    if let (Resource::Topics | Resource::Subscriptions, Some(topic)) =
+                        (&app.active_resource, app.resources.selected_topic())
+                    {
+                        app.input_modal = Some(InputModal {
+                            title: format!(
+                                "Fetch message of '{}' by id (ledgerId:entryId)",
+                                topic.name
+                            ),
+                            input: String::new(),
+                            command: InputCommand::FetchMessage { topic: topic.clone() },
+                        });
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::ExportSession) => {
+                    if app.session.is_empty() {
+                        show_info_msg(app, "Nothing was changed in this session yet.");
+                    } else {
+                        app.input_modal = Some(InputModal {
+                            title: "Export session as a pulsar-admin script to".to_string(),
+                            input: "lgm-session.sh".to_string(),
+                            command: InputCommand::ExportSession,
+                        });
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::ToggleQuarantine) => {
+                    if let Resource::Listening { .. } = &app.active_resource {
+                        let listening = &mut app.resources.listening;
+                        listening.quarantine_only = !listening.quarantine_only;
+                        listening.filter_messages();
+                        listening.cursor = get_new_cursor(&listening.filtered_messages, None);
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::ToggleFollow) => {
+                    if let Resource::Listening { .. } = &app.active_resource {
+                        let listening = &mut app.resources.listening;
+                        listening.follow = !listening.follow;
+                        if listening.follow {
+                            listening.follow_newest();
+                        }
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::ResizeSplit(step)) => {
+                    if let Resource::Listening { .. } = &app.active_resource {
+                        app.ui_state.listening_split = app
+                            .ui_state
+                            .listening_split
+                            .saturating_add_signed(step)
+                            .clamp(MIN_LISTENING_SPLIT, MAX_LISTENING_SPLIT);
+                        if let Err(err) = app.ui_state.save(&app.ui_state_path) {
+                            show_error_msg(app, err.to_string());
+                        }
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::ToggleWrap) => {
+                    if let Resource::Listening { .. } = &app.active_resource {
+                        app.resources.listening.preview_wrap =
+                            !app.resources.listening.preview_wrap;
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::ToggleAutoRefresh) => {
+                    app.auto_refresh = !app.auto_refresh;
+                }
+
+                AppEvent::Control(ControlEvent::NextPage) => {
+                    if let Resource::Consumers = &app.active_resource {
+                        app.resources.consumers.next_page();
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::PreviousPage) => {
+                    if let Resource::Consumers = &app.active_resource {
+                        app.resources.consumers.previous_page();
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::RecoverTopic) => {
+                    if let Resource::Topics = &app.active_resource {
+                        let tenant = app
+                            .resources
+                            .selected_tenant_name()
+                            .expect("tenant must be set")
+                            .to_string();
+                        let namespace = app
+                            .resources
+                            .selected_namespace_name()
+                            .expect("namespace must be set")
+                            .to_string();
+
+                        let deleted = match app.snapshot_dir.as_ref() {
+                            None => Err(anyhow!(
+                                "Deleted topics are detected from stats export snapshots, configure [stats_export] to enable it."
+                            )),
+                            Some(dir) => recovery::latest_snapshot(dir, &tenant, &namespace).map(
+                                |snapshot| {
+                                    snapshot
+                                        .map(|snapshot| {
+                                            recovery::deleted_topics(
+                                                &snapshot,
+                                                &app.resources.topics.topics,
+                                            )
+                                        })
+                                        .unwrap_or_default()
+                                },
+                            ),
+                        };
+
+                        match deleted {
+                            Ok(deleted) if deleted.is_empty() => show_info_msg(
+                                app,
+                                "No topics were deleted since the latest stats export snapshot.",
+                            ),
+                            Ok(deleted) => {
+                                app.input_modal = Some(InputModal {
+                                    title: format!(
+                                        "Recreate deleted topic ({})",
+                                        deleted.iter().map(|topic| &topic.name).join(", ")
+                                    ),
+                                    input: deleted[0].name.clone(),
+                                    command: InputCommand::RecoverTopic {
+                                        tenant,
+                                        namespace,
+                                        deleted,
+                                    },
+                                })
+                            }
+                            Err(err) => show_error_msg(app, err.to_string()),
+                        }
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::Migrate) => {
+                    if let (Resource::Topics, Some(topic)) =
+                        (&app.active_resource, app.resources.selected_topic())
+                    {
+                        app.input_modal = Some(InputModal {
+                            title: format!("Migrate '{}' to (<new topic> [replay])", topic.name),
+                            input: String::new(),
+                            command: InputCommand::MigrateTopic {
+                                tenant: app
+                                    .resources
+                                    .selected_tenant_name()
+                                    .expect("tenant must be set")
+                                    .to_string(),
+                                namespace: app
+                                    .resources
+                                    .selected_namespace_name()
+                                    .expect("namespace must be set")
+                                    .to_string(),
+                                source: topic.name.clone(),
+                            },
+                        })
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::Mark) => {
+                    if let (Resource::Subscriptions, Some(subscription)) =
+                        (&app.active_resource, app.resources.selected_subscription())
+                    {
+                        if let Some(path) = app.resources.subscription_path(subscription) {
+                            if !app.resources.subscriptions.marked.remove(&path) {
+                                app.resources.subscriptions.marked.insert(path);
+                            }
+                        }
+                        app.resources
+                            .cursor_down(&app.active_resource, app.list_filter.as_ref());
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::Compare) => {
+                    if let (Resource::Subscriptions, Some(subscription)) =
+                        (&app.active_resource, app.resources.selected_subscription())
+                    {
+                        let subscription = subscription.name.clone();
+                        match app.resources.subscriptions.compare_with.take() {
+                            Some(first) if first != subscription => {
+                                compare_subscriptions(app, [first, subscription]).await;
+                            }
+                            Some(_) => {}
+                            None => app.resources.subscriptions.compare_with = Some(subscription),
+                        }
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::Refresh) => refresh_view(app),
+
+                AppEvent::Control(ControlEvent::Columns) => {
+                    if app.confirmation_modal.is_none() {
+                        app.action_menu = columns_menu(app, 0);
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::ToggleColumn(index)) => {
+                    if let Some((_, hidden)) = app.resources.columns_mut(&app.active_resource) {
+                        if !hidden.remove(&index) {
+                            hidden.insert(index);
+                        }
+                        // Stays open to toggle a few at once
+                        app.action_menu = columns_menu(app, index - 1);
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::CycleSort) => app
+                    .resources
+                    .change_sort(&app.active_resource, TableSort::next_column),
+
+                AppEvent::Control(ControlEvent::ReverseSort) => app
+                    .resources
+                    .change_sort(&app.active_resource, |sort, _| sort.reverse()),
+
+                AppEvent::Control(ControlEvent::Overview) => {
+                    if let Resource::Tenants = &app.active_resource {
+                        load_overview(app).await;
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::CycleDecoder) => {
+                    if let Resource::Listening { .. } = &app.active_resource {
+                        let decoder = app.resources.listening.decoder.next();
+                        app.resources.listening.set_decoder(decoder);
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::CyclePreview) => {
+                    if let Resource::Listening { .. } = &app.active_resource {
+                        app.resources.listening.preview_mode =
+                            app.resources.listening.preview_mode.next();
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::TogglePin) => {
+                    if let Resource::Listening { .. } = &app.active_resource {
+                        app.resources.listening.toggle_pin();
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::CycleHistogram) => {
+                    if let Resource::Listening { .. } = &app.active_resource {
+                        app.resources.listening.histogram =
+                            app.resources.listening.histogram.next();
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::JumpToCompanion(companion)) => {
+                    if let Resource::Subscriptions = &app.active_resource {
+                        match app.resources.companion_topic_position(companion) {
+                            Some(position) => {
+                                app.resources.topics.cursor = Some(position);
+                                app.resources.subscriptions.cursor = None;
+                                refresh_subscriptions(app).await;
+                            }
+                            None => show_error_msg(
+                                app,
+                                format!(
+                                    "No {} topic found for this subscription.",
+                                    companion.suffix()
+                                ),
+                            ),
+                        }
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::ListenToCompanion(companion)) => {
+                    if let Resource::Subscriptions = &app.active_resource {
+                        match app.resources.companion_topic_position(companion) {
+                            Some(position) => {
+                                app.resources.topics.cursor = Some(position);
+                                if let Some(topic) = app.resources.selected_topic().cloned() {
+                                    ask_start_position(app, topic);
+                                }
+                            }
+                            None => show_error_msg(
+                                app,
+                                format!(
+                                    "No {} topic found for this subscription.",
+                                    companion.suffix()
+                                ),
+                            ),
+                        }
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::Up) => {
+                    app.confirmation_modal = None;
+                    if let SelectedPanel::Right { scroll_offset } =
+                        &mut app.resources.listening.panel
+                    {
+                        *scroll_offset = scroll_offset.saturating_sub(1)
+                    } else {
+                        app.resources
+                            .cursor_up(&app.active_resource, app.list_filter.as_ref())
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::Down) => {
+                    app.confirmation_modal = None;
+                    if let SelectedPanel::Right { scroll_offset } =
+                        &mut app.resources.listening.panel
+                    {
+                        *scroll_offset = scroll_offset.saturating_add(1)
+                    } else {
+                        app.resources
+                            .cursor_down(&app.active_resource, app.list_filter.as_ref())
+                    }
+                }
+
+                AppEvent::Control(
+                    event @ (ControlEvent::PageUp
+                    | ControlEvent::PageDown
+                    | ControlEvent::Top
+                    | ControlEvent::Bottom),
+                ) => {
+                    app.confirmation_modal = None;
+                    let page = page_size(terminal);
+                    let preview_lines = draw::preview_content(&app.resources.listening)
+                        .map(|content| content.lines().count())
+                        .unwrap_or(0);
+
+                    if let SelectedPanel::Right { scroll_offset } =
+                        &mut app.resources.listening.panel
+                    {
+                        *scroll_offset = match event {
+                            ControlEvent::PageUp => scroll_offset.saturating_sub(page as u16),
+                            ControlEvent::PageDown => scroll_offset.saturating_add(page as u16),
+                            ControlEvent::Top => 0,
+                            _ => preview_lines.saturating_sub(page) as u16,
+                        }
+                    } else {
+                        let active_resource = app.active_resource.clone();
+                        let filter = app.list_filter.as_ref();
+                        match event {
+                            ControlEvent::PageUp => {
+                                app.resources
+                                    .step_cursor(&active_resource, filter, page_up(page))
+                            }
+                            ControlEvent::PageDown => {
+                                app.resources
+                                    .step_cursor(&active_resource, filter, page_down(page))
+                            }
+                            ControlEvent::Top => app.resources.step_cursor(
+                                &active_resource,
+                                filter,
+                                |_, col_size| (col_size > 0).then_some(0),
+                            ),
+                            _ => app.resources.step_cursor(
+                                &active_resource,
+                                filter,
+                                |_, col_size| col_size.checked_sub(1),
+                            ),
+                        }
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::BackSpace) => {
+                    if let Resource::Listening { .. } = &app.active_resource {
+                        if matches!(app.resources.listening.panel, SelectedPanel::Search) {
+                            app.resources.listening.search = match &app.resources.listening.search {
+                                Some(current_search) => {
+                                    let len = current_search.len();
+                                    if len > 0 {
+                                        Some(current_search[0..len - 1].to_owned())
+                                    } else {
+                                        Some("".to_string())
+                                    }
+                                }
+                                None => None,
+                            };
+                            schedule_filter(app);
+                        }
+                    }
+                }
+
+                AppEvent::Control(ControlEvent::Esc)
+                    if app.list_filter.is_some() && app.confirmation_modal.is_none() =>
+                {
+                    app.list_filter = None;
+                }
+
+                AppEvent::Control(ControlEvent::Esc)
+                    if app.resources.listening.preview_search.is_some()
+                        && matches!(app.resources.listening.panel, SelectedPanel::Right { .. })
+                        && app.confirmation_modal.is_none() =>
+                {
+                    app.resources.listening.preview_search = None;
+                }
+
+                AppEvent::Control(ControlEvent::Back | ControlEvent::Esc) => {
+                    if app.confirmation_modal.is_some() {
+                        app.confirmation_modal = None;
+                    } else {
+                        match &app.active_resource {
+                            Resource::Tenants => {}
+                            Resource::Namespaces => {
+                                let tenants = app.cache.tenants(&app.pulsar_admin_cfg).await;
+                                match tenants {
+                                    Ok(tenants) => {
+                                        app.resources.tenants.tenants = tenants;
+                                        app.resources
+                                            .tenants
+                                            .tenants
+                                            .sort_by(|a, b| a.name.cmp(&b.name));
+                                        app.show_fetched(Resource::Tenants);
+                                    }
+                                    Err(err) => {
+                                        show_error_msg(
+                                            app,
+                                            format!("Failed to fetch tenants :[ {:?}", err),
+                                        );
+                                    }
+                                }
+                            }
+                            Resource::Topics => {
+                                let namespaces = app
+                                    .cache
+                                    .namespaces(
+                                        &app.resources.selected_tenant().unwrap().name,
+                                        &app.pulsar_admin_cfg,
+                                    )
+                                    .await;
+
+                                match namespaces {
+                                    Ok(namespaces) => {
+                                        app.resources.namespaces.namespaces = namespaces;
+                                        app.resources
+                                            .namespaces
+                                            .namespaces
+                                            .sort_by(|a, b| a.name.cmp(&b.name));
+                                        app.show_fetched(Resource::Namespaces);
+                                        load_namespace_stats(app);
+                                    }
+                                    Err(err) => {
+                                        show_error_msg(
+                                            app,
+                                            format!("Failed to fetch namespaces :[ {:?}", err),
+                                        );
+                                    }
+                                }
+                            }
+
+                            Resource::Subscriptions => {
+                                app.resources.subscriptions.compare_with = None;
+                                let topics = app
+                                    .cache
+                                    .topics(
+                                        &app.resources.selected_tenant().unwrap().name,
+                                        &app.resources.selected_namespace().unwrap().name,
+                                        &app.pulsar_admin_cfg,
+                                    )
+                                    .await;
+
+                                match topics {
+                                    Ok(topics) => {
+                                        app.resources.topics.topics = topics;
+                                        app.resources.topics.sort();
+                                        app.show_fetched(Resource::Topics);
+                                    }
+                                    Err(err) => {
+                                        show_error_msg(
+                                            app,
+                                            format!("Failed to fetch topics :[ {:?}", err),
+                                        );
+                                    }
+                                }
+                            }
+
+                            Resource::Consumers => {
+                                let subscriptions = pulsar_admin::fetch_subs(
+                                    &app.resources.selected_tenant().unwrap().name,
+                                    &app.resources.selected_namespace().unwrap().name,
+                                    &app.resources.selected_topic().unwrap().name,
+                                    &app.pulsar_admin_cfg,
+                                )
+                                .await;
+
+                                match subscriptions {
+                                    Ok(subscriptions) => {
+                                        app.resources.subscriptions.subscriptions = subscriptions;
+                                        app.resources.subscriptions.sort();
+                                        app.show_fetched(Resource::Subscriptions);
+                                    }
+                                    Err(err) => {
+                                        show_error_msg(
+                                            app,
+                                            format!("Failed to fetch subscriptions :[ {:?}", err),
+                                        );
+                                    }
+                                }
+                            }
+
+                            Resource::Comparison => {
+                                refresh_subscriptions(app).await;
+                            }
+
+                            Resource::Overview => {
+                                app.stats_fetch = None;
+                                app.show_fetched(Resource::Tenants);
+                            }
+
+                            Resource::Listening { .. } => {
+                                match &app.resources.listening.panel {
+                                    SelectedPanel::Search => {
+                                        app.resources.listening.panel = SelectedPanel::Left;
+                                        app.resources.listening.search = None;
+                                        app.resources.listening.filter_messages();
+                                    }
+                                    // Recovered captures may be shown before any namespace is picked
+                                    _ if app.resources.selected_namespace().is_none() => {
+                                        app.resources.listening.panel = SelectedPanel::Left;
+                                        app.active_resource = Resource::Namespaces;
+                                    }
+                                    _ => {
+                                        let topics = app
+                                            .cache
+                                            .topics(
+                                                &app.resources.selected_tenant().unwrap().name,
+                                                &app.resources.selected_namespace().unwrap().name,
+                                                &app.pulsar_admin_cfg,
+                                            )
+                                            .await;
+
+                                        match topics {
+                                            Ok(topics) => {
+                                                app.resources.topics.topics = topics;
+                                                app.resources.topics.sort();
+                                                app.resources.listening.search = None;
+                                                app.resources.listening.panel = SelectedPanel::Left;
+                                                app.show_fetched(Resource::Topics);
+
+                                                if let Some(sender) =
+                                                    app.pulsar.active_sub_handle.take()
+                                                {
+                                                    sender.send(()).map_err(|()| { anyhow!( "Failed to send termination singal to subscription")
+                                                })?
+                                                };
+                                            }
+                                            Err(err) => {
+                                                show_error_msg(
+                                                    app,
+                                                    format!("Failed to fetch topics :[ {:?}", err),
+                                                );
+                                            }
+                                        }
+                                    }
+                                };
+                            }
+                        }
+                    }
+                }
+                AppEvent::SubscriptionEvent(event) => {
+                    if let Resource::Listening { .. } = &mut app.active_resource {
+                        let message = app.resources.listening.to_sub_message(event);
+                        if message.decode_error.is_some() {
+                            if let Some(topic) = app.resources.selected_topic() {
+                                *app.resources
+                                    .listening
+                                    .decode_errors
+                                    .entry(topic.name.clone())
+                                    .or_default() += 1;
+                            }
+                        }
+                        app.resources.listening.capture(message);
+
+                        if app.resources.listening.follow {
+                            app.resources.listening.follow_newest();
+                        } else if app.resources.listening.cursor.is_none() {
+                            app.resources.listening.cursor = Some(0)
+                        }
+                    }
+                }
+                AppEvent::Control(ControlEvent::Terminate) => break,
+                AppEvent::Control(ControlEvent::Enter) => {
+                    app.confirmation_modal = None;
+                    match app.active_resource.clone() {
+                        Resource::Tenants => {
+                            if let Some(tenant) = app.resources.selected_tenant() {
+                                let namespaces = app
+                                    .cache
+                                    .namespaces(&tenant.name, &app.pulsar_admin_cfg)
+                                    .await;
+
+                                match namespaces {
+                                    Ok(namespaces) => {
+                                        app.resources.namespaces.cursor = get_new_cursor(
+                                            &namespaces,
+                                            app.resources.namespaces.cursor,
+                                        );
+                                        app.resources.namespaces.namespaces = namespaces;
+                                        app.resources
+                                            .namespaces
+                                            .namespaces
+                                            .sort_by(|a, b| a.name.cmp(&b.name));
+                                        app.show_fetched(Resource::Namespaces);
+                                        load_namespace_stats(app);
+                                    }
+                                    Err(err) => {
+                                        show_error_msg(
+                                            app,
+                                            format!("Failed to fetch namespaces :[ {:?}", err),
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                        Resource::Namespaces => {
+                            if let Some(namespace) = app.resources.selected_namespace() {
+                                let topics = app
+                                    .cache
+                                    .topics(
+                                        &app.resources.selected_tenant().unwrap().name,
+                                        &namespace.name,
+                                        &app.pulsar_admin_cfg,
+                                    )
+                                    .await;
+                                let limits = pulsar_admin::fetch_namespace_limits(
+                                    &app.resources.selected_tenant().unwrap().name,
+                                    &namespace.name,
+                                    &app.pulsar_admin_cfg,
+                                )
+                                .await
+                                .unwrap_or_default();
 
-                                                if let Some(sender) =
-                                                    app.pulsar.active_sub_handle.take()
-                                                {
-                                                    sender.send(()).map_err(|()| { anyhow!( "Failed to send termination singal to subscription")
-                                                })?
-                                                };
-                                            }
-                                            Err(err) => {
-                                                show_error_msg(
-                                                    app,
-                                                    format!("Failed to fetch topics :[ {:?}", err),
-                                                );
-                                            }
-                                        }
+                                match topics {
+                                    Ok(topics) => {
+                                        app.resources.topics.cursor =
+                                            get_new_cursor(&topics, app.resources.topics.cursor);
+                                        app.resources.topics.topics = topics;
+                                        app.resources.topics.sort();
+                                        app.resources.namespaces.limits = limits;
+                                        app.show_fetched(Resource::Topics);
+                                        load_topic_stats(app);
                                     }
-                                };
+                                    Err(err) => {
+                                        show_error_msg(
+                                            app,
+                                            format!("Failed to fetch topics :[ {:?}", err),
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                        Resource::Topics => {
+                            if app.resources.selected_topic().is_some() {
+                                refresh_subscriptions(app).await;
+                            }
+                        }
+                        Resource::Subscriptions => {
+                            if app.resources.selected_subscription().is_some() {
+                                refresh_consumers(app).await;
+                            }
+                        }
+                        Resource::Listening { .. } => {
+                            if let SelectedPanel::Search = &app.resources.listening.panel {
+                                app.resources.listening.panel = SelectedPanel::Left
                             }
                         }
+                        Resource::Consumers => {}
+                        Resource::Comparison | Resource::Overview => {}
+                    }
+                }
+            }
+        }
+
+        apply_due_filter(app);
+
+        // The list filter only applies to the view it was typed in
+        if app
+            .list_filter
+            .as_ref()
+            .is_some_and(|filter| filter.view != View::of(&app.active_resource))
+        {
+            app.list_filter = None;
+        }
+
+        let auto_refresh_due = app.confirmation_modal.is_none()
+            && app.input_modal.is_none()
+            && app.produce_modal.is_none()
+            && app.consume_report.is_none()
+            && app.help_scroll.is_none()
+            && app.action_menu.is_none()
+            && app.auto_refresh
+            && app.fetched_at.elapsed() >= app.auto_refresh_interval
+            && app.auto_refreshed_at.elapsed() >= app.auto_refresh_interval
+            // Slow stats would otherwise be restarted before they ever arrive
+            && app
+                .stats_fetch
+                .as_ref()
+                .is_none_or(|stop| stop.is_closed())
+            && app
+                .view_refresh
+                .as_ref()
+                .is_none_or(|stop| stop.is_closed());
+        if auto_refresh_due && app.data_age().is_some() {
+            app.auto_refreshed_at = Instant::now();
+            refresh_view(app);
+        }
+
+        if let (Resource::Listening { .. }, false) =
+            (&app.active_resource, app.resources.listening.browse_only)
+        {
+            let total = app.resources.listening.messages.len() + app.resources.listening.dropped;
+            if app.journal.is_due(total) {
+                let checkpoint = Checkpoint {
+                    saved_at: Utc::now(),
+                    topic: app
+                        .resources
+                        .selected_topic()
+                        .map(|topic| topic.fqn.clone()),
+                    bookmarks: app.resources.topics.bookmarks.clone(),
+                    messages: app
+                        .resources
+                        .listening
+                        .messages
+                        .iter()
+                        .cloned()
+                        .collect(),
+                };
+                let sender = app.pulsar.sender.clone();
+                app.journal.save(checkpoint, total, sender);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn submit_produce(app: &mut App, modal: ProduceModal) {
+    let [body, key, properties] = &modal.fields;
+    let message = match ProducedMessage::parse(body, key, properties) {
+        Ok(message) => message,
+        Err(err) => {
+            show_error_msg(app, err.to_string());
+            // Keep what was typed so a typo does not cost the whole message
+            app.produce_modal = Some(modal);
+            return;
+        }
+    };
+
+    match produce::publish(app.pulsar.client.clone(), &modal.topic.fqn, &message).await {
+        Ok(message_id) => {
+            app.session.produce(&modal.topic.fqn, &message);
+            show_info_msg(
+                app,
+                &format!("Published {} to '{}'.", message_id, modal.topic.name),
+            );
+        }
+        Err(err) => show_error_msg(app, err.to_string()),
+    }
+}
+
+fn browse_capture(app: &mut App, checkpoint: Checkpoint, label: &str) {
+    if let Some(sender) = app.pulsar.active_sub_handle.take() {
+        let _ = sender.send(());
+    }
+    app.pulsar.active_pause_handle = None;
+
+    for bookmark in checkpoint.bookmarks {
+        if !app.resources.topics.bookmarks.contains(&bookmark) {
+            app.resources.topics.bookmarks.push(bookmark);
+        }
+    }
+
+    let count = checkpoint.messages.len();
+    let listening = &mut app.resources.listening;
+    listening.messages = checkpoint.messages.into_iter().collect();
+    listening.message_sizes = listening
+        .messages
+        .iter()
+        .map(|message| message.size)
+        .counts()
+        .into_iter()
+        .collect();
+    listening.pinned.clear();
+    listening.dropped = 0;
+    listening.search = None;
+    listening.browse_only = true;
+    listening.filter_messages();
+    listening.cursor = get_new_cursor(&listening.filtered_messages, None);
+    app.active_resource = Resource::Listening { sub_name: label.to_string() };
+    show_info_msg(
+        app,
+        &format!(
+            "Showing {} {} messages of '{}' captured until {}.",
+            count,
+            label,
+            checkpoint
+                .topic
+                .unwrap_or("unknown topic".to_string()),
+            checkpoint
+                .saved_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S")
+        ),
+    );
+}
+
+fn copy_to_clipboard(content: String) -> anyhow::Result<()> {
+    ClipboardContext::new()
+        .map_err(|_| anyhow!("Failed to get the clipboard."))
+        .and_then(|mut ctx| {
+            ctx.set_contents(content)
+                .map_err(|_| anyhow!("Failed to copy to clipboard."))
+        })
+}
+
+fn schedule_filter(app: &mut App) {
+    app.filter_due = Some(Instant::now() + FILTER_DEBOUNCE);
+}
+
+fn apply_due_filter(app: &mut App) {
+    if app
+        .filter_due
+        .is_some_and(|due| due <= Instant::now())
+    {
+        app.filter_due = None;
+        sync_list_filter(app);
+        if let Resource::Listening { .. } = &app.active_resource {
+            app.resources.listening.apply_search();
+        }
+    }
+}
+
+fn sync_list_filter(app: &mut App) {
+    if let Some(InputModal {
+        input,
+        command: InputCommand::FilterList { view },
+        ..
+    }) = &app.input_modal
+    {
+        app.list_filter = (!input.is_empty()).then(|| ListFilter::new(*view, input));
+        app.resources
+            .snap_cursor(&app.active_resource, app.list_filter.as_ref());
+    }
+}
+
+async fn run_palette(app: &mut App, input: &str, resumed: bool) {
+    let command = match PaletteCommand::parse(input) {
+        Ok(command) => command,
+        Err(err) => return show_error_msg(app, err.to_string()),
+    };
+    let typed = |text: &str| {
+        text.chars()
+            .map(|char| AppEvent::Input(KeyCode::Char(char)))
+            .collect_vec()
+    };
+
+    let events = match command {
+        PaletteCommand::Jump(path) => {
+            if let Err(err) = open_path(app, &path).await {
+                show_error_msg(app, err.to_string());
+            }
+            return;
+        }
+        PaletteCommand::Quit => vec![AppEvent::Control(ControlEvent::Terminate)],
+        PaletteCommand::Context(None) => vec![AppEvent::Control(ControlEvent::Contexts)],
+        PaletteCommand::Context(Some(name)) => {
+            vec![AppEvent::Control(ControlEvent::SwitchContext(name))]
+        }
+        PaletteCommand::Tenants => {
+            let Some(events) = palette_route(app, View::Tenants, None, input, resumed) else {
+                return;
+            };
+            events
+        }
+        PaletteCommand::Open { parent, name } => {
+            let Some(mut events) = palette_route(app, parent, name.as_deref(), input, resumed)
+            else {
+                return;
+            };
+            events.push(AppEvent::Control(ControlEvent::Enter));
+            events
+        }
+        PaletteCommand::Listen { topic, start } => {
+            let Some(mut events) =
+                palette_route(app, View::Topics, topic.as_deref(), input, resumed)
+            else {
+                return;
+            };
+            events.push(AppEvent::Control(ControlEvent::Subscribe));
+            if !start.is_empty() {
+                events.push(AppEvent::Control(ControlEvent::ClearInput));
+                events.extend(typed(&start));
+            }
+            events.push(AppEvent::Control(ControlEvent::Enter));
+            events
+        }
+        PaletteCommand::Seek(target) => {
+            if !matches!(
+                app.active_resource,
+                Resource::Topics | Resource::Subscriptions
+            ) {
+                return show_error_msg(
+                    app,
+                    "Seek works in the topics and subscriptions views.".to_string(),
+                );
+            }
+
+            let mut events = vec![AppEvent::Control(ControlEvent::SeekTo)];
+            events.extend(typed(&target));
+            events.push(AppEvent::Control(ControlEvent::Enter));
+            events
+        }
+    };
+
+    for event in events {
+        let _ = app.pulsar.sender.send(event);
+    }
+}
+
+// Bookmarks made in the app are saved to the ui state, the ones of the config can only be
+// removed until the next start.
+fn toggle_bookmark(app: &mut App, bookmark: String) {
+    let selected = app.resources.selected_topic().cloned();
+    let topics = &mut app.resources.topics;
+    let message = if topics.bookmarks.contains(&bookmark) {
+        topics
+            .bookmarks
+            .retain(|candidate| *candidate != bookmark);
+        if app.ui_state.bookmarks.contains(&bookmark) {
+            app.ui_state
+                .bookmarks
+                .retain(|candidate| *candidate != bookmark);
+            format!("Removed the bookmark of '{}'.", bookmark)
+        } else {
+            format!(
+                "Removed the bookmark of '{}' until restart, it is set in the config.",
+                bookmark
+            )
+        }
+    } else {
+        topics.bookmarks.push(bookmark.clone());
+        app.ui_state.bookmarks.push(bookmark.clone());
+        format!("Bookmarked '{}'.", bookmark)
+    };
+
+    // Bookmarked topics are kept on top, the cursor follows the selected one
+    topics.sort();
+    if let Some(selected) = selected {
+        topics.cursor = topics
+            .topics
+            .iter()
+            .position(|topic| topic.fqn == selected.fqn);
+    }
+
+    match app.ui_state.save(&app.ui_state_path) {
+        Ok(_) => show_info_msg(app, &message),
+        Err(err) => show_error_msg(app, err.to_string()),
+    }
+}
+
+// Topic stats are fetched this many at a time, namespaces may have thousands of topics.
+pub const TOPIC_STATS_CONCURRENCY: usize = 16;
+const OVERVIEW_CONCURRENCY: usize = 4;
+
+pub fn load_topic_stats(app: &mut App) {
+    let (Some(tenant), Some(namespace)) = (
+        app.resources.selected_tenant_name(),
+        app.resources.selected_namespace_name(),
+    ) else {
+        return;
+    };
+    let tenant = tenant.to_string();
+    let namespace = namespace.to_string();
+    let topics = app
+        .resources
+        .topics
+        .topics
+        .iter()
+        .map(|topic| topic.name.clone())
+        .collect_vec();
+    let cfg = app.pulsar_admin_cfg.clone();
+    let cache = app.cache.clone();
+    let sender = app.pulsar.sender.clone();
+    let (stop, stopped) = oneshot::channel::<()>();
+    app.stats_fetch = Some(stop);
+    app.resources.topics.stats.clear();
+
+    app.tasks
+        .spawn("topic stats", move |mut shutdown| async move {
+            tokio::select! {
+                stats = cache.topics_stats(
+                    &tenant,
+                    &namespace,
+                    topics,
+                    &cfg,
+                    TOPIC_STATS_CONCURRENCY,
+                ) => {
+                    let _ = sender.send(AppEvent::TopicStats {
+                        namespace: format!("{}/{}", tenant, namespace),
+                        stats,
+                    });
+                }
+                _ = stopped => {}
+                _ = shutdown.wait() => {}
+            }
+        });
+}
+
+pub fn load_namespace_stats(app: &mut App) {
+    let Some(tenant) = app.resources.selected_tenant_name() else {
+        return;
+    };
+    let tenant = tenant.to_string();
+    let namespaces = app
+        .resources
+        .namespaces
+        .namespaces
+        .iter()
+        .map(|namespace| namespace.name.clone())
+        .collect_vec();
+    let cfg = app.pulsar_admin_cfg.clone();
+    let cache = app.cache.clone();
+    let sender = app.pulsar.sender.clone();
+    let (stop, stopped) = oneshot::channel::<()>();
+    app.stats_fetch = Some(stop);
+    app.resources.namespaces.stats.clear();
+
+    app.tasks
+        .spawn("namespace stats", move |mut shutdown| async move {
+            let summaries = async {
+                for namespace in namespaces {
+                    let Some((topics, stats)) =
+                        fetch_namespace_stats(&cache, &tenant, &namespace, &cfg).await
+                    else {
+                        continue;
+                    };
+
+                    let summary = NamespaceStats {
+                        topics,
+                        backlog: stats.values().map(|stats| stats.backlog).sum(),
+                    };
+                    let sent = sender.send(AppEvent::NamespaceStats {
+                        tenant: tenant.clone(),
+                        namespace,
+                        stats: summary,
+                    });
+                    if sent.is_err() {
+                        break;
+                    }
+                }
+            };
+
+            tokio::select! {
+                _ = summaries => {}
+                _ = stopped => {}
+                _ = shutdown.wait() => {}
+            }
+        });
+}
+
+// Topic count and topic stats of a namespace, `None` when its topics can't be listed.
+async fn fetch_namespace_stats(
+    cache: &AdminCache,
+    tenant: &str,
+    namespace: &str,
+    cfg: &Configuration,
+) -> Option<(usize, BTreeMap<String, TopicStats>)> {
+    let topics = cache.topics(tenant, namespace, cfg).await.ok()?;
+    let count = topics.len();
+    let stats = cache
+        .topics_stats(
+            tenant,
+            namespace,
+            topics
+                .into_iter()
+                .map(|topic| topic.name)
+                .collect(),
+            cfg,
+            TOPIC_STATS_CONCURRENCY,
+        )
+        .await;
+
+    Some((count, stats))
+}
+
+async fn load_overview(app: &mut App) {
+    let tenant = app
+        .resources
+        .selected_tenant_name()
+        .expect("tenant must be set")
+        .to_string();
+    match app
+        .cache
+        .namespaces(&tenant, &app.pulsar_admin_cfg)
+        .await
+    {
+        Ok(namespaces) => show_overview(app, tenant, namespaces),
+        Err(err) => show_error_msg(app, format!("Failed to fetch namespaces :[ {:?}", err)),
+    }
+}
+
+fn show_overview(app: &mut App, tenant: String, namespaces: Vec<Namespace>) {
+    let namespaces = namespaces
+        .into_iter()
+        .map(|namespace| namespace.name)
+        .sorted()
+        .collect_vec();
+
+    app.resources.overview = Overview {
+        tenant: tenant.clone(),
+        namespaces: namespaces.clone(),
+        ..Overview::default()
+    };
+    app.show_fetched(Resource::Overview);
+
+    let cfg = app.pulsar_admin_cfg.clone();
+    let cache = app.cache.clone();
+    let sender = app.pulsar.sender.clone();
+    let (stop, stopped) = oneshot::channel::<()>();
+    app.stats_fetch = Some(stop);
+
+    app.tasks
+        .spawn("tenant overview", move |mut shutdown| async move {
+            let summaries = futures::stream::iter(namespaces)
+                .map(|namespace| {
+                    let (cache, tenant, cfg) = (&cache, &tenant, &cfg);
+                    async move {
+                        let stats = fetch_namespace_stats(cache, tenant, &namespace, cfg).await;
+                        (namespace, stats)
+                    }
+                })
+                .buffer_unordered(OVERVIEW_CONCURRENCY);
+            let forward = async {
+                futures::pin_mut!(summaries);
+                while let Some((namespace, stats)) = summaries.next().await {
+                    let Some((topics, stats)) = stats else {
+                        continue;
+                    };
+                    let sent = sender.send(AppEvent::OverviewStats {
+                        tenant: tenant.clone(),
+                        namespace,
+                        topics,
+                        stats,
+                    });
+                    if sent.is_err() {
+                        break;
+                    }
+                }
+            };
+
+            tokio::select! {
+                _ = forward => {}
+                _ = stopped => {}
+                _ = shutdown.wait() => {}
+            }
+        });
+}
+
+// The current cluster stays active when the new one can't be reached.
+async fn switch_context(app: &mut App, name: &str) -> anyhow::Result<()> {
+    let (_, context) = app
+        .contexts
+        .iter()
+        .find(|(candidate, _)| candidate == name)
+        .cloned()
+        .ok_or(anyhow!("No context '{}'", name))?;
+
+    let token = auth::auth(&context.auth)
+        .await
+        .map_err(|err| anyhow!("Failed to authenticate to '{}': '{}'", name, err))?;
+    let client = app
+        .connections
+        .pulsar_client(&context.pulsar_url, &context.auth, &token)
+        .await
+        .map_err(|err| anyhow!("Failed to connect to '{}': '{}'", name, err))?;
+    let cfg = app
+        .connections
+        .admin_config(&context.pulsar_admin_url, &context.auth, &token);
+    let cluster_name = pulsar_admin::fetch_clusters(&cfg)
+        .await
+        .map_err(|err| anyhow!("Failed to reach the admin API of '{}': '{}'", name, err))?
+        .first()
+        .cloned()
+        .unwrap_or("unknown cluster".to_string());
+
+    if let Some(probe) = app.probe.take() {
+        probe.stop();
+    }
+    app.stats_fetch = None;
+    app.view_refresh = None;
+    app.cache.clear();
+    app.pulsar.client = client;
+    app.pulsar.token = token;
+    app.pulsar_admin_cfg = cfg;
+    app.cluster_name = cluster_name;
+    app.production = context.production;
+    app.context = name.to_string();
+    app.resources.subscriptions.marked.clear();
+    spawn_token_refresh(app);
+
+    let path = ResourcePath {
+        tenant: context.default_tenant,
+        namespace: context.default_namespace,
+        topic: None,
+        subscription: None,
+    };
+    if let Err(err) = open_path(app, &path).await {
+        // Nothing of the previous cluster may stay listed
+        app.resources.tenants.tenants = app
+            .cache
+            .tenants(&app.pulsar_admin_cfg)
+            .await
+            .unwrap_or_default();
+        app.resources
+            .tenants
+            .tenants
+            .sort_by(|a, b| a.name.cmp(&b.name));
+        app.resources.tenants.cursor = get_new_cursor(&app.resources.tenants.tenants, None);
+        app.show_fetched(Resource::Tenants);
+        return Err(err);
+    }
+
+    Ok(())
+}
+
+async fn open_path(app: &mut App, path: &ResourcePath) -> anyhow::Result<()> {
+    let cfg = app.pulsar_admin_cfg.clone();
+    let mut tenants = app.cache.tenants(&cfg).await?;
+    tenants.sort_by(|a, b| a.name.cmp(&b.name));
+    let tenant_cursor = tenants
+        .iter()
+        .position(|tenant| tenant.name == path.tenant)
+        .ok_or(anyhow!("No tenant '{}'", path.tenant))?;
+    let mut namespaces = app.cache.namespaces(&path.tenant, &cfg).await?;
+    namespaces.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut loaded = None;
+    if let Some(namespace) = &path.namespace {
+        let namespace_cursor = namespaces
+            .iter()
+            .position(|candidate| candidate.name == *namespace)
+            .ok_or(anyhow!("No namespace '{}/{}'", path.tenant, namespace))?;
+        let topics = app
+            .cache
+            .topics(&path.tenant, namespace, &cfg)
+            .await?;
+        let limits = pulsar_admin::fetch_namespace_limits(&path.tenant, namespace, &cfg)
+            .await
+            .unwrap_or_default();
+
+        let subscriptions = match &path.topic {
+            Some(topic) => {
+                if !topics
+                    .iter()
+                    .any(|candidate| candidate.name == *topic)
+                {
+                    return Err(anyhow!(
+                        "No topic '{}/{}/{}'",
+                        path.tenant,
+                        namespace,
+                        topic
+                    ));
+                }
+                let subscriptions =
+                    pulsar_admin::fetch_subs(&path.tenant, namespace, topic, &cfg).await?;
+                Some(subscriptions)
+            }
+            None => None,
+        };
+        loaded = Some((namespace_cursor, topics, limits, subscriptions));
+    }
+
+    if let Resource::Listening { .. } = &app.active_resource {
+        if let Some(sender) = app.pulsar.active_sub_handle.take() {
+            let _ = sender.send(());
+        }
+        app.pulsar.active_pause_handle = None;
+        app.resources.listening.search = None;
+        app.resources.listening.panel = SelectedPanel::Left;
+    }
+    app.resources.tenants.tenants = tenants;
+    app.resources.tenants.cursor = Some(tenant_cursor);
+    app.resources.namespaces.cursor = get_new_cursor(&namespaces, None);
+    app.resources.namespaces.namespaces = namespaces;
+
+    let Some((namespace_cursor, topics, limits, subscriptions)) = loaded else {
+        app.show_fetched(Resource::Namespaces);
+        load_namespace_stats(app);
+        return Ok(());
+    };
+    app.resources.namespaces.cursor = Some(namespace_cursor);
+    app.resources.namespaces.limits = limits;
+    app.resources.topics.topics = topics;
+    app.resources.topics.sort();
+    app.resources.topics.cursor = get_new_cursor(&app.resources.topics.topics, None);
+    load_topic_stats(app);
+
+    let Some(subscriptions) = subscriptions else {
+        app.show_fetched(Resource::Topics);
+        return Ok(());
+    };
+    app.resources.topics.cursor = app
+        .resources
+        .topics
+        .topics
+        .iter()
+        .position(|topic| Some(&topic.name) == path.topic.as_ref());
+    app.resources.subscriptions.compare_with = None;
+    app.resources.subscriptions.subscriptions = subscriptions;
+    app.resources.subscriptions.sort();
+    let subscriptions = &app.resources.subscriptions.subscriptions;
+    app.resources.subscriptions.cursor = path
+        .subscription
+        .as_ref()
+        .and_then(|name| {
+            subscriptions
+                .iter()
+                .position(|subscription| subscription.name == *name)
+        })
+        .or(get_new_cursor(subscriptions, None));
+    app.show_fetched(Resource::Subscriptions);
+
+    Ok(())
+}
+
+// Events walking from the active view to the `target` list. A named item can only be picked
+// once its list is shown, so the walk is queued with the command resumed after it and `None`
+// is returned, as it is when the item is missing.
+fn palette_route(
+    app: &mut App,
+    target: View,
+    name: Option<&str>,
+    input: &str,
+    resumed: bool,
+) -> Option<Vec<AppEvent>> {
+    let (mut events, current) = match View::of(&app.active_resource) {
+        View::Listening => (vec![AppEvent::Control(ControlEvent::Back)], 2),
+        View::Comparison => (vec![AppEvent::Control(ControlEvent::Back)], 3),
+        View::Overview => (vec![AppEvent::Control(ControlEvent::Back)], 0),
+        view => (
+            vec![],
+            palette::depth(view).expect("list views have a depth"),
+        ),
+    };
+    let depth = palette::depth(target).expect("commands walk to list views");
+    let step = if current > depth {
+        ControlEvent::Back
+    } else {
+        ControlEvent::Enter
+    };
+    events.extend((0..current.abs_diff(depth)).map(|_| AppEvent::Control(step.clone())));
+
+    match name {
+        None => Some(events),
+        Some(name) if events.is_empty() => {
+            if app.resources.select_named(target, name) {
+                app.list_filter = None;
+                Some(events)
+            } else {
+                show_error_msg(app, format!("'{}' is not in the list.", name));
+                None
+            }
+        }
+        Some(name) if resumed => {
+            show_error_msg(app, format!("Failed to open the list with '{}'.", name));
+            None
+        }
+        Some(_) => {
+            events.push(AppEvent::Palette {
+                input: input.to_string(),
+                resumed: true,
+            });
+            for event in events {
+                let _ = app.pulsar.sender.send(event);
+            }
+            None
+        }
+    }
+}
+
+async fn submit_input(app: &mut App, modal: InputModal) {
+    match modal.command {
+        InputCommand::Palette => {
+            if !modal.input.trim().is_empty() {
+                run_palette(app, &modal.input, false).await;
+            }
+        }
+        InputCommand::YankProperty => {
+            let key = modal.input.trim();
+            let value = app
+                .resources
+                .selected_message()
+                .and_then(|message| {
+                    message
+                        .property_pairs()
+                        .into_iter()
+                        .find(|(property, _)| *property == key)
+                        .map(|(_, value)| value.to_string())
+                });
+
+            match value.map(copy_to_clipboard) {
+                Some(Ok(_)) => show_info_msg(app, "Property value copied to clipboard."),
+                Some(Err(err)) => show_error_msg(app, err.to_string()),
+                None => show_error_msg(app, format!("Message has no property '{}'", key)),
+            }
+        }
+        InputCommand::SearchPreview => {
+            let listening = &mut app.resources.listening;
+            if modal.input.is_empty() {
+                listening.preview_search = None;
+            } else {
+                match SearchPattern::parse(&modal.input) {
+                    Ok(pattern) => {
+                        listening.preview_search = Some(PreviewSearch {
+                            query: modal.input,
+                            pattern,
+                            current: 0,
+                        });
+                        listening.jump_to_preview_match(0);
+                    }
+                    Err(err) => show_error_msg(app, err.to_string()),
+                }
+            }
+        }
+        InputCommand::FilterList { view } => {
+            app.list_filter =
+                (!modal.input.is_empty()).then(|| ListFilter::new(view, &modal.input));
+            app.resources
+                .snap_cursor(&app.active_resource, app.list_filter.as_ref());
+        }
+        InputCommand::StartListening { topic } => {
+            let input = modal.input.trim();
+            let start = match input {
+                "" | "latest" => Ok(StartPosition::Latest),
+                "earliest" => Ok(StartPosition::Earliest),
+                entries if entries.starts_with('-') => match entries[1..].parse::<u64>() {
+                    Ok(count) => pulsar_admin::fetch_positions_entries_back(
+                        app.resources
+                            .selected_tenant_name()
+                            .expect("tenant must be set"),
+                        app.resources
+                            .selected_namespace_name()
+                            .expect("namespace must be set"),
+                        &topic.name,
+                        count,
+                        &app.pulsar_admin_cfg,
+                        TOPIC_STATS_CONCURRENCY,
+                    )
+                    .await
+                    .map(StartPosition::Entries),
+                    Err(_) => Err(anyhow!("Invalid entry count '{}'", entries)),
+                },
+                time => SeekTarget::parse(time)
+                    .map(|target| StartPosition::Timestamp(target.timestamp_millis() as u64)),
+            };
+
+            match start {
+                Ok(start) => start_listening(app, topic, start).await,
+                Err(err) => show_error_msg(app, err.to_string()),
+            }
+        }
+        InputCommand::MigrateTopic { tenant, namespace, source } => {
+            let mut words = modal.input.split_whitespace();
+            let (Some(target), replay) = (words.next(), words.next()) else {
+                show_error_msg(app, "Expected '<new topic> [replay]'.".to_string());
+                return;
+            };
+
+            let plan = MigrationPlan {
+                tenant,
+                namespace,
+                source,
+                target: target.to_string(),
+                replay: replay == Some("replay"),
+            };
+            app.confirmation_modal = Some(ConfirmationModal {
+                message: format!(
+                    "Create '{}' with the schema and policies of '{}'{}?",
+                    plan.target,
+                    plan.source,
+                    if plan.replay {
+                        " and replay its retained messages"
+                    } else {
+                        ""
+                    }
+                ),
+                command: ConfirmedCommand::MigrateTopic {
+                    plan,
+                    cfg: app.pulsar_admin_cfg.clone(),
+                },
+            })
+        }
+        InputCommand::RecoverTopic { tenant, namespace, deleted } => {
+            let Some(topic) = deleted
+                .into_iter()
+                .find(|topic| topic.name == modal.input.trim())
+            else {
+                show_error_msg(
+                    app,
+                    format!("'{}' is not among the deleted topics.", modal.input.trim()),
+                );
+                return;
+            };
+
+            let recorded = topic.describe();
+            app.confirmation_modal = Some(ConfirmationModal {
+                message: format!(
+                    "Recreate '{}' as recorded at {}{}? Its messages can not be restored.",
+                    topic.name,
+                    topic
+                        .seen_at
+                        .with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M:%S"),
+                    if recorded.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" ({})", recorded)
                     }
+                ),
+                command: ConfirmedCommand::RecoverTopic {
+                    tenant,
+                    namespace,
+                    topic,
+                    cfg: app.pulsar_admin_cfg.clone(),
+                },
+            })
+        }
+        InputCommand::ExportMessage => {
+            let path = modal.input.trim();
+            let topic = app
+                .resources
+                .selected_topic_name()
+                .unwrap_or_default();
+            let result = match app.resources.selected_message() {
+                Some(message) => message_export::write_message(path, topic, message),
+                None => Err(anyhow!("No message selected")),
+            };
+
+            match result {
+                Ok(_) => show_info_msg(app, &format!("Exported message to '{}'.", path)),
+                Err(err) => show_error_msg(app, err.to_string()),
+            }
+        }
+        InputCommand::ExportCapture => {
+            let path = modal.input.trim();
+            let result = message_export::write_ndjson(
+                path,
+                app.resources
+                    .selected_topic_name()
+                    .unwrap_or_default(),
+                &app.resources.listening.filtered_messages,
+            );
+
+            match result {
+                Ok(count) => {
+                    show_info_msg(app, &format!("Exported {} messages to '{}'.", count, path))
+                }
+                Err(err) => show_error_msg(app, err.to_string()),
+            }
+        }
+        InputCommand::SaveCapture => {
+            let path = modal.input.trim();
+            let checkpoint = Checkpoint {
+                saved_at: Utc::now(),
+                topic: app
+                    .resources
+                    .selected_topic()
+                    .map(|topic| topic.fqn.clone()),
+                bookmarks: app.resources.topics.bookmarks.clone(),
+                messages: app
+                    .resources
+                    .listening
+                    .messages
+                    .iter()
+                    .cloned()
+                    .collect(),
+            };
+
+            match journal::save_capture(path, &checkpoint) {
+                Ok(_) => show_info_msg(
+                    app,
+                    &format!(
+                        "Saved {} messages to '{}'.",
+                        checkpoint.messages.len(),
+                        path
+                    ),
+                ),
+                Err(err) => show_error_msg(app, err.to_string()),
+            }
+        }
+        InputCommand::OpenCapture => match journal::load_capture(modal.input.trim()) {
+            Ok(checkpoint) => browse_capture(app, checkpoint, "saved"),
+            Err(err) => show_error_msg(app, err.to_string()),
+        },
+        InputCommand::FetchMessage { topic } => {
+            let events = pulsar_admin::fetch_message_by_id(
+                app.resources
+                    .selected_tenant_name()
+                    .expect("tenant must be set"),
+                app.resources
+                    .selected_namespace_name()
+                    .expect("namespace must be set"),
+                &topic.name,
+                modal.input.trim(),
+                &app.pulsar_admin_cfg,
+            )
+            .await;
+
+            match events {
+                Ok(events) => {
+                    load_decoder(app, &topic).await;
+                    let messages = events
+                        .into_iter()
+                        .map(|event| app.resources.listening.to_sub_message(event))
+                        .collect();
+                    browse_capture(
+                        app,
+                        Checkpoint {
+                            saved_at: Utc::now(),
+                            topic: Some(topic.fqn.clone()),
+                            bookmarks: vec![],
+                            messages,
+                        },
+                        "fetched",
+                    );
+                    show_info_msg(
+                        app,
+                        &format!(
+                            "Fetched message {} of '{}'.",
+                            modal.input.trim(),
+                            topic.name
+                        ),
+                    );
+                }
+                Err(err) => show_error_msg(app, err.to_string()),
+            }
+        }
+        InputCommand::ExportTable => {
+            let path = modal.input.trim();
+            let result = match app
+                .resources
+                .table_rows(&app.active_resource, app.list_filter.as_ref())
+            {
+                Some(table) => table_export::write_csv(path, &table),
+                None => Err(anyhow!("Nothing to export in this view")),
+            };
+
+            match result {
+                Ok(count) => show_info_msg(app, &format!("Exported {} rows to '{}'.", count, path)),
+                Err(err) => show_error_msg(app, err.to_string()),
+            }
+        }
+        InputCommand::ExportSession => {
+            match app
+                .session
+                .export(modal.input.trim(), &app.cluster_name)
+            {
+                Ok(_) => show_info_msg(
+                    app,
+                    &format!("Exported session to '{}'.", modal.input.trim()),
+                ),
+                Err(err) => show_error_msg(app, err.to_string()),
+            }
+        }
+        InputCommand::SetNamespaceLimits { tenant, namespace, cfg } => {
+            let result = match NamespaceLimits::parse(&modal.input) {
+                Ok(limits) => {
+                    pulsar_admin::update_namespace_limits(&tenant, &namespace, &limits, &cfg)
+                        .await
+                        .map(|_| limits)
+                }
+                Err(err) => Err(err),
+            };
+
+            match result {
+                Ok(limits) => {
+                    app.session
+                        .set_namespace_limits(&tenant, &namespace, &limits);
+                    app.resources.namespaces.limits = limits;
+                    show_info_msg(app, &format!("Updated '{}' limits.", namespace));
                 }
-                AppEvent::SubscriptionEvent(event) => {
-                    if let Resource::Listening { .. } = &mut app.active_resource {
-                        app.resources.listening.messages.push(SubMessage {
-                            body: serde_json::to_string(&event.body)?,
-                            properties: event.properties,
-                        });
+                Err(err) => show_error_msg(app, err.to_string()),
+            }
+        }
+        InputCommand::TypedConfirmation { expected, command } => {
+            if modal.input.trim() == expected {
+                let _ = app.pulsar.sender.send(AppEvent::Command(command));
+            } else {
+                show_error_msg(
+                    app,
+                    format!("Typed name didn't match '{}', nothing was done.", expected),
+                );
+            }
+        }
+        InputCommand::Bench { topic_fqn } => match BenchSpec::parse(&modal.input) {
+            Ok(spec) => {
+                app.confirmation_modal = Some(ConfirmationModal {
+                    message: format!("Publish {} to '{}'?", spec, topic_fqn),
+                    command: ConfirmedCommand::Bench { topic_fqn, spec },
+                })
+            }
+            Err(err) => show_error_msg(app, err.to_string()),
+        },
+        InputCommand::ConsumeBench { topic_fqn } => match BenchLength::parse(modal.input.trim()) {
+            Ok(BenchLength::Duration(duration)) => {
+                show_info_msg(
+                    app,
+                    &format!("Consuming '{}' for {}s...", topic_fqn, duration.as_secs()),
+                );
 
-                        app.resources.listening.filter_messages();
+                let pulsar = app.pulsar.client.clone();
+                let sender = app.pulsar.sender.clone();
+                app.tasks.spawn("consume bench", move |shutdown| {
+                    bench::consume(topic_fqn, duration, pulsar, sender, shutdown)
+                });
+            }
+            Ok(BenchLength::Count(_)) => show_error_msg(
+                app,
+                format!(
+                    "Expected a duration like '30s' or '5m', got '{}'",
+                    modal.input.trim()
+                ),
+            ),
+            Err(err) => show_error_msg(app, err.to_string()),
+        },
+        InputCommand::SeekSubscriptions { subscriptions } => {
+            match SeekTarget::parse(&modal.input) {
+                Ok(target) => {
+                    app.confirmation_modal = Some(ConfirmationModal {
+                        message: format!(
+                            "Seek {} subscriptions to {}?",
+                            subscriptions.len(),
+                            target
+                        ),
+                        command: ConfirmedCommand::SeekSubscriptions {
+                            subscriptions,
+                            target,
+                            cfg: app.pulsar_admin_cfg.clone(),
+                        },
+                    })
+                }
+                Err(err) => show_error_msg(app, err.to_string()),
+            }
+        }
+        InputCommand::SeekNamespace { tenant, namespace, topics } => {
+            let Some((pattern, target)) = modal.input.trim().split_once(char::is_whitespace) else {
+                show_error_msg(app, "Expected '<subscription pattern> <time>'.".to_string());
+                return;
+            };
+            let target = match SeekTarget::parse(target) {
+                Ok(target) => target,
+                Err(err) => return show_error_msg(app, err.to_string()),
+            };
 
-                        if app.resources.listening.cursor.is_none() {
-                            app.resources.listening.cursor = Some(0)
-                        }
-                    }
+            let names = join_all(topics.iter().map(|topic| {
+                pulsar_admin::fetch_subscription_names(
+                    &tenant,
+                    &namespace,
+                    topic,
+                    &app.pulsar_admin_cfg,
+                )
+            }))
+            .await;
+            let mut subscriptions = vec![];
+            for (topic, names) in topics.iter().zip(names) {
+                match names {
+                    Ok(names) => subscriptions.extend(
+                        names
+                            .into_iter()
+                            .filter(|name| glob_match(pattern, name))
+                            .map(|sub_name| SubscriptionPath {
+                                tenant: tenant.clone(),
+                                namespace: namespace.clone(),
+                                topic: topic.clone(),
+                                sub_name,
+                            }),
+                    ),
+                    Err(err) => return show_error_msg(app, err.to_string()),
                 }
-                AppEvent::Control(ControlEvent::Terminate) => break,
-                AppEvent::Control(ControlEvent::Enter) => {
-                    app.confirmation_modal = None;
-                    match app.active_resource.clone() {
-                        Resource::Tenants => {
-                            if let Some(tenant) = app.resources.selected_tenant() {
-                                let namespaces = pulsar_admin::fetch_namespaces(
-                                    &tenant.name,
-                                    &app.pulsar_admin_cfg,
-                                )
-                                .await;
+            }
 
-                                match namespaces {
-                                    Ok(namespaces) => {
-                                        app.resources.namespaces.cursor = get_new_cursor(
-                                            &namespaces,
-                                            app.resources.namespaces.cursor,
-                                        );
-                                        app.resources.namespaces.namespaces = namespaces;
-                                        app.resources
-                                            .namespaces
-                                            .namespaces
-                                            .sort_by(|a, b| a.name.cmp(&b.name));
-                                        app.active_resource = Resource::Namespaces;
-                                    }
-                                    Err(err) => {
-                                        show_error_msg(
-                                            app,
-                                            format!("Failed to fetch namespaces :[ {:?}", err),
-                                        );
-                                    }
-                                }
-                            }
-                        }
-                        Resource::Namespaces => {
-                            if let Some(namespace) = app.resources.selected_namespace() {
-                                let topics = pulsar_admin::fetch_topics(
-                                    &app.resources.selected_tenant().unwrap().name,
-                                    &namespace.name,
-                                    &app.pulsar_admin_cfg,
-                                )
-                                .await;
+            if subscriptions.is_empty() {
+                show_info_msg(
+                    app,
+                    &format!("No subscriptions of '{}' match '{}'.", namespace, pattern),
+                );
+                return;
+            }
 
-                                match topics {
-                                    Ok(topics) => {
-                                        app.resources.topics.cursor =
-                                            get_new_cursor(&topics, app.resources.topics.cursor);
-                                        app.resources.topics.topics = topics;
-                                        app.resources
-                                            .topics
-                                            .topics
-                                            .sort_by(|a, b| a.name.cmp(&b.name));
-                                        app.active_resource = Resource::Topics;
-                                    }
-                                    Err(err) => {
-                                        show_error_msg(
-                                            app,
-                                            format!("Failed to fetch topics :[ {:?}", err),
-                                        );
-                                    }
-                                }
-                            }
-                        }
-                        Resource::Topics => {
-                            if let Some(_) = app.resources.selected_topic() {
-                                refresh_subscriptions(app).await;
-                            }
-                        }
-                        Resource::Subscriptions { .. } => {
-                            if let Some(subscription) = app.resources.selected_subscription() {
-                                let consumers = pulsar_admin::fetch_consumers(
-                                    app.resources
-                                        .selected_tenant_name()
-                                        .expect("tenant must be set"),
-                                    app.resources
-                                        .selected_namespace_name()
-                                        .expect("namespace must be set"),
-                                    app.resources
-                                        .selected_topic_name()
-                                        .expect("namespace must be set"),
-                                    subscription.name.as_ref(),
-                                    &app.pulsar_admin_cfg,
-                                )
-                                .await;
+            let topic_count = subscriptions
+                .iter()
+                .map(|path| &path.topic)
+                .unique()
+                .count();
+            request_confirmation(
+                app,
+                ConfirmationModal {
+                    message: format!(
+                        "Seek {} subscriptions matching '{}' on {} topics of '{}' to {}?",
+                        subscriptions.len(),
+                        pattern,
+                        topic_count,
+                        namespace,
+                        target
+                    ),
+                    command: ConfirmedCommand::SeekSubscriptions {
+                        subscriptions,
+                        target,
+                        cfg: app.pulsar_admin_cfg.clone(),
+                    },
+                },
+                namespace,
+            )
+        }
+        InputCommand::SetSubscriptionProperty {
+            tenant,
+            namespace,
+            topic,
+            sub_name,
+            mut properties,
+            cfg,
+        } => {
+            let Some((key, value)) = modal.input.split_once('=') else {
+                show_error_msg(app, "Expected input in 'key=value' format.".to_string());
+                return;
+            };
 
-                                match consumers {
-                                    Ok(consumers) => {
-                                        app.resources.consumers.cursor = get_new_cursor(
-                                            &consumers,
-                                            app.resources.consumers.cursor,
-                                        );
-                                        app.resources.consumers.consumers = consumers;
-                                        app.resources
-                                            .consumers
-                                            .consumers
-                                            .sort_by(|a, b| a.name.cmp(&b.name));
-                                        app.active_resource = Resource::Consumers;
-                                    }
-                                    Err(err) => {
-                                        show_error_msg(
-                                            app,
-                                            format!("Failed to fetch consumers :[ {:?}", err),
-                                        );
-                                    }
-                                }
-                            }
-                        }
-                        Resource::Listening { .. } => {
-                            if let SelectedPanel::Search = &app.resources.listening.panel {
-                                app.resources.listening.panel = SelectedPanel::Left
-                            }
-                        }
-                        Resource::Consumers => {}
-                    }
+            // An empty value removes the property
+            if value.trim().is_empty() {
+                properties.remove(key.trim());
+            } else {
+                properties.insert(key.trim().to_string(), value.trim().to_string());
+            }
+
+            let result = pulsar_admin::update_subscription_properties(
+                &tenant,
+                &namespace,
+                &topic,
+                &sub_name,
+                properties.clone(),
+                &cfg,
+            )
+            .await;
+
+            match result {
+                Ok(_) => {
+                    app.session.update_subscription_properties(
+                        &SubscriptionPath {
+                            tenant,
+                            namespace,
+                            topic,
+                            sub_name,
+                        },
+                        &properties,
+                    );
+                    refresh_subscriptions(app).await;
+                    show_info_msg(app, "Subscription properties updated.");
                 }
+                Err(err) => show_error_msg(app, err.to_string()),
             }
         }
     }
+}
 
-    Ok(())
+fn ask_start_position(app: &mut App, topic: Topic) {
+    app.input_modal = Some(InputModal {
+        title: format!(
+            "Listen to '{}' from (latest, earliest, -<n> entries back per partition (a batch is one entry), 3h, 2024-05-01 14:30)",
+            topic.name
+        ),
+        input: "latest".to_string(),
+        command: InputCommand::StartListening { topic },
+    })
+}
+
+async fn start_listening(app: &mut App, topic: Topic, start: StartPosition) {
+    load_decoder(app, &topic).await;
+
+    let sub_name = format!("lgm_reader_{}", Uuid::new_v4());
+    app.active_resource = Resource::Listening { sub_name: sub_name.clone() };
+    app.resources.listening.cursor = None;
+    app.resources.listening.messages = VecDeque::new();
+    app.resources.listening.message_sizes.clear();
+    app.resources.listening.pinned.clear();
+    app.resources.listening.dropped = 0;
+    app.resources.listening.receipts.clear();
+    app.resources.listening.filtered_messages = vec![];
+    app.resources.listening.search = None;
+    app.resources.listening.paused = false;
+    app.resources.listening.browse_only = false;
+    let new_pulsar = app.pulsar.client.clone();
+    let new_sender = app.pulsar.sender.clone();
+    let (tx, rx) = oneshot::channel::<()>();
+    app.pulsar.active_sub_handle = Some(tx);
+    let (pause_tx, pause_rx) = watch::channel(false);
+    app.pulsar.active_pause_handle = Some(pause_tx);
+    app.tasks
+        .spawn("listener", |shutdown| async move {
+            let _ = pulsar_listener::listen_to_topic(
+                sub_name, topic.fqn, new_sender, new_pulsar, rx, pause_rx, shutdown, start,
+            )
+            .await;
+        });
+}
+
+async fn ensure_probe_topic(
+    tenant: &str,
+    namespace: &str,
+    cfg: &Configuration,
+) -> anyhow::Result<String> {
+    let topics = pulsar_admin::fetch_topics(tenant, namespace, cfg).await?;
+    if !topics
+        .iter()
+        .any(|topic| topic.name == probe::TOPIC)
+    {
+        pulsar_admin::create_topic(tenant, namespace, probe::TOPIC, 0, None, cfg).await?;
+    }
+
+    Ok(format!(
+        "persistent://{}/{}/{}",
+        tenant,
+        namespace,
+        probe::TOPIC
+    ))
+}
+
+async fn load_decoder(app: &mut App, topic: &Topic) {
+    let tenant = app
+        .resources
+        .selected_tenant_name()
+        .expect("tenant must be set");
+    let namespace = app
+        .resources
+        .selected_namespace_name()
+        .expect("namespace must be set");
+    let schema = pulsar_admin::fetch_schema(tenant, namespace, &topic.name, &app.pulsar_admin_cfg)
+        .await
+        .ok();
+    let schema_type = schema.as_ref().and_then(|schema| schema.r#type);
+    let protobuf = app
+        .resources
+        .listening
+        .protobuf_descriptors
+        .get(&format!("{}/{}/{}", tenant, namespace, topic.name))
+        .cloned();
+    // A configured descriptor wins over the registry, bytes topics carry no schema at all
+    app.resources.listening.decoder = match protobuf {
+        Some(_) => Decoder::Protobuf,
+        None => Decoder::from_schema_type(schema_type),
+    };
+    app.resources.listening.schema_definitions = SchemaDefinitions {
+        protobuf,
+        ..SchemaDefinitions::parse(
+            schema_type.as_ref(),
+            schema
+                .as_ref()
+                .and_then(|schema| schema.data.as_deref()),
+        )
+    };
+    app.resources.listening.decoder_overridden = false;
+}
+
+// Cursor onto the item of `items` that `cursor` selected in `previous`, matched by `key`, so a
+// refetch keeps the selection when items come and go.
+fn follow_cursor<A, K: PartialEq>(
+    previous: &[A],
+    items: &[A],
+    cursor: Option<usize>,
+    key: impl Fn(&A) -> K,
+) -> Option<usize> {
+    cursor
+        .and_then(|cursor| previous.get(cursor))
+        .map(&key)
+        .and_then(|selected| {
+            items
+                .iter()
+                .position(|item| key(item) == selected)
+        })
+        .or_else(|| get_new_cursor(items, cursor))
 }
 
 fn get_new_cursor<A>(col: &[A], old_cursor: Option<usize>) -> Option<usize> {
@@ -1068,6 +5538,318 @@ fn get_new_cursor<A>(col: &[A], old_cursor: Option<usize>) -> Option<usize> {
     }
 }
 
+fn columns_menu(app: &mut App, cursor: usize) -> Option<ActionMenu> {
+    let (columns, hidden) = app.resources.columns_mut(&app.active_resource)?;
+    let actions = columns
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(index, column)| Action {
+            key: index.to_string(),
+            label: format!(
+                "[{}] {}",
+                if hidden.contains(&index) { " " } else { "x" },
+                column
+            ),
+            event: ControlEvent::ToggleColumn(index),
+        })
+        .collect();
+
+    Some(ActionMenu {
+        title: "Columns",
+        actions,
+        query: String::new(),
+        cursor,
+        shortcuts: true,
+    })
+}
+
+pub fn spawn_token_refresh(app: &mut App) {
+    app.token_refresh = None;
+    let Some(auth) = app
+        .contexts
+        .iter()
+        .find(|(name, _)| *name == app.context)
+        .map(|(_, context)| context.auth.clone())
+        .filter(Auth::refreshable)
+    else {
+        return;
+    };
+    let Some(expires_at) = app.pulsar.token.expires_at else {
+        return;
+    };
+
+    let (stop, stopped) = oneshot::channel();
+    app.token_refresh = Some(stop);
+    let context = app.context.clone();
+    let sender = app.pulsar.sender.clone();
+    app.tasks.spawn("token refresh", move |shutdown| {
+        token_refresh::run(context, auth, expires_at, sender, stopped, shutdown)
+    });
+}
+
+// Command tokens are handed to the broker client when it is built, so it is built again with the
+// refreshed one. OAuth clients refresh their tokens themselves.
+async fn rebuild_token_cmd_client(app: &mut App, token: &Token) -> anyhow::Result<()> {
+    let Some((_, context)) = app
+        .contexts
+        .iter()
+        .find(|(name, context)| {
+            *name == app.context && matches!(context.auth, Auth::TokenCmd { .. })
+        })
+        .cloned()
+    else {
+        return Ok(());
+    };
+
+    app.pulsar.client = app
+        .connections
+        .rebuild_pulsar_client(&context.pulsar_url, &context.auth, token)
+        .await
+        .map_err(|err| anyhow!("Failed to reconnect with the refreshed token: '{}'", err))?;
+
+    Ok(())
+}
+
+pub enum RefreshedView {
+    Tenants(Vec<Tenant>),
+    Namespaces(Vec<Namespace>),
+    Topics {
+        topics: Vec<Topic>,
+        limits: NamespaceLimits,
+    },
+    Subscriptions(Vec<Subscription>),
+    Consumers(Vec<Consumer>),
+    Comparison(Vec<SubscriptionDetails>),
+    Overview(Vec<Namespace>),
+}
+
+// Refreshes of a view left in the meantime are dropped.
+fn view_path(app: &App) -> String {
+    let resources = &app.resources;
+    let selection = match &app.active_resource {
+        Resource::Tenants | Resource::Listening { .. } => vec![],
+        Resource::Namespaces | Resource::Overview => vec![resources.selected_tenant_name()],
+        Resource::Topics => vec![
+            resources.selected_tenant_name(),
+            resources.selected_namespace_name(),
+        ],
+        Resource::Subscriptions => vec![
+            resources.selected_tenant_name(),
+            resources.selected_namespace_name(),
+            resources.selected_topic_name(),
+        ],
+        Resource::Consumers => vec![
+            resources.selected_tenant_name(),
+            resources.selected_namespace_name(),
+            resources.selected_topic_name(),
+            resources
+                .selected_subscription()
+                .map(|subscription| subscription.name.as_str()),
+        ],
+        Resource::Comparison => vec![
+            resources.selected_tenant_name(),
+            resources.selected_namespace_name(),
+            resources.selected_topic_name(),
+        ]
+        .into_iter()
+        .chain(
+            resources
+                .comparison
+                .subscriptions
+                .iter()
+                .map(|subscription| Some(subscription.name.as_str())),
+        )
+        .collect(),
+    };
+
+    std::iter::once(app.active_resource.to_string())
+        .chain(
+            selection
+                .into_iter()
+                .map(|name| name.unwrap_or_default().to_string()),
+        )
+        .join("/")
+}
+
+fn refresh_view(app: &mut App) {
+    if let Resource::Listening { .. } = app.active_resource {
+        return;
+    }
+
+    let resource = app.active_resource.clone();
+    let path = view_path(app);
+    let tenant = app
+        .resources
+        .selected_tenant_name()
+        .map(str::to_string);
+    let namespace = app
+        .resources
+        .selected_namespace_name()
+        .map(str::to_string);
+    let topic = app
+        .resources
+        .selected_topic_name()
+        .map(str::to_string);
+    let subscription = app
+        .resources
+        .selected_subscription()
+        .map(|subscription| subscription.name.clone());
+    let compared = app
+        .resources
+        .comparison
+        .subscriptions
+        .iter()
+        .map(|subscription| subscription.name.clone())
+        .collect_vec();
+    let cfg = app.pulsar_admin_cfg.clone();
+    let cache = app.cache.clone();
+    let sender = app.pulsar.sender.clone();
+    let (stop, stopped) = oneshot::channel::<()>();
+    app.view_refresh = Some(stop);
+
+    let refresh = async move {
+        let tenant = || tenant.clone().expect("tenant must be set");
+        let namespace = || namespace.clone().expect("namespace must be set");
+        let topic = || topic.clone().expect("topic must be set");
+
+        match resource {
+            Resource::Tenants => {
+                cache.invalidate_tenants();
+                cache
+                    .tenants(&cfg)
+                    .await
+                    .map(RefreshedView::Tenants)
+            }
+            Resource::Namespaces => {
+                cache.invalidate_tenant(&tenant());
+                cache
+                    .namespaces(&tenant(), &cfg)
+                    .await
+                    .map(RefreshedView::Namespaces)
+            }
+            Resource::Topics => {
+                cache.invalidate_namespace(&tenant(), &namespace());
+                let topics = cache
+                    .topics(&tenant(), &namespace(), &cfg)
+                    .await?;
+                let limits = pulsar_admin::fetch_namespace_limits(&tenant(), &namespace(), &cfg)
+                    .await
+                    .unwrap_or_default();
+                Ok(RefreshedView::Topics { topics, limits })
+            }
+            Resource::Subscriptions => {
+                pulsar_admin::fetch_subs(&tenant(), &namespace(), &topic(), &cfg)
+                    .await
+                    .map(RefreshedView::Subscriptions)
+            }
+            Resource::Consumers => pulsar_admin::fetch_consumers(
+                &tenant(),
+                &namespace(),
+                &topic(),
+                subscription
+                    .as_deref()
+                    .expect("subscription must be set"),
+                &cfg,
+            )
+            .await
+            .map(RefreshedView::Consumers),
+            Resource::Comparison => pulsar_admin::fetch_subscriptions_details(
+                &tenant(),
+                &namespace(),
+                &topic(),
+                &compared,
+                &cfg,
+            )
+            .await
+            .map(RefreshedView::Comparison),
+            Resource::Overview => {
+                cache.invalidate_tenant(&tenant());
+                cache
+                    .namespaces(&tenant(), &cfg)
+                    .await
+                    .map(RefreshedView::Overview)
+            }
+            Resource::Listening { .. } => unreachable!("listening is not refreshed"),
+        }
+    };
+
+    app.tasks
+        .spawn("view refresh", move |mut shutdown| async move {
+            tokio::select! {
+                view = refresh => {
+                    let _ = sender.send(AppEvent::ViewRefreshed { path, view });
+                }
+                _ = stopped => {}
+                _ = shutdown.wait() => {}
+            }
+        });
+}
+
+fn show_refreshed_view(app: &mut App, view: RefreshedView) {
+    match view {
+        RefreshedView::Tenants(mut tenants) => {
+            tenants.sort_by(|a, b| a.name.cmp(&b.name));
+            app.resources.tenants.cursor = follow_cursor(
+                &app.resources.tenants.tenants,
+                &tenants,
+                app.resources.tenants.cursor,
+                |tenant| tenant.name.clone(),
+            );
+            app.resources.tenants.tenants = tenants;
+            app.show_fetched(Resource::Tenants);
+        }
+
+        // The previous stats stay shown until the new ones arrive
+        RefreshedView::Namespaces(mut namespaces) => {
+            namespaces.sort_by(|a, b| a.name.cmp(&b.name));
+            app.resources.namespaces.cursor = follow_cursor(
+                &app.resources.namespaces.namespaces,
+                &namespaces,
+                app.resources.namespaces.cursor,
+                |namespace| namespace.name.clone(),
+            );
+            app.resources.namespaces.namespaces = namespaces;
+            app.show_fetched(Resource::Namespaces);
+            let stats = std::mem::take(&mut app.resources.namespaces.stats);
+            load_namespace_stats(app);
+            app.resources.namespaces.stats = stats;
+        }
+
+        RefreshedView::Topics { topics, limits } => {
+            let previous = std::mem::replace(&mut app.resources.topics.topics, topics);
+            app.resources.topics.sort();
+            app.resources.topics.cursor = follow_cursor(
+                &previous,
+                &app.resources.topics.topics,
+                app.resources.topics.cursor,
+                |topic| topic.name.clone(),
+            );
+            app.resources.namespaces.limits = limits;
+            app.show_fetched(Resource::Topics);
+            let stats = std::mem::take(&mut app.resources.topics.stats);
+            load_topic_stats(app);
+            app.resources.topics.stats = stats;
+        }
+
+        RefreshedView::Subscriptions(subscriptions) => show_subscriptions(app, subscriptions),
+        RefreshedView::Consumers(consumers) => show_consumers(app, consumers),
+
+        RefreshedView::Comparison(subscriptions) => {
+            app.resources.comparison.subscriptions = subscriptions;
+            app.show_fetched(Resource::Comparison);
+        }
+
+        RefreshedView::Overview(namespaces) => {
+            let previous = std::mem::take(&mut app.resources.overview);
+            show_overview(app, previous.tenant.clone(), namespaces);
+            let overview = &mut app.resources.overview;
+            overview.namespace_stats = previous.namespace_stats;
+            overview.topic_stats = previous.topic_stats;
+        }
+    }
+}
+
 async fn refresh_subscriptions(app: &mut App) {
     let subscriptions = pulsar_admin::fetch_subs(
         app.resources
@@ -1084,22 +5866,172 @@ async fn refresh_subscriptions(app: &mut App) {
     .await;
 
     match subscriptions {
-        Ok(subscriptions) => {
-            app.resources.subscriptions.cursor =
-                get_new_cursor(&subscriptions, app.resources.subscriptions.cursor);
-            app.resources.subscriptions.subscriptions = subscriptions;
-            app.resources
-                .subscriptions
-                .subscriptions
-                .sort_by(|a, b| a.name.cmp(&b.name));
-            app.active_resource = Resource::Subscriptions;
-        }
+        Ok(subscriptions) => show_subscriptions(app, subscriptions),
         Err(err) => {
             show_error_msg(app, format!("Failed to fetch subscriptions :[ {:?}", err));
         }
     }
 }
 
+fn show_subscriptions(app: &mut App, subscriptions: Vec<Subscription>) {
+    let previous = std::mem::replace(
+        &mut app.resources.subscriptions.subscriptions,
+        subscriptions,
+    );
+    app.resources.subscriptions.sort();
+    app.resources.subscriptions.cursor = follow_cursor(
+        &previous,
+        &app.resources.subscriptions.subscriptions,
+        app.resources.subscriptions.cursor,
+        |subscription| subscription.name.clone(),
+    );
+    app.show_fetched(Resource::Subscriptions);
+}
+
+async fn compare_subscriptions(app: &mut App, sub_names: [String; 2]) {
+    let tenant = app
+        .resources
+        .selected_tenant_name()
+        .expect("tenant must be set")
+        .to_string();
+    let namespace = app
+        .resources
+        .selected_namespace_name()
+        .expect("namespace must be set")
+        .to_string();
+    let topic = app
+        .resources
+        .selected_topic_name()
+        .expect("topic must be set")
+        .to_string();
+
+    let subscriptions = match pulsar_admin::fetch_subscriptions_details(
+        &tenant,
+        &namespace,
+        &topic,
+        &sub_names,
+        &app.pulsar_admin_cfg,
+    )
+    .await
+    {
+        Ok(subscriptions) => subscriptions,
+        Err(err) => {
+            show_error_msg(app, format!("Failed to compare subscriptions :[ {:?}", err));
+            return;
+        }
+    };
+
+    app.resources.comparison.subscriptions = subscriptions;
+    app.show_fetched(Resource::Comparison);
+}
+
+async fn refresh_consumers(app: &mut App) {
+    let consumers = pulsar_admin::fetch_consumers(
+        app.resources
+            .selected_tenant_name()
+            .expect("tenant must be set"),
+        app.resources
+            .selected_namespace_name()
+            .expect("namespace must be set"),
+        app.resources
+            .selected_topic_name()
+            .expect("namespace must be set"),
+        &app.resources
+            .selected_subscription()
+            .expect("subscription must be set")
+            .name,
+        &app.pulsar_admin_cfg,
+    )
+    .await;
+
+    match consumers {
+        Ok(consumers) => show_consumers(app, consumers),
+        Err(err) => {
+            show_error_msg(app, format!("Failed to fetch consumers :[ {:?}", err));
+        }
+    }
+}
+
+fn show_consumers(app: &mut App, consumers: Vec<Consumer>) {
+    let previous = std::mem::replace(&mut app.resources.consumers.consumers, consumers);
+    app.resources.consumers.sort();
+    app.resources.consumers.cursor = follow_cursor(
+        &previous,
+        &app.resources.consumers.consumers,
+        app.resources.consumers.cursor,
+        |consumer| (consumer.name.clone(), consumer.address.clone()),
+    );
+    app.show_fetched(Resource::Consumers);
+}
+
+fn request_confirmation(app: &mut App, modal: ConfirmationModal, resource_name: String) {
+    if app.typed_confirmation {
+        app.input_modal = Some(InputModal {
+            title: format!("{} Type '{}' to confirm", modal.message, resource_name),
+            input: String::new(),
+            command: InputCommand::TypedConfirmation {
+                expected: resource_name,
+                command: modal.command,
+            },
+        })
+    } else {
+        app.confirmation_modal = Some(modal)
+    }
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    match pattern.chars().next() {
+        None => text.is_empty(),
+        Some('*') => {
+            let rest = &pattern[1..];
+            text.char_indices()
+                .map(|(index, _)| index)
+                .chain(std::iter::once(text.len()))
+                .any(|index| glob_match(rest, &text[index..]))
+        }
+        Some(expected) => {
+            let mut chars = text.chars();
+            match chars.next() {
+                Some(char) if expected == '?' || char == expected => {
+                    glob_match(&pattern[expected.len_utf8()..], chars.as_str())
+                }
+                _ => false,
+            }
+        }
+    }
+}
+
+fn show_bulk_summary(
+    app: &mut App,
+    action: &str,
+    results: Vec<(SubscriptionPath, anyhow::Result<()>)>,
+) {
+    let (succeeded, failed): (Vec<_>, Vec<_>) = results
+        .into_iter()
+        .partition(|(_, result)| result.is_ok());
+    let summary = format!(
+        "{} {}/{}: {}.",
+        action,
+        succeeded.len(),
+        succeeded.len() + failed.len(),
+        succeeded.iter().map(|(path, _)| path).join(", ")
+    );
+
+    if failed.is_empty() {
+        show_info_msg(app, &summary);
+    } else {
+        let failures = failed
+            .into_iter()
+            .filter_map(|(path, result)| {
+                result
+                    .err()
+                    .map(|err| format!("{} ({})", path, err))
+            })
+            .join(", ");
+        show_error_msg(app, format!("{} Failed: {}", summary, failures));
+    }
+}
+
 fn show_info_msg(app: &mut App, msg: &str) {
     app.info_to_show = Some(InfoToShow::info(msg.to_string()));
 
@@ -1119,3 +6051,103 @@ fn show_error_msg(app: &mut App, msg: String) {
         sender.send(AppEvent::Command(ConfirmedCommand::CloseInfoMessage))
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: usize, body: &str) -> SubMessage {
+        SubMessage {
+            body: body.to_string(),
+            raw: body.as_bytes().to_vec(),
+            properties: vec![],
+            size: body.len(),
+            decode_error: None,
+            meta: MessageMeta {
+                message_id: format!("1:{}", id),
+                ..MessageMeta::default()
+            },
+        }
+    }
+
+    fn listening(bodies: &[&str]) -> Listening {
+        let mut listening = Listening {
+            messages: VecDeque::new(),
+            pinned: vec![],
+            filtered_messages: vec![],
+            panel: SelectedPanel::Search,
+            cursor: None,
+            search: None,
+            filtered_search: None,
+            compiled_search: None,
+            decoder: Decoder::Json,
+            decoder_overridden: false,
+            schema_definitions: SchemaDefinitions::default(),
+            paused: false,
+            follow: false,
+            preview_wrap: true,
+            max_messages: 100,
+            dropped: 0,
+            timestamp_format: "%H:%M:%S".to_string(),
+            quarantine_only: false,
+            preview_mode: PreviewMode::Decoded,
+            histogram: HistogramWindow::Hidden,
+            preview_search: None,
+            browse_only: false,
+            protobuf_descriptors: BTreeMap::new(),
+            decode_errors: BTreeMap::new(),
+            receipts: VecDeque::new(),
+            message_sizes: BTreeMap::new(),
+        };
+        for (id, body) in bodies.iter().enumerate() {
+            listening.capture(message(id, body));
+        }
+
+        listening
+    }
+
+    fn filtered(listening: &Listening) -> Vec<&str> {
+        listening
+            .filtered_messages
+            .iter()
+            .map(|message| message.body.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn longer_plain_search_narrows_the_filtered_messages() {
+        let mut listening = listening(&["order", "ordered", "refund"]);
+        listening.search = Some("ord".to_string());
+        listening.apply_search();
+        assert_eq!(filtered(&listening), vec!["order", "ordered"]);
+
+        // Narrowing only looks at the filtered messages, an unfiltered one stays out
+        listening
+            .messages
+            .push_back(message(3, "ordered late"));
+        listening.search = Some("order ed".to_string());
+        listening.apply_search();
+        assert_eq!(filtered(&listening), vec!["ordered"]);
+
+        listening.search = Some("order".to_string());
+        listening.apply_search();
+        assert_eq!(
+            filtered(&listening),
+            vec!["order", "ordered", "ordered late"]
+        );
+    }
+
+    #[test]
+    fn non_plain_search_filters_every_message_again() {
+        let mut listening = listening(&["order", "refund"]);
+        listening.search = Some("ord".to_string());
+        listening.apply_search();
+
+        listening
+            .messages
+            .push_back(message(2, "ordered"));
+        listening.search = Some("~ord.*".to_string());
+        listening.apply_search();
+        assert_eq!(filtered(&listening), vec!["order", "ordered"]);
+    }
+}